@@ -0,0 +1,45 @@
+//! Compares evaluating expensive easings directly with evaluating their lookup tables.
+//!
+//! Run with `cargo bench --bench easing_lut`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use leptos_animation::{easing, Easing};
+
+const SAMPLES: usize = 1_000_000;
+
+fn measure(f: impl Fn(f64) -> f64) -> Duration {
+    let start = Instant::now();
+    let mut sum = 0.0;
+    for sample in 0..SAMPLES {
+        sum += f(black_box(sample as f64 / SAMPLES as f64));
+    }
+    black_box(sum);
+    start.elapsed()
+}
+
+fn main() {
+    let easings: [(&str, Easing); 4] = [
+        ("BOUNCE_IN_OUT", easing::BOUNCE_IN_OUT),
+        ("ELASTIC_IN_OUT", easing::ELASTIC_IN_OUT),
+        ("BACK_IN_OUT", easing::BACK_IN_OUT),
+        ("SINE_OUT", easing::SINE_OUT),
+    ];
+
+    for (name, easing) in easings {
+        let lut = easing::lut(easing.clone(), 256);
+        // Warm up caches and the branch predictor before timing
+        measure(|t| easing.ease(t));
+        measure(|t| lut.ease(t));
+
+        let direct = measure(|t| easing.ease(t));
+        let table = measure(|t| lut.ease(t));
+        println!(
+            "{name:>15}: direct {:>8.2?}, lut {:>8.2?} ({:.1}x)",
+            direct,
+            table,
+            direct.as_secs_f64() / table.as_secs_f64()
+        );
+    }
+}
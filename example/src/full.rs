@@ -26,27 +26,37 @@ pub fn Full() -> impl IntoView {
     let (mode, set_mode) = signal(MouseMoveAnimationMode::None);
 
     // Animated derived signals
-    let size = create_animated_signal(
+    let size = AnimatedSignal::new(
         move || AnimationTarget {
             target: target_size.get(),
             duration: duration.get_untracked().into(),
             easing: easing.get_untracked().into(),
             mode: AnimationMode::Start,
+            delay: std::time::Duration::ZERO,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
         },
         |from, to, progress| tween_default(&from.to_pixels(), &to.to_pixels(), progress),
     );
 
-    let rotation = create_animated_signal(
+    let rotation = AnimatedSignal::new(
         move || AnimationTarget {
             target: target_rotation.get(),
             duration: duration.get_untracked().into(),
             easing: easing.get_untracked().into(),
             mode: AnimationMode::Start,
+            delay: std::time::Duration::ZERO,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
         },
         tween_default,
     );
 
-    let position = create_animated_signal(
+    let position = AnimatedSignal::new(
         move || {
             let (target, mode) = target_position.get();
             AnimationTarget {
@@ -59,17 +69,27 @@ pub fn Full() -> impl IntoView {
                     Easing::Elastic => easing::ELASTIC_IN_OUT,
                 },
                 mode,
+                delay: std::time::Duration::ZERO,
+                keyframes: Vec::new(),
+                repeat: RepeatMode::Count(1),
+                on_complete: None,
+                relative: None,
             }
         },
         tween_default,
     );
 
-    let color = create_animated_signal(
+    let color = AnimatedSignal::new(
         move || AnimationTarget {
             target: target_color.get(),
             duration: duration.get_untracked().into(),
             easing: easing.get_untracked().into(),
             mode: AnimationMode::Start,
+            delay: std::time::Duration::ZERO,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
         },
         |from, to, progress| -> Color {
             // Convert to HSV to do the tweening
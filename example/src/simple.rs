@@ -6,7 +6,7 @@ pub fn Simple() -> impl IntoView {
     // Note that the necessary AnimatedContext is initialized in main.rs in this demo
     let (value, set_value) = signal(0.0);
 
-    let animated_value = create_animated_signal(move || value.get().into(), tween_default);
+    let animated_value = AnimatedSignal::new(move || value.get().into(), tween_default);
     let clear = move |_| set_value.set(0.0);
     let decrement = move |_| set_value.update(|value| *value -= 1.0);
     let increment = move |_| set_value.update(|value| *value += 1.0);
@@ -4,9 +4,9 @@ use std::ops::Sub;
 
 #[component]
 pub fn Text() -> impl IntoView {
-    let (text, set_text) = create_signal("");
+    let (text, set_text) = signal("");
 
-    let animated_text = create_animated_signal(
+    let animated_text = AnimatedSignal::new(
         move || text.get().into(),
         |from, to, progress| {
             // Animate between strings by taking the beginning of the to-string
@@ -39,7 +39,7 @@ pub fn Text() -> impl IntoView {
 #[derive(Clone)]
 struct TextString(String);
 
-/// create_animated_signal requires interpolated values to be subtractable to allow for overlapping
+/// AnimatedSignal requires interpolated values to be subtractable to allow for overlapping
 /// animations. This is a very ill-defined operations for strings, so we basically cheat by not providing
 /// a real implementation. This will result in glitches when there are multiple overlapping animations.
 impl Sub for TextString {
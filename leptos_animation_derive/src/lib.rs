@@ -0,0 +1,213 @@
+//! Derive macros for [leptos_animation](https://docs.rs/leptos_animation), re-exported by it with
+//! the `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, Index};
+
+/// Derives `Tween` for a struct of tweenable fields, interpolating every field on its own. The
+/// fields have to interpolate into their own type, as `f64` and `f32` do.
+///
+/// With `#[tween(sub)]` it also derives `Sub` field by field, which additive animations need, for
+/// structs that don't implement it themselves.
+#[proc_macro_derive(Tween, attributes(tween))]
+pub fn derive_tween(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derives a struct named after the struct with `Targets` appended, which has an `AnimationTarget`
+/// in place of every field and implements `FieldTargets` with the struct as its value.
+#[proc_macro_derive(FieldTargets)]
+pub fn derive_field_targets(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_field_targets(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// The members of the fields, such as `x` or `0`
+fn members(fields: &Fields) -> Vec<TokenStream2> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = Index::from(index);
+                quote!(#index)
+            }
+        })
+        .collect()
+}
+
+fn expand_field_targets(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "FieldTargets can only be derived for structs",
+        ));
+    };
+    if data.fields.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "FieldTargets needs a struct with fields to animate",
+        ));
+    }
+
+    let private = quote!(::leptos_animation::fields::__private);
+    let vis = &input.vis;
+    let name = &input.ident;
+    let targets = format_ident!("{}Targets", name);
+    let mut impl_generics = input.generics.clone();
+    for field in &data.fields {
+        let ty = &field.ty;
+        impl_generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ty: ::leptos_animation::fields::Field));
+    }
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let generics = &input.generics;
+
+    let target_fields = data.fields.iter().map(|field| {
+        let vis = &field.vis;
+        let ty = &field.ty;
+        match &field.ident {
+            Some(ident) => quote!(#vis #ident: ::leptos_animation::AnimationTarget<#ty>),
+            None => quote!(#vis ::leptos_animation::AnimationTarget<#ty>),
+        }
+    });
+    let definition = match &data.fields {
+        Fields::Named(_) => {
+            quote!(#vis struct #targets #generics #where_clause { #(#target_fields),* })
+        }
+        _ => quote!(#vis struct #targets #generics (#(#target_fields),*) #where_clause;),
+    };
+
+    let members = members(&data.fields);
+    let values: Vec<_> = (0..members.len())
+        .map(|index| format_ident!("field_{}", index))
+        .collect();
+    let value = match &data.fields {
+        Fields::Named(_) => {
+            quote!(#name { #(#members: #private::Get::get(&*#values)),* })
+        }
+        _ => quote!(#name(#(#private::Get::get(&*#values)),*)),
+    };
+
+    let (impl_generics, _, impl_where_clause) = impl_generics.split_for_impl();
+    Ok(quote! {
+        #[doc = concat!("The animation targets of the fields of [`", stringify!(#name), "`]")]
+        #definition
+
+        impl #impl_generics ::leptos_animation::fields::FieldTargets for #targets #ty_generics
+            #impl_where_clause
+        {
+            type Value = #name #ty_generics;
+
+            fn animate(
+                source: #private::Signal<Self, #private::LocalStorage>,
+            ) -> #private::Signal<Self::Value, #private::LocalStorage> {
+                #(
+                    let #values = ::leptos_animation::fields::animate_field(
+                        source,
+                        |targets: &Self| ::core::clone::Clone::clone(&targets.#members),
+                    );
+                )*
+                #private::Signal::derive_local(move || #value)
+            }
+        }
+    })
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Tween can only be derived for structs",
+        ));
+    };
+
+    let mut sub = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("tween"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("sub") {
+                sub = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown tween option, expected `sub`"))
+            }
+        })?;
+    }
+
+    let name = &input.ident;
+    let mut tween_generics = input.generics.clone();
+    let mut sub_generics = input.generics.clone();
+    for field in &data.fields {
+        let ty = &field.ty;
+        tween_generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ty: ::leptos_animation::tween::Tween<Interpolated = #ty>));
+        sub_generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ty: ::core::ops::Sub<#ty, Output = #ty>));
+    }
+
+    let members = members(&data.fields);
+    let construct = |values: Vec<TokenStream2>| match &data.fields {
+        Fields::Named(_) => quote!(Self { #(#members: #values),* }),
+        Fields::Unnamed(_) => quote!(Self(#(#values),*)),
+        Fields::Unit => quote!(Self),
+    };
+    let tweened = construct(
+        members
+            .iter()
+            .map(|member| {
+                quote!(::leptos_animation::tween::Tween::tween(&self.#member, &other.#member, progress))
+            })
+            .collect(),
+    );
+    let subtracted = construct(
+        members
+            .iter()
+            .map(|member| quote!(self.#member - rhs.#member))
+            .collect(),
+    );
+
+    let (impl_generics, ty_generics, where_clause) = tween_generics.split_for_impl();
+    let (sub_impl_generics, _, sub_where_clause) = sub_generics.split_for_impl();
+    let sub_impl = sub.then(|| {
+        quote! {
+            impl #sub_impl_generics ::core::ops::Sub for #name #ty_generics #sub_where_clause {
+                type Output = Self;
+
+                fn sub(self, rhs: Self) -> Self {
+                    #subtracted
+                }
+            }
+        }
+    });
+    Ok(quote! {
+        impl #impl_generics ::leptos_animation::tween::Tween for #name #ty_generics #where_clause {
+            type Interpolated = Self;
+
+            fn tween(&self, other: &Self, progress: f64) -> Self {
+                #tweened
+            }
+        }
+
+        #sub_impl
+    })
+}
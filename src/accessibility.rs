@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+#[cfg(not(feature = "disable-animations"))]
+use instant::Instant;
+use leptos::prelude::*;
+
+use crate::AnimatedSignal;
+#[cfg(not(feature = "disable-animations"))]
+use crate::AnimationStatus;
+
+impl<T, I: Clone + PartialEq> AnimatedSignal<T, I> {
+    /// Returns a signal that only updates once the animations have settled, with the final value.
+    ///
+    /// Bind this to an `aria-live` region instead of the animated signal itself, so screen readers
+    /// politely announce the final value once instead of every intermediate value of the animation.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (count, set_count) = signal(0.0);
+    /// let animated_count = AnimatedSignal::new(move || count.get().into(), tween_default);
+    /// let announced_count = animated_count.settled();
+    ///
+    /// # let _html =
+    /// view! {
+    ///     <div aria-hidden="true">{move || format!("{:.0}", animated_count.get())}</div>
+    ///     <div aria-live="polite" class="visually-hidden">{move || format!("{:.0}", announced_count.get())}</div>
+    /// }
+    /// # .to_html();
+    /// ```
+    pub fn settled(&self) -> Signal<I, LocalStorage> {
+        self.settled_after(Duration::ZERO)
+    }
+
+    /// Like [`settled()`](AnimatedSignal::settled), but only updates once the value has stayed
+    /// settled for `quiet`. A value that settles again and again in quick succession, such as a
+    /// counter that is clicked repeatedly, is then announced once instead of after every click.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (count, set_count) = signal(0.0);
+    /// let animated_count = AnimatedSignal::new(move || count.get().into(), tween_default);
+    /// let announced_count = animated_count.settled_after(Duration::from_millis(500));
+    /// ```
+    pub fn settled_after(&self, quiet: Duration) -> Signal<I, LocalStorage> {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let context = self.context;
+            let animation_status = self.animation_status;
+            let animated_signal = self.animated_signal;
+            let settled = RwSignal::new_local(animated_signal.get_untracked());
+            // The value that settled last and since when, while it waits for the quiet period
+            let waiting: StoredValue<Option<(I, Instant)>, LocalStorage> =
+                StoredValue::new_local(None);
+
+            Effect::new(move |_| {
+                let value = animated_signal.get();
+                let running = animation_status.with_value(|animation_status| {
+                    matches!(animation_status, AnimationStatus::Running { .. })
+                });
+                if running || settled.with_untracked(|settled| *settled == value) {
+                    waiting.set_value(None);
+                    return;
+                }
+
+                let now = context.real_now();
+                let since = waiting
+                    .with_value(|waiting| match waiting {
+                        Some((waiting, since)) if *waiting == value => Some(*since),
+                        _ => None,
+                    })
+                    .unwrap_or_else(|| {
+                        waiting.set_value(Some((value.clone(), now)));
+                        now
+                    });
+                let remaining = quiet.saturating_sub(now.saturating_duration_since(since));
+                if remaining.is_zero() {
+                    waiting.set_value(None);
+                    settled.set(value);
+                } else {
+                    // Check again once the quiet period has passed
+                    context.animation_frame.track();
+                    context.request_frame_after(remaining);
+                }
+            });
+
+            settled.into()
+        }
+        // Without animations the signal is always settled
+        #[cfg(feature = "disable-animations")]
+        {
+            let _ = quiet;
+            self.animated_signal
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "disable-animations")))]
+mod tests {
+    use super::*;
+    use crate::testing::TestAnimationHarness;
+    use crate::{easing, tween_default};
+
+    #[test]
+    fn settled_values_wait_for_the_quiet_period() {
+        let harness = TestAnimationHarness::new();
+        let (count, set_count) = signal(0.0);
+        let animated: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+            move || (count.get(), Duration::from_millis(100), easing::LINEAR).into(),
+            tween_default,
+        );
+        harness.observe(*animated);
+        let announced = animated.settled_after(Duration::from_millis(300));
+        harness.observe(announced);
+
+        // Clicked again soon after the first animation settled
+        set_count.set(1.0);
+        for _ in 0..4 {
+            harness.step_by(Duration::from_millis(50));
+        }
+        assert_eq!(announced.get_untracked(), 0.0);
+        set_count.set(2.0);
+        for _ in 0..4 {
+            harness.step_by(Duration::from_millis(50));
+        }
+        assert_eq!(announced.get_untracked(), 0.0);
+
+        assert!(harness.run_until_settled() >= Duration::from_millis(200));
+        assert_eq!(announced.get_untracked(), 2.0);
+        assert!(!harness.is_animating());
+    }
+}
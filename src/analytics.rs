@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use instant::Instant;
+use leptos::prelude::*;
+
+use crate::{AnimationContext, AnimationContextState};
+
+/// The interval between frames of a 60fps display, used to detect dropped frames
+const FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
+/// Counters of everything the animations of an [`AnimationContext`] did since it was created or
+/// since the counters were last reset. They are cheap enough to always be enabled, so they can
+/// be forwarded to the telemetry of an application in production.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::{AnimationContext, AnimatedSignal, tween_default};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let context = AnimationContext::provide();
+/// let (x, set_x) = signal(0.0);
+/// let animated_x = AnimatedSignal::new(move || x.get().into(), tween_default);
+///
+/// // Report the motion health every now and then
+/// let stats = context.stats();
+/// assert_eq!(stats.started, 0);
+/// context.reset_stats();
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct AnimationStats {
+    /// The number of animations that were started
+    pub started: u64,
+
+    /// The number of animations that ran until their end
+    pub completed: u64,
+
+    /// The number of animations that were stopped before their end, because their signal
+    /// snapped to a new target or was disposed
+    pub cancelled: u64,
+
+    /// The number of frames that were skipped while animations were running, assuming a 60fps
+    /// display. A frame that took 50ms counts as two dropped frames.
+    pub dropped_frames: u64,
+
+    /// The number of frames that were requested from the [`Scheduler`](crate::scheduler::Scheduler).
+    /// It stays the same while nothing animates, see [`AnimationContext::requested_frame_within()`].
+    pub frame_requests: u64,
+}
+
+#[derive(Default)]
+pub(crate) struct StatsState {
+    stats: AnimationStats,
+    last_frame: Option<Instant>,
+    /// The real time of the latest frame request, which resetting the counters keeps
+    last_request: Option<Instant>,
+}
+
+impl AnimationContext {
+    /// The counters of this context, see [`AnimationStats`]
+    pub fn stats(&self) -> AnimationStats {
+        self.stats.with_value(|state| state.stats)
+    }
+
+    /// Sets all counters of this context back to zero
+    pub fn reset_stats(&self) {
+        self.stats
+            .update_value(|state| state.stats = AnimationStats::default());
+    }
+
+    /// Whether this context requested an animation frame within the last `duration`, or is still
+    /// waiting for one. A page whose animations have all settled requests no frames at all, so
+    /// the device can sleep. Checking this after the page has been static for a while catches
+    /// anything that keeps the animation loop alive, which drains the battery of phones.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # use leptos_animation::testing::TestAnimationHarness;
+    /// let harness = TestAnimationHarness::new();
+    /// let context = harness.context();
+    /// let (x, set_x) = signal(0.0);
+    /// let animated_x: AnimatedSignal<f64, f64> = AnimatedSignal::new(move || x.get().into(), tween_default);
+    /// harness.observe(*animated_x);
+    ///
+    /// // Nothing moves, so no frames are requested however long the page stays open
+    /// harness.step_by(Duration::from_secs(5));
+    /// assert!(!context.requested_frame_within(Duration::from_secs(5)));
+    /// assert_eq!(context.stats().frame_requests, 0);
+    ///
+    /// // Once an animation has settled, the context goes back to sleep
+    /// set_x.set(1.0);
+    /// harness.run_until_settled();
+    /// let requests = context.stats().frame_requests;
+    /// harness.step_by(Duration::from_secs(5));
+    /// assert!(!context.requested_frame_within(Duration::from_secs(1)));
+    /// assert_eq!(context.stats().frame_requests, requests);
+    /// ```
+    pub fn requested_frame_within(&self, duration: Duration) -> bool {
+        let now = self.real_now();
+        let waiting = self
+            .state
+            .try_with_value(|state| {
+                !matches!(state, AnimationContextState::NoAnimationFrameRequested)
+            })
+            .unwrap_or_default();
+        waiting
+            || self.stats.with_value(|state| {
+                state
+                    .last_request
+                    .is_some_and(|at| now.saturating_duration_since(at) <= duration)
+            })
+    }
+
+    /// Counts a frame that was requested from the scheduler
+    pub(crate) fn count_frame_request(&self) {
+        let now = self.real_now();
+        self.stats.try_update_value(|state| {
+            state.stats.frame_requests += 1;
+            state.last_request = Some(now);
+        });
+    }
+
+    /// Counts animations that were started, completed or cancelled
+    #[cfg(not(feature = "disable-animations"))]
+    pub(crate) fn count_animations(&self, started: usize, completed: usize, cancelled: usize) {
+        if started == 0 && completed == 0 && cancelled == 0 {
+            return;
+        }
+        self.stats.try_update_value(|state| {
+            state.stats.started += started as u64;
+            state.stats.completed += completed as u64;
+            state.stats.cancelled += cancelled as u64;
+        });
+    }
+
+    /// Counts the frames that were dropped since the previous frame
+    pub(crate) fn count_dropped_frames(&self, consecutive: bool) {
+        let now = self.real_now();
+        self.stats.update_value(|state| {
+            let last_frame = state.last_frame.replace(now);
+            if let (true, Some(last_frame)) = (consecutive, last_frame) {
+                let frames = now.saturating_duration_since(last_frame).as_secs_f64()
+                    / FRAME_INTERVAL.as_secs_f64();
+                state.stats.dropped_frames += (frames.round() as u64).saturating_sub(1);
+            }
+        });
+    }
+}
@@ -0,0 +1,90 @@
+//! Tweens for rotations, which turn the shorter way around instead of spinning across the whole
+//! circle when an angle crosses from 359° to 0°.
+
+use std::f64::consts::TAU;
+
+/// How the angles produced by the angle tweens are written
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Wrap {
+    /// Continue from the start angle without wrapping, so 350° to 10° passes through 360° rather
+    /// than jumping back to 0°. This is the default, it keeps the values of CSS `rotate()`
+    /// transforms continuous.
+    #[default]
+    Continuous,
+
+    /// Wrap the angles into one turn, 0° - 360° or 0 - 2π, for compass headings and other readouts
+    Normalized,
+}
+
+/// The angle at `progress` of the way from `from` to `to`, turning by at most half of `turn`
+fn tween_angle(from: f64, to: f64, progress: f64, turn: f64, wrap: Wrap) -> f64 {
+    let half = turn / 2.0;
+    let difference = (to - from + half).rem_euclid(turn) - half;
+    let angle = from + difference * progress;
+    match wrap {
+        Wrap::Continuous => angle,
+        Wrap::Normalized => angle.rem_euclid(turn),
+    }
+}
+
+/// Interpolates between two angles in degrees along the shorter way around the circle, with the
+/// angles in between continuing from `from`, see [`tween_angle_deg_with()`] to wrap them instead.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::angle::tween_angle_deg;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// assert_eq!(tween_angle_deg(&350.0, &10.0, 0.5), 360.0);
+/// assert_eq!(tween_angle_deg(&10.0, &350.0, 0.5), 0.0);
+///
+/// // A compass needle that turns through north instead of all the way around through south
+/// let (heading, set_heading) = signal(350.0);
+/// let needle = AnimatedSignal::new(move || heading.get().into(), tween_angle_deg);
+/// set_heading.set(10.0);
+/// # let _html =
+/// view! { <div class="needle" style:transform=move || format!("rotate({}deg)", needle.get())></div> }
+/// # .to_html();
+/// ```
+pub fn tween_angle_deg(from: &f64, to: &f64, progress: f64) -> f64 {
+    tween_angle_deg_with(from, to, progress, Wrap::Continuous)
+}
+
+/// Interpolates between two angles in degrees along the shorter way around the circle, written as
+/// `wrap` says. A closure that picks the wrap can be passed to
+/// [`AnimatedSignal::new()`](crate::AnimatedSignal::new) as the tween function.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::angle::{tween_angle_deg_with, Wrap};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// assert_eq!(tween_angle_deg_with(&350.0, &20.0, 0.5, Wrap::Normalized), 5.0);
+///
+/// // A heading readout that never shows more than 359°
+/// let (heading, set_heading) = signal(350.0);
+/// let readout = AnimatedSignal::new(
+///     move || heading.get().into(),
+///     |from, to, progress| tween_angle_deg_with(from, to, progress, Wrap::Normalized),
+/// );
+/// ```
+pub fn tween_angle_deg_with(from: &f64, to: &f64, progress: f64, wrap: Wrap) -> f64 {
+    tween_angle(*from, *to, progress, 360.0, wrap)
+}
+
+/// Interpolates between two angles in radians along the shorter way around the circle, with the
+/// angles in between continuing from `from`, see [`tween_angle_rad_with()`] to wrap them instead.
+/// ```
+/// # use std::f64::consts::PI;
+/// # use leptos_animation::angle::tween_angle_rad;
+/// // From just below a full turn to just above zero through 2π
+/// let halfway = tween_angle_rad(&(1.9 * PI), &(0.1 * PI), 0.5);
+/// assert!((halfway - 2.0 * PI).abs() < 1e-9);
+/// ```
+pub fn tween_angle_rad(from: &f64, to: &f64, progress: f64) -> f64 {
+    tween_angle_rad_with(from, to, progress, Wrap::Continuous)
+}
+
+/// Interpolates between two angles in radians along the shorter way around the circle, written as
+/// `wrap` says, see [`tween_angle_deg_with()`]
+pub fn tween_angle_rad_with(from: &f64, to: &f64, progress: f64, wrap: Wrap) -> f64 {
+    tween_angle(*from, *to, progress, TAU, wrap)
+}
@@ -1,10 +1,15 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::Add;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use crate::{easing::SINE_OUT, AnimationMode, AnimationTarget, Easing};
+use leptos::prelude::*;
 
-const DEFAULT_MODE: AnimationMode = AnimationMode::Start;
-const DEFAULT_DURATION: Duration = Duration::new(0, 500 * 1000 * 1000);
-const DEFAULT_EASING: Easing = SINE_OUT;
+use crate::{easing::SINE_OUT, AnimationMode, AnimationTarget, Easing, Keyframe, RepeatMode};
+
+pub(crate) const DEFAULT_MODE: AnimationMode = AnimationMode::Start;
+pub(crate) const DEFAULT_DURATION: Duration = Duration::new(0, 500 * 1000 * 1000);
+pub(crate) const DEFAULT_EASING: Easing = SINE_OUT;
 
 impl<T: Default> Default for AnimationTarget<T> {
     fn default() -> Self {
@@ -12,7 +17,12 @@ impl<T: Default> Default for AnimationTarget<T> {
             target: T::default(),
             mode: DEFAULT_MODE,
             duration: DEFAULT_DURATION,
-            easing: DEFAULT_EASING
+            easing: DEFAULT_EASING,
+            delay: Duration::ZERO,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
         }
     }
 }
@@ -24,6 +34,11 @@ impl<T> From<T> for AnimationTarget<T> {
             mode: DEFAULT_MODE,
             duration: DEFAULT_DURATION,
             easing: DEFAULT_EASING,
+            delay: Duration::ZERO,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
         }
     }
 }
@@ -37,6 +52,11 @@ impl<T> From<(T, Duration)> for AnimationTarget<T> {
             mode: DEFAULT_MODE,
             duration,
             easing: DEFAULT_EASING,
+            delay: Duration::ZERO,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
         }
     }
 }
@@ -48,6 +68,11 @@ impl<T> From<(T, Easing)> for AnimationTarget<T> {
             mode: DEFAULT_MODE,
             duration: DEFAULT_DURATION,
             easing,
+            delay: Duration::ZERO,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
         }
     }
 }
@@ -59,6 +84,11 @@ impl<T> From<(T, AnimationMode)> for AnimationTarget<T> {
             mode,
             duration: DEFAULT_DURATION,
             easing: DEFAULT_EASING,
+            delay: Duration::ZERO,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
         }
     }
 }
@@ -70,6 +100,11 @@ impl<T> From<(T, Duration, Easing)> for AnimationTarget<T> {
             mode: DEFAULT_MODE,
             duration,
             easing,
+            delay: Duration::ZERO,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
         }
     }
 }
@@ -81,6 +116,11 @@ impl<T> From<(T, Easing, AnimationMode)> for AnimationTarget<T> {
             mode,
             duration: DEFAULT_DURATION,
             easing,
+            delay: Duration::ZERO,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
         }
     }
 }
@@ -92,6 +132,11 @@ impl<T> From<(T, Duration, AnimationMode)> for AnimationTarget<T> {
             mode,
             duration,
             easing: DEFAULT_EASING,
+            delay: Duration::ZERO,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
         }
     }
 }
@@ -103,6 +148,314 @@ impl<T> From<(T, Duration, Easing, AnimationMode)> for AnimationTarget<T> {
             mode,
             duration,
             easing,
+            delay: Duration::ZERO,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
+        }
+    }
+}
+
+// A second duration after the first one is the delay
+
+impl<T> From<(T, Duration, Duration)> for AnimationTarget<T> {
+    fn from((target, duration, delay): (T, Duration, Duration)) -> Self {
+        AnimationTarget {
+            target,
+            mode: DEFAULT_MODE,
+            duration,
+            easing: DEFAULT_EASING,
+            delay,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
+        }
+    }
+}
+
+impl<T> From<(T, Duration, Duration, Easing)> for AnimationTarget<T> {
+    fn from((target, duration, delay, easing): (T, Duration, Duration, Easing)) -> Self {
+        AnimationTarget {
+            target,
+            mode: DEFAULT_MODE,
+            duration,
+            easing,
+            delay,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
+        }
+    }
+}
+
+impl<T> From<(T, Duration, Duration, AnimationMode)> for AnimationTarget<T> {
+    fn from((target, duration, delay, mode): (T, Duration, Duration, AnimationMode)) -> Self {
+        AnimationTarget {
+            target,
+            mode,
+            duration,
+            easing: DEFAULT_EASING,
+            delay,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
+        }
+    }
+}
+
+impl<T> From<(T, Duration, Duration, Easing, AnimationMode)> for AnimationTarget<T> {
+    fn from(
+        (target, duration, delay, easing, mode): (T, Duration, Duration, Easing, AnimationMode),
+    ) -> Self {
+        AnimationTarget {
+            target,
+            mode,
+            duration,
+            easing,
+            delay,
+            keyframes: Vec::new(),
+            repeat: RepeatMode::Count(1),
+            on_complete: None,
+            relative: None,
+        }
+    }
+}
+
+impl<T: Clone + Add<T, Output = T>> AnimationTarget<T> {
+    /// A target that moves the value by `delta` from where it settles when the target is applied,
+    /// instead of towards an absolute value. Keyframes added to it are relative as well.
+    ///
+    /// As every target starts another animation by default, relative targets that arrive while
+    /// the previous ones are still playing add up, so the value ends up moved by all of them
+    /// together. This suits nudges such as moving a selection with the arrow keys, without keeping
+    /// track of the absolute position. The first target of a source is the initial value of the
+    /// signal and is used as is.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (nudge, set_nudge) = signal(AnimationTarget::from(100.0));
+    /// let x: AnimatedSignal<f64, f64> = AnimatedSignal::new(nudge, tween_default);
+    ///
+    /// // Pressing the right arrow key twice moves the value to 120
+    /// set_nudge.set(AnimationTarget::relative(10.0));
+    /// set_nudge.set(AnimationTarget::relative(10.0));
+    /// ```
+    pub fn relative(delta: T) -> Self {
+        AnimationTarget {
+            relative: Some(|settled, delta| settled.clone() + delta.clone()),
+            ..AnimationTarget::from(delta)
+        }
+    }
+}
+
+impl<T> AnimationTarget<T> {
+    /// Adds a value for the animation to pass through at `offset` (from 0.0 to 1.0) of its duration.
+    /// The `easing` applies to the segment that ends at this keyframe, see [`Keyframe`].
+    ///
+    /// Keyframes can be added in any order, they are kept sorted by their offset. Offsets outside
+    /// of 0.0 - 1.0 are clamped into it and a NaN offset counts as 0.0. A keyframe at the same
+    /// offset as an earlier one comes after it, so the value jumps from the one to the other.
+    /// ```
+    /// # use leptos_animation::*;
+    /// let target = AnimationTarget::from(100.0)
+    ///     .keyframe(0.75, 80.0, easing::LINEAR)
+    ///     .keyframe(0.25, 20.0, easing::LINEAR)
+    ///     .keyframe(1.5, 90.0, easing::LINEAR);
+    /// let offsets: Vec<f64> = target.keyframes.iter().map(|keyframe| keyframe.offset).collect();
+    /// assert_eq!(offsets, [0.25, 0.75, 1.0]);
+    /// ```
+    pub fn keyframe(mut self, offset: f64, value: T, easing: Easing) -> Self {
+        let offset = match offset.is_nan() {
+            true => 0.0,
+            false => offset.clamp(0.0, 1.0),
+        };
+        let index = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.offset <= offset);
+        self.keyframes.insert(
+            index,
+            Keyframe {
+                offset,
+                value,
+                easing,
+            },
+        );
+        self
+    }
+
+    /// Sets how often the animation plays, see [`RepeatMode`]
+    pub fn repeat(mut self, repeat: RepeatMode) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Runs `callback` once the animation started for this target has finished, for example to
+    /// remove an element after it has faded out. It runs during the animation frame in which the
+    /// animation ends, after the signal has been updated.
+    ///
+    /// The callback does not run if the animation never finishes: when it is cut short by a snap,
+    /// when its signal is disposed, when it repeats infinitely or when another target replaces it
+    /// (the callback of the replacing target runs instead). Targets that don't start an
+    /// animation also don't run their callback.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (visible, set_visible) = signal(true);
+    /// let (mounted, set_mounted) = signal(true);
+    ///
+    /// let opacity = AnimatedSignal::new(
+    ///     move || match visible.get() {
+    ///         true => AnimationTarget::from(1.0),
+    ///         false => AnimationTarget::from(0.0).on_complete(move || set_mounted.set(false)),
+    ///     },
+    ///     tween_default,
+    /// );
+    /// ```
+    pub fn on_complete(mut self, callback: impl FnOnce() + Send + 'static) -> Self {
+        self.on_complete = Some(OnComplete::new(callback));
+        self
+    }
+}
+
+/// A callback that runs once when an animation finishes, see [`AnimationTarget::on_complete()`].
+/// Clones share the callback, so it only runs once, even if the target was cloned.
+#[derive(Clone)]
+pub struct OnComplete(Arc<Mutex<Option<Callback>>>);
+
+type Callback = Box<dyn FnOnce() + Send>;
+
+impl OnComplete {
+    /// Wraps `callback`
+    pub fn new(callback: impl FnOnce() + Send + 'static) -> OnComplete {
+        OnComplete(Arc::new(Mutex::new(Some(Box::new(callback)))))
+    }
+
+    /// Whether both are the same callback, or both are none
+    pub(crate) fn same(a: &Option<OnComplete>, b: &Option<OnComplete>) -> bool {
+        match (a, b) {
+            (Some(a), Some(b)) => Arc::ptr_eq(&a.0, &b.0),
+            (a, b) => a.is_none() && b.is_none(),
+        }
+    }
+
+    /// Runs the callback unless it already ran
+    #[cfg(not(feature = "disable-animations"))]
+    pub(crate) fn call(&self) {
+        let callback = self.0.lock().ok().and_then(|mut callback| callback.take());
+        if let Some(callback) = callback {
+            callback();
         }
     }
 }
+
+impl Debug for OnComplete {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("OnComplete")
+    }
+}
+
+// Allow users to pass either a closure or any kind of signal as the source of an animated signal
+
+/// Marker for [`AnimationSource`] implemented by closures
+#[doc(hidden)]
+pub struct FnSource;
+
+/// Marker for [`AnimationSource`] implemented by thread safe signals
+#[doc(hidden)]
+pub struct SignalSource;
+
+/// Marker for [`AnimationSource`] implemented by local signals
+#[doc(hidden)]
+pub struct LocalSignalSource;
+
+/// Something that produces the targets for an [`AnimatedSignal`](crate::AnimatedSignal): a closure
+/// returning an `AnimationTarget`, or any signal of `AnimationTarget`s such as a `ReadSignal`, `Memo`
+/// or `Signal`. Passing a signal directly avoids wrapping it in another closure that only reads it.
+/// Read and write signals and local signals are used as they are, thread safe memos and signals are
+/// read through a derived signal that forwards their value.
+///
+/// The `Marker` type only serves to keep the implementations apart and is always inferred.
+pub trait AnimationSource<T: 'static, Marker> {
+    /// Converts the source into a signal
+    fn into_signal(self) -> Signal<AnimationTarget<T>, LocalStorage>;
+}
+
+impl<T, F> AnimationSource<T, FnSource> for F
+where
+    T: 'static,
+    F: Fn() -> AnimationTarget<T> + 'static,
+{
+    fn into_signal(self) -> Signal<AnimationTarget<T>, LocalStorage> {
+        Signal::derive_local(self)
+    }
+}
+
+impl<T: Send + Sync + 'static> AnimationSource<T, SignalSource> for ReadSignal<AnimationTarget<T>> {
+    fn into_signal(self) -> Signal<AnimationTarget<T>, LocalStorage> {
+        ArcReadSignal::from(self).into()
+    }
+}
+
+impl<T: Send + Sync + 'static> AnimationSource<T, SignalSource>
+    for ArcReadSignal<AnimationTarget<T>>
+{
+    fn into_signal(self) -> Signal<AnimationTarget<T>, LocalStorage> {
+        self.into()
+    }
+}
+
+impl<T: Send + Sync + 'static> AnimationSource<T, SignalSource> for RwSignal<AnimationTarget<T>> {
+    fn into_signal(self) -> Signal<AnimationTarget<T>, LocalStorage> {
+        ArcReadSignal::from(self.read_only()).into()
+    }
+}
+
+impl<T: Send + Sync + 'static> AnimationSource<T, SignalSource>
+    for ArcRwSignal<AnimationTarget<T>>
+{
+    fn into_signal(self) -> Signal<AnimationTarget<T>, LocalStorage> {
+        self.into()
+    }
+}
+
+// Thread safe memos and signals can't be turned into local signals: reactive_graph only converts
+// local memos and signals, and read signals of either kind, into a `Signal<_, LocalStorage>`. So
+// they are read by a derived signal, which is a closure and not another memo, so the value is
+// still only computed once
+macro_rules! impl_derived_animation_source {
+    ($($signal:ident),*) => {
+        $(
+            impl<T: Clone + Send + Sync + 'static> AnimationSource<T, SignalSource>
+                for $signal<AnimationTarget<T>>
+            {
+                fn into_signal(self) -> Signal<AnimationTarget<T>, LocalStorage> {
+                    Signal::derive_local(move || self.get())
+                }
+            }
+        )*
+    };
+}
+
+impl_derived_animation_source!(Signal, ArcSignal, Memo, ArcMemo);
+
+macro_rules! impl_local_animation_source {
+    ($($signal:ident),*) => {
+        $(
+            impl<T: 'static> AnimationSource<T, LocalSignalSource>
+                for $signal<AnimationTarget<T>, LocalStorage>
+            {
+                fn into_signal(self) -> Signal<AnimationTarget<T>, LocalStorage> {
+                    self.into()
+                }
+            }
+        )*
+    };
+}
+
+impl_local_animation_source!(Signal, ReadSignal, RwSignal, Memo);
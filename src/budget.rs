@@ -0,0 +1,184 @@
+#[cfg(not(feature = "disable-animations"))]
+use leptos::logging::debug_warn;
+use leptos::prelude::*;
+
+#[cfg(not(feature = "disable-animations"))]
+use crate::{AnimationMode, AnimationTarget};
+
+/// An `AnimationBudget` limits the number of animations that can run simultaneously in a component subtree.
+/// Animations that would be started beyond the budget snap directly to their target instead, and a
+/// warning is logged in debug builds. This protects list-heavy pages from accidental animation storms
+/// where every row starts its own animation at the same time.
+///
+/// The budget applies to all animated signals created in the scope where it is provided and all of
+/// its child scopes. Budgets can be nested, the innermost budget is the one that applies.
+///
+/// A budget can also limit the animated signals that are evaluated in a single frame with
+/// [`provide_with_max_evaluations()`](AnimationBudget::provide_with_max_evaluations). The running
+/// animations of signals beyond that limit snap to their target in that frame.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::{AnimatedSignal, budget::AnimationBudget, tween_default};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// // In the component rendering a list of hundreds of rows
+/// AnimationBudget::provide(20);
+///
+/// let (height, set_height) = signal(0.0);
+/// let animated_height = AnimatedSignal::new(move || height.get().into(), tween_default);
+/// ```
+#[derive(Copy, Clone)]
+pub struct AnimationBudget {
+    #[cfg(not(feature = "disable-animations"))]
+    max_animations: usize,
+    active_animations: StoredValue<usize>,
+    #[cfg(not(feature = "disable-animations"))]
+    max_evaluations: Option<usize>,
+    /// The frame that is being evaluated and the animated signals evaluated in it so far
+    #[cfg(not(feature = "disable-animations"))]
+    evaluations: StoredValue<(u64, usize)>,
+}
+
+impl AnimationBudget {
+    /// Provides a budget of `max_animations` simultaneously running animations for this scope and all child scopes
+    pub fn provide(max_animations: usize) -> AnimationBudget {
+        Self::provide_budget(max_animations, None)
+    }
+
+    /// Provides a budget of `max_animations` simultaneously running animations, of which at most
+    /// `max_evaluations` animated signals are evaluated per frame, for this scope and all child
+    /// scopes
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, budget::AnimationBudget, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// AnimationBudget::provide_with_max_evaluations(100, 20);
+    ///
+    /// let (height, set_height) = signal(0.0);
+    /// let animated_height = AnimatedSignal::new(
+    ///     move || (height.get(), Duration::from_millis(300)).into(),
+    ///     tween_default::<f64, f64>,
+    /// );
+    /// ```
+    pub fn provide_with_max_evaluations(
+        max_animations: usize,
+        max_evaluations: usize,
+    ) -> AnimationBudget {
+        Self::provide_budget(max_animations, Some(max_evaluations))
+    }
+
+    fn provide_budget(max_animations: usize, max_evaluations: Option<usize>) -> AnimationBudget {
+        // Without animations nothing ever runs against the budget
+        #[cfg(feature = "disable-animations")]
+        let _ = (max_animations, max_evaluations);
+        let budget = AnimationBudget {
+            #[cfg(not(feature = "disable-animations"))]
+            max_animations,
+            active_animations: StoredValue::new(0),
+            #[cfg(not(feature = "disable-animations"))]
+            max_evaluations,
+            #[cfg(not(feature = "disable-animations"))]
+            evaluations: StoredValue::new((0, 0)),
+        };
+        provide_context(budget);
+        budget
+    }
+
+    /// The number of animations that are currently running within this budget
+    pub fn active_animations(&self) -> usize {
+        self.active_animations.get_value()
+    }
+
+    /// Turns the target into a snap if starting it would exceed the budget
+    #[cfg(not(feature = "disable-animations"))]
+    pub(crate) fn adapt_target<T>(
+        &self,
+        mut target: AnimationTarget<T>,
+        running_animations: usize,
+    ) -> AnimationTarget<T> {
+        let starts_animation = match target.mode {
+            AnimationMode::Start | AnimationMode::ReplaceWithVelocity => true,
+            AnimationMode::ReplaceOrStart => running_animations == 0,
+            AnimationMode::ReplaceOrSnap | AnimationMode::Snap => false,
+        };
+        if starts_animation && self.active_animations() >= self.max_animations {
+            debug_warn!(
+                "Animation budget of {} simultaneous animations exceeded, snapping to target instead",
+                self.max_animations
+            );
+            target.mode = AnimationMode::Snap;
+        }
+        target
+    }
+
+    /// Counts the evaluation of an animated signal in `frame`, returns whether it is within the budget
+    #[cfg(not(feature = "disable-animations"))]
+    pub(crate) fn spend_evaluation(&self, frame: u64) -> bool {
+        let Some(max_evaluations) = self.max_evaluations else {
+            return true;
+        };
+        let evaluations = self
+            .evaluations
+            .try_update_value(|(evaluated_frame, evaluations)| {
+                if *evaluated_frame != frame {
+                    *evaluated_frame = frame;
+                    *evaluations = 0;
+                }
+                *evaluations += 1;
+                *evaluations
+            })
+            .unwrap_or_default();
+        if evaluations == max_evaluations + 1 {
+            debug_warn!(
+                "Animation budget of {max_evaluations} evaluations per frame exceeded, snapping to target instead"
+            );
+        }
+        evaluations <= max_evaluations
+    }
+
+    /// Updates the number of active animations after a signal went from `before` to `after` running animations
+    #[cfg(not(feature = "disable-animations"))]
+    pub(crate) fn track(&self, before: usize, after: usize) {
+        if before != after {
+            self.active_animations
+                .update_value(|active| *active = (*active + after).saturating_sub(before));
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "disable-animations")))]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::testing::TestAnimationHarness;
+    use crate::{tween_default, AnimatedSignal};
+
+    #[test]
+    fn signals_beyond_the_evaluations_of_a_frame_snap() {
+        let harness = TestAnimationHarness::new();
+        AnimationBudget::provide_with_max_evaluations(10, 2);
+        let (height, set_height) = signal(0.0);
+        let signals: Vec<AnimatedSignal<f64, f64>> = (0..3)
+            .map(|_| {
+                AnimatedSignal::new(
+                    move || (height.get(), Duration::from_secs(1)).into(),
+                    tween_default,
+                )
+            })
+            .collect();
+        for signal in &signals {
+            harness.observe(**signal);
+        }
+
+        set_height.set(100.0);
+        harness.step();
+        harness.step();
+        let snapped = signals
+            .iter()
+            .filter(|signal| signal.get_untracked() == 100.0)
+            .count();
+        assert_eq!(snapped, 1);
+        assert!(harness.is_animating());
+    }
+}
@@ -0,0 +1,420 @@
+use std::time::Duration;
+
+use instant::Instant;
+use leptos::prelude::*;
+
+use crate::time::saturating_mul_f64;
+use crate::AnimationContext;
+
+/// The time as seen by the animations of a context or a single signal, which can stand still or
+/// run at another speed than the time it follows
+pub(crate) struct Clock {
+    /// The followed time at which the clock was last paused, resumed or rescaled
+    real_anchor: Instant,
+    /// The time of the clock at `real_anchor`
+    anchor: Instant,
+    paused: bool,
+    /// Whether the document the clock is shown in is hidden, which stops the clock apart from
+    /// being paused
+    hidden: bool,
+    /// How fast the clock runs compared to the followed time
+    scale: f64,
+    /// Whether the clock was resumed since the last frame, so the frame does not follow the
+    /// previous one directly
+    resumed: bool,
+}
+
+impl Clock {
+    pub(crate) fn new(now: Instant) -> Clock {
+        Clock {
+            real_anchor: now,
+            anchor: now,
+            paused: false,
+            hidden: false,
+            scale: 1.0,
+            resumed: false,
+        }
+    }
+
+    pub(crate) fn now(&self, real_now: Instant) -> Instant {
+        if !self.is_running() {
+            self.anchor
+        } else {
+            let elapsed = real_now.saturating_duration_since(self.real_anchor);
+            // A time beyond what the platform can represent keeps the clock where it was
+            self.anchor
+                .checked_add(saturating_mul_f64(elapsed, self.scale))
+                .unwrap_or(self.anchor)
+        }
+    }
+
+    /// Moves the anchors to `real_now` without changing the current time
+    fn reanchor(&mut self, real_now: Instant) {
+        self.anchor = self.now(real_now);
+        self.real_anchor = real_now;
+    }
+
+    pub(crate) fn pause(&mut self, real_now: Instant) {
+        self.reanchor(real_now);
+        self.paused = true;
+    }
+
+    /// Lets the clock run again unless it is hidden, returns whether it started running
+    pub(crate) fn resume(&mut self, real_now: Instant) -> bool {
+        self.stop_while(real_now, |clock| clock.paused = false)
+    }
+
+    /// Stops the clock while its document is hidden, without touching whether it is paused,
+    /// returns whether it started running again
+    pub(crate) fn set_hidden(&mut self, real_now: Instant, hidden: bool) -> bool {
+        self.stop_while(real_now, |clock| clock.hidden = hidden)
+    }
+
+    /// Changes why the clock stands still, returns whether it started running because of it
+    fn stop_while(&mut self, real_now: Instant, change: impl FnOnce(&mut Clock)) -> bool {
+        let was_running = self.is_running();
+        self.reanchor(real_now);
+        change(self);
+        let started = !was_running && self.is_running();
+        self.resumed |= started;
+        started
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Whether time advances, so the clock is neither paused nor hidden
+    pub(crate) fn is_running(&self) -> bool {
+        !self.paused && !self.hidden
+    }
+
+    pub(crate) fn set_scale(&mut self, real_now: Instant, scale: f64) {
+        self.reanchor(real_now);
+        self.scale = match scale.is_nan() {
+            true => 0.0,
+            false => scale.clamp(0.0, MAX_SCALE),
+        };
+    }
+
+    pub(crate) fn scale(&self) -> f64 {
+        self.scale
+    }
+}
+
+/// The fastest a clock runs compared to the time it follows, so that no time scale makes the
+/// animations jump to their end or overflow the time
+const MAX_SCALE: f64 = 1000.0;
+
+/// The longest step the time of a context takes between two frames by default, see
+/// [`AnimationContext::set_max_frame_delta()`]
+pub const DEFAULT_MAX_FRAME_DELTA: Duration = Duration::from_millis(250);
+
+/// The real time without its jumps: it advances by the real time that passes between frames, but
+/// at most `max_delta` at once, and never goes backwards. Suspending the device or adjusting the
+/// system clock then only moves the animations on by a single frame.
+pub(crate) struct SteadyTime {
+    /// The real time of the last frame or jump
+    last_real: Instant,
+    /// The steady time at `last_real`
+    last: Instant,
+    max_delta: Duration,
+    /// The real time until which a longer time between frames is expected, because the next frame
+    /// was requested to arrive late
+    expected_gap: Option<Instant>,
+}
+
+impl SteadyTime {
+    pub(crate) fn new(now: Instant) -> SteadyTime {
+        SteadyTime {
+            last_real: now,
+            last: now,
+            max_delta: DEFAULT_MAX_FRAME_DELTA,
+            expected_gap: None,
+        }
+    }
+
+    pub(crate) fn now(&mut self, real_now: Instant) -> Instant {
+        let max_delta = match self.expected_gap {
+            Some(until) => until.saturating_duration_since(self.last_real) + self.max_delta,
+            None => self.max_delta,
+        };
+        match real_now.checked_duration_since(self.last_real) {
+            Some(delta) if delta <= max_delta => self.last + delta,
+            // The real time jumped forwards or backwards, continue from here in one step
+            delta => {
+                self.last += delta.unwrap_or_default().min(max_delta);
+                self.last_real = real_now;
+                self.last
+            }
+        }
+    }
+
+    /// Moves on to the time of a new frame
+    pub(crate) fn frame(&mut self, real_now: Instant) {
+        self.last = self.now(real_now);
+        self.last_real = real_now;
+        self.expected_gap = None;
+    }
+
+    /// Expects no frame before the real time `until`, so the time until then is not a jump
+    pub(crate) fn expect_gap(&mut self, until: Instant) {
+        self.expected_gap = Some(self.expected_gap.map_or(until, |gap| gap.max(until)));
+    }
+}
+
+impl AnimationContext {
+    /// The real time with its jumps removed, which the clock of the context follows
+    pub(crate) fn steady_now(&self) -> Instant {
+        let real_now = self.real_now();
+        self.steady_time
+            .try_update_value(|steady_time| steady_time.now(real_now))
+            .unwrap_or(real_now)
+    }
+
+    /// Limits how far the animations of this context move on between two frames. When the real
+    /// time between two frames is longer, for example after the device woke up from sleep, the
+    /// system clock was adjusted or the page was in the background, the animations continue as if
+    /// only `max_delta` had passed instead of jumping to their end. Time never goes backwards.
+    /// Defaults to [`DEFAULT_MAX_FRAME_DELTA`].
+    // Without animations there is no animation to move on
+    #[cfg_attr(not(feature = "disable-animations"), doc = "```")]
+    #[cfg_attr(feature = "disable-animations", doc = "```ignore")]
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # use leptos_animation::testing::TestAnimationHarness;
+    /// let harness = TestAnimationHarness::new();
+    /// harness.context().set_max_frame_delta(Duration::from_millis(100));
+    ///
+    /// let (open, set_open) = signal(false);
+    /// let height: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+    ///     move || (if open.get() { 200.0 } else { 0.0 }, Duration::from_millis(1000), easing::LINEAR).into(),
+    ///     tween_default,
+    /// );
+    /// harness.observe(*height);
+    /// set_open.set(true);
+    ///
+    /// // The laptop sleeps for an hour in the middle of the animation
+    /// harness.step_by(Duration::from_secs(3600));
+    /// // The animation has only moved on by 100ms
+    /// harness.assert_value_near(&height, 20.0, 0.001);
+    /// ```
+    pub fn set_max_frame_delta(&self, max_delta: Duration) {
+        let real_now = self.real_now();
+        self.steady_time.update_value(|steady_time| {
+            steady_time.frame(real_now);
+            steady_time.max_delta = max_delta;
+        });
+    }
+
+    /// How far the animations of this context move on between two frames at most, see
+    /// [`set_max_frame_delta()`](AnimationContext::set_max_frame_delta)
+    pub fn max_frame_delta(&self) -> Duration {
+        self.steady_time
+            .with_value(|steady_time| steady_time.max_delta)
+    }
+
+    /// Halts the advance of time for every animation of this context, freezing all animated values
+    /// mid-animation until [`resume_all()`](AnimationContext::resume_all) is called. Animations that
+    /// are started while paused don't progress either. No animation frames are requested while
+    /// paused.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::AnimationContext;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = AnimationContext::provide_with_custom_request_animation_frame(|| {}).0;
+    ///
+    /// // The game opens its pause menu
+    /// context.pause_all();
+    /// assert!(context.is_paused());
+    ///
+    /// // And closes it again, all animations continue where they were
+    /// context.resume_all();
+    /// ```
+    pub fn pause_all(&self) {
+        let real_now = self.steady_now();
+        self.clock.update_value(|clock| clock.pause(real_now));
+    }
+
+    /// Lets time advance again after [`pause_all()`](AnimationContext::pause_all). While the
+    /// document is [hidden](AnimationContext::visible), time only advances once it is shown.
+    pub fn resume_all(&self) {
+        let real_now = self.steady_now();
+        let started = self.clock.try_update_value(|clock| clock.resume(real_now));
+        if started == Some(true) && self.animating.get_value() {
+            self.request_frame();
+        }
+    }
+
+    /// Whether time is halted with [`pause_all()`](AnimationContext::pause_all)
+    pub fn is_paused(&self) -> bool {
+        self.clock.with_value(Clock::is_paused)
+    }
+
+    /// Makes all animations of this context run `scale` times as fast as normal, for example 0.1
+    /// to watch an animation glitch in slow motion or 2.0 to try out snappier motion. Running
+    /// animations keep their current value and continue at the new speed. Negative values and NaN
+    /// are treated as 0.0, which stands still like [`pause_all()`](AnimationContext::pause_all) but
+    /// keeps requesting frames. Scales beyond 1000.0, including infinity, run 1000 times as fast.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::AnimationContext;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = AnimationContext::provide();
+    /// context.set_time_scale(0.1);
+    /// assert_eq!(context.time_scale(), 0.1);
+    /// ```
+    pub fn set_time_scale(&self, scale: f64) {
+        let real_now = self.steady_now();
+        self.clock
+            .update_value(|clock| clock.set_scale(real_now, scale));
+    }
+
+    /// How fast the animations of this context run, see
+    /// [`set_time_scale()`](AnimationContext::set_time_scale)
+    pub fn time_scale(&self) -> f64 {
+        self.clock.with_value(Clock::scale)
+    }
+
+    /// Whether the next frame directly follows the previous one, which is not the case after the
+    /// clock was resumed
+    pub(crate) fn take_resumed(&self) -> bool {
+        self.clock
+            .try_update_value(|clock| std::mem::take(&mut clock.resumed))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn steady_time_follows_short_frames() {
+        let start = Instant::now();
+        let mut steady_time = SteadyTime::new(start);
+        steady_time.frame(start + ms(16));
+        assert_eq!(steady_time.now(start + ms(32)), start + ms(32));
+    }
+
+    #[test]
+    fn steady_time_clamps_jumps_forwards() {
+        let start = Instant::now();
+        let mut steady_time = SteadyTime::new(start);
+        steady_time.max_delta = ms(100);
+        // The device slept for an hour
+        steady_time.frame(start + Duration::from_secs(3600));
+        assert_eq!(
+            steady_time.now(start + Duration::from_secs(3600)),
+            start + ms(100)
+        );
+        // And continues normally from there
+        assert_eq!(
+            steady_time.now(start + Duration::from_secs(3600) + ms(16)),
+            start + ms(116)
+        );
+    }
+
+    #[test]
+    fn steady_time_never_goes_backwards() {
+        let start = Instant::now() + Duration::from_secs(10);
+        let mut steady_time = SteadyTime::new(start);
+        steady_time.frame(start + ms(50));
+        // The system clock was set back
+        steady_time.frame(start - Duration::from_secs(5));
+        assert_eq!(
+            steady_time.now(start - Duration::from_secs(5)),
+            start + ms(50)
+        );
+    }
+
+    #[test]
+    fn steady_time_allows_expected_gaps() {
+        let start = Instant::now();
+        let mut steady_time = SteadyTime::new(start);
+        steady_time.max_delta = ms(100);
+        steady_time.expect_gap(start + ms(500));
+        assert_eq!(steady_time.now(start + ms(550)), start + ms(550));
+        // Beyond the gap and the max delta the time is clamped again
+        steady_time.frame(start + ms(2000));
+        assert_eq!(steady_time.now(start + ms(2000)), start + ms(600));
+    }
+
+    #[test]
+    fn hidden_clocks_stand_still_and_stay_paused() {
+        let start = Instant::now();
+        let mut clock = Clock::new(start);
+        assert!(!clock.set_hidden(start + ms(100), true));
+        assert_eq!(clock.now(start + ms(500)), start + ms(100));
+        assert!(clock.set_hidden(start + ms(500), false));
+        assert_eq!(clock.now(start + ms(600)), start + ms(200));
+
+        // Showing the document again does not resume a paused clock
+        clock.pause(start + ms(600));
+        clock.set_hidden(start + ms(700), true);
+        assert!(!clock.set_hidden(start + ms(800), false));
+        assert!(clock.is_paused());
+        assert_eq!(clock.now(start + ms(900)), start + ms(200));
+        // Nor does resuming run a hidden clock
+        clock.set_hidden(start + ms(900), true);
+        assert!(!clock.resume(start + ms(900)));
+        assert!(clock.set_hidden(start + ms(1000), false));
+        assert_eq!(clock.now(start + ms(1100)), start + ms(300));
+    }
+
+    #[test]
+    fn paused_clock_stands_still() {
+        let start = Instant::now();
+        let mut clock = Clock::new(start);
+        clock.pause(start + ms(100));
+        assert!(clock.is_paused());
+        assert_eq!(clock.now(start + ms(500)), start + ms(100));
+        assert!(clock.resume(start + ms(500)));
+        assert!(!clock.resume(start + ms(500)));
+        assert_eq!(clock.now(start + ms(600)), start + ms(200));
+    }
+
+    #[test]
+    fn scaled_clock_runs_at_its_speed() {
+        let start = Instant::now();
+        let mut clock = Clock::new(start);
+        clock.set_scale(start + ms(100), 0.5);
+        assert_eq!(clock.now(start + ms(300)), start + ms(200));
+        clock.set_scale(start + ms(300), 2.0);
+        assert_eq!(clock.now(start + ms(400)), start + ms(400));
+        clock.set_scale(start + ms(400), -1.0);
+        assert_eq!(clock.scale(), 0.0);
+        assert_eq!(clock.now(start + ms(1000)), start + ms(400));
+    }
+
+    #[test]
+    fn scaled_durations_saturate() {
+        assert_eq!(saturating_mul_f64(ms(100), 2.5), ms(250));
+        assert_eq!(saturating_mul_f64(Duration::MAX, 2.0), Duration::MAX);
+        assert_eq!(saturating_mul_f64(ms(100), f64::INFINITY), Duration::MAX);
+        assert_eq!(
+            saturating_mul_f64(Duration::ZERO, f64::INFINITY),
+            Duration::ZERO
+        );
+        assert_eq!(saturating_mul_f64(ms(100), f64::NAN), Duration::ZERO);
+        assert_eq!(saturating_mul_f64(ms(100), -1.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn clock_scales_stay_in_range() {
+        let start = Instant::now();
+        let mut clock = Clock::new(start);
+        clock.set_scale(start, f64::NAN);
+        assert_eq!(clock.scale(), 0.0);
+        clock.set_scale(start, f64::INFINITY);
+        assert_eq!(clock.scale(), MAX_SCALE);
+        assert_eq!(clock.now(start + ms(1)), start + Duration::from_secs(1));
+        clock.set_scale(start, 1e300);
+        assert_eq!(clock.scale(), MAX_SCALE);
+    }
+}
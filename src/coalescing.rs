@@ -0,0 +1,162 @@
+#[cfg(not(feature = "disable-animations"))]
+use leptos::prelude::*;
+
+use crate::AnimatedSignal;
+
+/// How an [`AnimatedSignal`] handles a source that emits several targets between two animation
+/// frames, for example a store that is updated a couple of times within one event handler
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum UpdatePolicy {
+    /// Every target is applied right away, in the order the source emits them, following the
+    /// [`AnimationMode`](crate::AnimationMode) of each. With the default mode every one of them
+    /// starts an animation.
+    #[default]
+    ApplyAll,
+
+    /// Only the last target emitted before a frame is applied, at the start of that frame.
+    /// The targets before it are dropped without ever starting an animation.
+    TakeLast,
+
+    /// Up to this many targets are held back until the next frame and then applied in order.
+    /// If the source emits more, the oldest ones are dropped. This bounds the number of
+    /// animations a bursty source can start per frame.
+    Bounded(usize),
+}
+
+impl UpdatePolicy {
+    /// The number of targets that are held back until the next frame, `None` if they are applied right away
+    #[cfg(not(feature = "disable-animations"))]
+    pub(crate) fn capacity(self) -> Option<usize> {
+        match self {
+            UpdatePolicy::ApplyAll => None,
+            UpdatePolicy::TakeLast => Some(1),
+            UpdatePolicy::Bounded(capacity) => Some(capacity.max(1)),
+        }
+    }
+}
+
+impl<T, I> AnimatedSignal<T, I> {
+    /// Sets the [`UpdatePolicy`] for targets the source emits between two frames
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, coalescing::UpdatePolicy, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (price, set_price) = signal(0.0);
+    ///
+    /// // Quotes arrive from a websocket faster than the display refreshes, only animate towards
+    /// // the latest quote of every frame
+    /// let animated_price = AnimatedSignal::new(move || price.get().into(), tween_default)
+    ///     .with_update_policy(UpdatePolicy::TakeLast);
+    /// ```
+    pub fn with_update_policy(self, policy: UpdatePolicy) -> Self {
+        // Signals without animations have nothing to apply the setting to
+        #[cfg(not(feature = "disable-animations"))]
+        self.update_policy.set_value(policy);
+        #[cfg(feature = "disable-animations")]
+        let _ = policy;
+        self
+    }
+}
+
+#[cfg(all(test, not(feature = "disable-animations")))]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::testing::TestAnimationHarness;
+    use crate::{easing, tween_default, AnimationStatus};
+
+    fn animated_price(
+        harness: &TestAnimationHarness,
+        policy: UpdatePolicy,
+    ) -> (WriteSignal<f64>, AnimatedSignal<f64, f64>) {
+        let (price, set_price) = signal(0.0);
+        let animated = AnimatedSignal::new(
+            move || (price.get(), Duration::from_millis(100), easing::LINEAR).into(),
+            tween_default,
+        )
+        .with_update_policy(policy);
+        harness.observe(*animated);
+        (set_price, animated)
+    }
+
+    /// Emits every price as its own target, like several updates within one event handler
+    fn emit(harness: &TestAnimationHarness, set_price: WriteSignal<f64>, prices: &[f64]) {
+        for price in prices {
+            set_price.set(*price);
+            harness.run_effects();
+        }
+    }
+
+    /// The targets of the running animations, the most recent one first
+    fn running_targets(animated: &AnimatedSignal<f64, f64>) -> Vec<f64> {
+        animated
+            .animation_status
+            .with_value(|animation_status| match animation_status {
+                AnimationStatus::Running { animations, .. } => {
+                    animations.iter().map(|animation| animation.to).collect()
+                }
+                _ => Vec::new(),
+            })
+    }
+
+    #[test]
+    fn apply_all_starts_every_target_right_away() {
+        let harness = TestAnimationHarness::new();
+        let (set_price, animated) = animated_price(&harness, UpdatePolicy::ApplyAll);
+        emit(&harness, set_price, &[10.0, 20.0, 30.0]);
+        assert_eq!(running_targets(&animated), [30.0, 20.0, 10.0]);
+
+        harness.run_until_settled();
+        harness.assert_value_near(&animated, 30.0, 1e-9);
+    }
+
+    #[test]
+    fn take_last_replaces_the_targets_of_a_frame() {
+        let harness = TestAnimationHarness::new();
+        let (set_price, animated) = animated_price(&harness, UpdatePolicy::TakeLast);
+        emit(&harness, set_price, &[10.0, 20.0, 30.0]);
+        // Nothing starts before the frame
+        assert!(running_targets(&animated).is_empty());
+
+        harness.step_by(Duration::ZERO);
+        assert_eq!(running_targets(&animated), [30.0]);
+        harness.step_by(Duration::from_millis(50));
+        harness.assert_value_near(&animated, 15.0, 1e-9);
+        harness.run_until_settled();
+        harness.assert_value_near(&animated, 30.0, 1e-9);
+    }
+
+    #[test]
+    fn bounded_queues_the_most_recent_targets() {
+        let harness = TestAnimationHarness::new();
+        let (set_price, animated) = animated_price(&harness, UpdatePolicy::Bounded(2));
+        emit(&harness, set_price, &[10.0, 20.0, 30.0]);
+        assert!(running_targets(&animated).is_empty());
+
+        // The oldest target is dropped, the others are applied in order
+        harness.step_by(Duration::ZERO);
+        assert_eq!(running_targets(&animated), [30.0, 20.0]);
+        harness.run_until_settled();
+        harness.assert_value_near(&animated, 30.0, 1e-9);
+    }
+
+    #[test]
+    fn targets_wait_for_the_frame_while_animations_run() {
+        let harness = TestAnimationHarness::new();
+        let (set_price, animated) = animated_price(&harness, UpdatePolicy::TakeLast);
+        emit(&harness, set_price, &[100.0]);
+        harness.step_by(Duration::ZERO);
+        harness.step_by(Duration::from_millis(50));
+
+        emit(&harness, set_price, &[50.0, 60.0]);
+        // The running animation is left alone until the next frame
+        assert_eq!(running_targets(&animated), [100.0]);
+        harness.assert_value_near(&animated, 50.0, 1e-9);
+
+        harness.step_by(Duration::ZERO);
+        assert_eq!(running_targets(&animated), [60.0, 100.0]);
+        harness.run_until_settled();
+        harness.assert_value_near(&animated, 60.0, 1e-9);
+    }
+}
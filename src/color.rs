@@ -0,0 +1,907 @@
+//! Colors that are animated the way they are perceived, and written out as CSS colors.
+//!
+//! [`Rgba`] colors interpolate in linear light, which keeps their brightness. [`Oklab`] and
+//! [`Oklch`] colors interpolate in the perceptual OKLab space, which also keeps the hues in
+//! between from turning grey and muddy, for example when fading from blue to yellow. [`Hsla`]
+//! colors interpolate their hue around the color wheel, in a [`HueDirection`] of choice.
+
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+use crate::tween::Tween;
+
+/// A color in the sRGB color space of CSS, with all channels and the alpha from 0.0 to 1.0.
+///
+/// The channels are stored as they are written in CSS, with gamma applied. Animate colors with
+/// [`tween_rgba`], which interpolates in linear light, and format them with
+/// [`to_css()`](Rgba::to_css) or `format!("{color}")`.
+/// ```
+/// # use leptos_animation::color::Rgba;
+/// let orange = Rgba::from_hex("#ff8000").unwrap();
+/// assert_eq!(orange, Rgba::rgb8(255, 128, 0));
+/// assert_eq!(orange.to_css(), "rgb(255, 128, 0)");
+/// assert_eq!(orange.with_alpha(0.5).to_css(), "rgba(255, 128, 0, 0.5)");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rgba {
+    /// The red channel
+    pub r: f64,
+    /// The green channel
+    pub g: f64,
+    /// The blue channel
+    pub b: f64,
+    /// The opacity, 0.0 is fully transparent
+    pub a: f64,
+}
+
+impl Rgba {
+    /// A color from channels and an alpha from 0.0 to 1.0
+    pub const fn new(r: f64, g: f64, b: f64, a: f64) -> Rgba {
+        Rgba { r, g, b, a }
+    }
+
+    /// An opaque color from channels from 0 to 255
+    pub fn rgb8(r: u8, g: u8, b: u8) -> Rgba {
+        Rgba::rgba8(r, g, b, u8::MAX)
+    }
+
+    /// A color from channels and an alpha from 0 to 255
+    pub fn rgba8(r: u8, g: u8, b: u8, a: u8) -> Rgba {
+        let channel = |value: u8| value as f64 / u8::MAX as f64;
+        Rgba::new(channel(r), channel(g), channel(b), channel(a))
+    }
+
+    /// Parses a CSS hex color such as `#ff8000`, `#f80`, `#ff800080` or `#f808`, with or without
+    /// the `#`. Returns `None` for anything else.
+    pub fn from_hex(hex: &str) -> Option<Rgba> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        // `from_str_radix()` also accepts a leading `+`
+        if !hex.chars().all(|char| char.is_ascii_hexdigit()) {
+            return None;
+        }
+        let digit = |index: usize| u8::from_str_radix(&hex[index..index + 1], 16).ok();
+        let pair = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+        match hex.len() {
+            3 | 4 => {
+                let short = |index: usize| digit(index).map(|value| value * 17);
+                let a = match hex.len() {
+                    4 => short(3)?,
+                    _ => u8::MAX,
+                };
+                Some(Rgba::rgba8(short(0)?, short(1)?, short(2)?, a))
+            }
+            6 | 8 => {
+                let a = match hex.len() {
+                    8 => pair(6)?,
+                    _ => u8::MAX,
+                };
+                Some(Rgba::rgba8(pair(0)?, pair(2)?, pair(4)?, a))
+            }
+            _ => None,
+        }
+    }
+
+    /// The same color with another alpha
+    pub fn with_alpha(self, a: f64) -> Rgba {
+        Rgba { a, ..self }
+    }
+
+    /// The color as a CSS `rgb()` or, if it is not opaque, `rgba()` color. Channels outside of
+    /// 0.0 - 1.0, such as those of colors animated with an overshooting easing, are clamped.
+    pub fn to_css(&self) -> String {
+        self.to_string()
+    }
+
+    /// The channels in linear light, premultiplied by the alpha
+    fn to_linear_premultiplied(self) -> [f64; 4] {
+        let a = self.a.clamp(0.0, 1.0);
+        [
+            srgb_to_linear(self.r) * a,
+            srgb_to_linear(self.g) * a,
+            srgb_to_linear(self.b) * a,
+            a,
+        ]
+    }
+
+    /// The inverse of [`to_linear_premultiplied()`](Rgba::to_linear_premultiplied)
+    fn from_linear_premultiplied([r, g, b, a]: [f64; 4]) -> Rgba {
+        if a <= 0.0 {
+            return Rgba::new(0.0, 0.0, 0.0, a);
+        }
+        Rgba::new(
+            linear_to_srgb(r / a),
+            linear_to_srgb(g / a),
+            linear_to_srgb(b / a),
+            a,
+        )
+    }
+}
+
+/// A channel of 0.0 - 1.0 written as 0 - 255
+fn channel_to_u8(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * u8::MAX as f64).round() as u8
+}
+
+impl fmt::Display for Rgba {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b] = [self.r, self.g, self.b].map(channel_to_u8);
+        let a = self.a.clamp(0.0, 1.0);
+        if a >= 1.0 {
+            write!(f, "rgb({r}, {g}, {b})")
+        } else {
+            // Three decimals are more precise than any display can show
+            let a = (a * 1000.0).round() / 1000.0;
+            write!(f, "rgba({r}, {g}, {b}, {a})")
+        }
+    }
+}
+
+/// Removes the gamma of an sRGB channel, see <https://www.w3.org/TR/css-color-4/#color-conversion-code>
+pub(crate) fn srgb_to_linear(value: f64) -> f64 {
+    let magnitude = value.abs();
+    if magnitude <= 0.04045 {
+        value / 12.92
+    } else {
+        value.signum() * ((magnitude + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Applies the gamma of sRGB to a channel in linear light
+pub(crate) fn linear_to_srgb(value: f64) -> f64 {
+    let magnitude = value.abs();
+    if magnitude <= 0.0031308 {
+        value * 12.92
+    } else {
+        value.signum() * (1.055 * magnitude.powf(1.0 / 2.4) - 0.055)
+    }
+}
+
+/// Interpolates between two colors in linear light, so the colors in between keep the brightness
+/// that the eye expects instead of turning dark and muddy. The channels are premultiplied by the
+/// alpha, so fading in from a transparent color doesn't show the color of the transparent end.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::color::{tween_rgba, Rgba};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let red = Rgba::rgb8(255, 0, 0);
+/// let green = Rgba::rgb8(0, 255, 0);
+/// // Halfway is a bright yellow, not the dark rgb(128, 128, 0) of interpolating the CSS values
+/// assert_eq!(tween_rgba(&red, &green, 0.5).to_css(), "rgb(188, 188, 0)");
+///
+/// let (selected, set_selected) = signal(false);
+/// let background = AnimatedSignal::new(
+///     move || if selected.get() { green } else { red }.into(),
+///     tween_rgba,
+/// );
+/// # let _html =
+/// view! { <div style:background-color=move || background.get().to_css()></div> }
+/// # .to_html();
+/// ```
+pub fn tween_rgba(from: &Rgba, to: &Rgba, progress: f64) -> Rgba {
+    let from = from.to_linear_premultiplied();
+    let to = to.to_linear_premultiplied();
+    let mut mixed = [0.0; 4];
+    for (index, channel) in mixed.iter_mut().enumerate() {
+        *channel = from[index].tween(&to[index], progress);
+    }
+    Rgba::from_linear_premultiplied(mixed)
+}
+
+impl Tween for Rgba {
+    type Interpolated = Rgba;
+
+    fn tween(&self, other: &Self, progress: f64) -> Rgba {
+        tween_rgba(self, other, progress)
+    }
+}
+
+impl Add for Rgba {
+    type Output = Rgba;
+
+    fn add(self, rhs: Rgba) -> Rgba {
+        Rgba::new(
+            self.r + rhs.r,
+            self.g + rhs.g,
+            self.b + rhs.b,
+            self.a + rhs.a,
+        )
+    }
+}
+
+impl Sub for Rgba {
+    type Output = Rgba;
+
+    fn sub(self, rhs: Rgba) -> Rgba {
+        Rgba::new(
+            self.r - rhs.r,
+            self.g - rhs.g,
+            self.b - rhs.b,
+            self.a - rhs.a,
+        )
+    }
+}
+
+impl Mul<f64> for Rgba {
+    type Output = Rgba;
+
+    fn mul(self, rhs: f64) -> Rgba {
+        Rgba::new(self.r * rhs, self.g * rhs, self.b * rhs, self.a * rhs)
+    }
+}
+
+/// Chroma or saturation below which a color counts as grey, and the distance of the lightness of
+/// HSL colors from black and white at which they count as such, so their hue is meaningless
+const ACHROMATIC_THRESHOLD: f64 = 1e-4;
+
+/// Rounds `value` to `decimals` decimals for CSS output
+fn round_to(value: f64, decimals: i32) -> f64 {
+    let scale = 10f64.powi(decimals);
+    (value * scale).round() / scale
+}
+
+/// Writes the ` / alpha` of a CSS color function, if the color is not opaque
+fn write_alpha(f: &mut fmt::Formatter<'_>, alpha: f64) -> fmt::Result {
+    let alpha = alpha.clamp(0.0, 1.0);
+    match alpha >= 1.0 {
+        true => Ok(()),
+        false => write!(f, " / {}", round_to(alpha, 3)),
+    }
+}
+
+/// A color in the perceptual [OKLab](https://bottosson.github.io/posts/oklab/) color space, as a
+/// lightness `l` from 0.0 to 1.0 and the coordinates `a` (green to red) and `b` (blue to yellow),
+/// which are roughly between -0.4 and 0.4.
+///
+/// Equal distances in OKLab look like equal differences in color, so the colors in between two
+/// OKLab colors look evenly spaced. Animate them with [`tween_oklab`] and format them as a CSS
+/// `oklab()` color with [`to_css()`](Oklab::to_css), or convert them back into an [`Rgba`].
+/// ```
+/// # use leptos_animation::color::{Oklab, Rgba};
+/// let white = Oklab::from(Rgba::rgb8(255, 255, 255));
+/// assert!((white.l - 1.0).abs() < 1e-6);
+/// assert_eq!(Rgba::from(Oklab::from_hex("#ff8000").unwrap()).to_css(), "rgb(255, 128, 0)");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Oklab {
+    /// The perceived lightness, from 0.0 for black to 1.0 for white
+    pub l: f64,
+    /// How green (negative) or red (positive) the color is
+    pub a: f64,
+    /// How blue (negative) or yellow (positive) the color is
+    pub b: f64,
+    /// The opacity, 0.0 is fully transparent
+    pub alpha: f64,
+}
+
+impl Oklab {
+    /// A color from its lightness, its `a` and `b` coordinates and an alpha from 0.0 to 1.0
+    pub const fn new(l: f64, a: f64, b: f64, alpha: f64) -> Oklab {
+        Oklab { l, a, b, alpha }
+    }
+
+    /// Parses a CSS hex color, see [`Rgba::from_hex()`]
+    pub fn from_hex(hex: &str) -> Option<Oklab> {
+        Rgba::from_hex(hex).map(Oklab::from)
+    }
+
+    /// The color as a CSS `oklab()` color
+    pub fn to_css(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl From<Rgba> for Oklab {
+    fn from(color: Rgba) -> Oklab {
+        let [r, g, b] = [color.r, color.g, color.b].map(srgb_to_linear);
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+        Oklab::new(
+            0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+            color.a,
+        )
+    }
+}
+
+impl From<Oklab> for Rgba {
+    /// Converts back to sRGB. Colors outside of the sRGB gamut get channels outside of 0.0 - 1.0,
+    /// which are clamped when they are written out as CSS.
+    fn from(color: Oklab) -> Rgba {
+        let l = (color.l + 0.3963377774 * color.a + 0.2158037573 * color.b).powi(3);
+        let m = (color.l - 0.1055613458 * color.a - 0.0638541728 * color.b).powi(3);
+        let s = (color.l - 0.0894841775 * color.a - 1.2914855480 * color.b).powi(3);
+        Rgba::new(
+            linear_to_srgb(4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s),
+            linear_to_srgb(-1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s),
+            linear_to_srgb(-0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s),
+            color.alpha,
+        )
+    }
+}
+
+impl fmt::Display for Oklab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "oklab({}% {} {}",
+            round_to(self.l * 100.0, 2),
+            round_to(self.a, 4),
+            round_to(self.b, 4)
+        )?;
+        write_alpha(f, self.alpha)?;
+        write!(f, ")")
+    }
+}
+
+/// Interpolates between two colors in OKLab, so the colors in between are evenly spaced to the
+/// eye and don't pass through muddy greys. Like CSS, the coordinates are premultiplied by the
+/// alpha.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::color::{tween_oklab, Oklab};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let blue = Oklab::from_hex("#0000ff").unwrap();
+/// let yellow = Oklab::from_hex("#ffff00").unwrap();
+///
+/// let (warm, set_warm) = signal(false);
+/// let accent = AnimatedSignal::new(
+///     move || if warm.get() { yellow } else { blue }.into(),
+///     tween_oklab,
+/// );
+/// # let _html =
+/// view! { <div style:color=move || accent.get().to_css()></div> }
+/// # .to_html();
+/// ```
+pub fn tween_oklab(from: &Oklab, to: &Oklab, progress: f64) -> Oklab {
+    let from_alpha = from.alpha.clamp(0.0, 1.0);
+    let to_alpha = to.alpha.clamp(0.0, 1.0);
+    let alpha = from_alpha.tween(&to_alpha, progress);
+    if alpha <= 0.0 {
+        return Oklab::new(from.l.tween(&to.l, progress), 0.0, 0.0, alpha);
+    }
+    let channel =
+        |from: f64, to: f64| (from * from_alpha).tween(&(to * to_alpha), progress) / alpha;
+    Oklab::new(
+        channel(from.l, to.l),
+        channel(from.a, to.a),
+        channel(from.b, to.b),
+        alpha,
+    )
+}
+
+impl Tween for Oklab {
+    type Interpolated = Oklab;
+
+    fn tween(&self, other: &Self, progress: f64) -> Oklab {
+        tween_oklab(self, other, progress)
+    }
+}
+
+impl Add for Oklab {
+    type Output = Oklab;
+
+    fn add(self, rhs: Oklab) -> Oklab {
+        Oklab::new(
+            self.l + rhs.l,
+            self.a + rhs.a,
+            self.b + rhs.b,
+            self.alpha + rhs.alpha,
+        )
+    }
+}
+
+impl Sub for Oklab {
+    type Output = Oklab;
+
+    fn sub(self, rhs: Oklab) -> Oklab {
+        Oklab::new(
+            self.l - rhs.l,
+            self.a - rhs.a,
+            self.b - rhs.b,
+            self.alpha - rhs.alpha,
+        )
+    }
+}
+
+impl Mul<f64> for Oklab {
+    type Output = Oklab;
+
+    fn mul(self, rhs: f64) -> Oklab {
+        Oklab::new(self.l * rhs, self.a * rhs, self.b * rhs, self.alpha * rhs)
+    }
+}
+
+/// An [`Oklab`] color in polar coordinates, as a lightness `l` from 0.0 to 1.0, a chroma `c` from
+/// 0.0 for grey to roughly 0.4 for the most saturated colors, and a hue angle `h` in degrees.
+///
+/// Interpolating the hue keeps the saturation up while the color turns, so a fade from red to green
+/// passes through orange and yellow instead of brown. Animate OKLCH colors with [`tween_oklch`] and
+/// format them as a CSS `oklch()` color with [`to_css()`](Oklch::to_css).
+/// ```
+/// # use leptos_animation::color::{Oklch, Rgba};
+/// let red = Oklch::from_hex("#ff0000").unwrap();
+/// assert_eq!(red.to_css(), "oklch(62.8% 0.2577 29.23)");
+/// assert_eq!(red.with_alpha(0.5).to_css(), "oklch(62.8% 0.2577 29.23 / 0.5)");
+/// assert_eq!(Rgba::from(red).to_css(), "rgb(255, 0, 0)");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Oklch {
+    /// The perceived lightness, from 0.0 for black to 1.0 for white
+    pub l: f64,
+    /// The chroma, 0.0 is grey
+    pub c: f64,
+    /// The hue angle in degrees, such as about 29 for red, 142 for green and 264 for blue
+    pub h: f64,
+    /// The opacity, 0.0 is fully transparent
+    pub alpha: f64,
+}
+
+impl Oklch {
+    /// A color from its lightness, chroma, hue in degrees and an alpha from 0.0 to 1.0
+    pub const fn new(l: f64, c: f64, h: f64, alpha: f64) -> Oklch {
+        Oklch { l, c, h, alpha }
+    }
+
+    /// Parses a CSS hex color, see [`Rgba::from_hex()`]
+    pub fn from_hex(hex: &str) -> Option<Oklch> {
+        Rgba::from_hex(hex).map(Oklch::from)
+    }
+
+    /// The same color with another alpha
+    pub fn with_alpha(self, alpha: f64) -> Oklch {
+        Oklch { alpha, ..self }
+    }
+
+    /// The color as a CSS `oklch()` color
+    pub fn to_css(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(color: Oklab) -> Oklch {
+        Oklch::new(
+            color.l,
+            color.a.hypot(color.b),
+            color.b.atan2(color.a).to_degrees().rem_euclid(360.0),
+            color.alpha,
+        )
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(color: Oklch) -> Oklab {
+        let (sin, cos) = color.h.to_radians().sin_cos();
+        Oklab::new(color.l, color.c * cos, color.c * sin, color.alpha)
+    }
+}
+
+impl From<Rgba> for Oklch {
+    fn from(color: Rgba) -> Oklch {
+        Oklab::from(color).into()
+    }
+}
+
+impl From<Oklch> for Rgba {
+    /// Converts back to sRGB, out of gamut colors are clamped when they are written out as CSS
+    fn from(color: Oklch) -> Rgba {
+        Oklab::from(color).into()
+    }
+}
+
+impl fmt::Display for Oklch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "oklch({}% {} {}",
+            round_to(self.l * 100.0, 2),
+            round_to(self.c, 4),
+            round_to(self.h.rem_euclid(360.0), 2)
+        )?;
+        write_alpha(f, self.alpha)?;
+        write!(f, ")")
+    }
+}
+
+/// Interpolates between two colors in OKLCH, turning the hue the shorter way around the color
+/// wheel. Like CSS, a grey end takes on the hue of the other end, so fading from white to blue
+/// doesn't pass through other hues, and the lightness and chroma are premultiplied by the alpha.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::color::{tween_oklab, tween_oklch, Oklch, Rgba};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let red = Oklch::from_hex("#ff0000").unwrap();
+/// let green = Oklch::from_hex("#00ff00").unwrap();
+/// // Halfway is a saturated orange, OKLab passes through a duller color of the same lightness
+/// let halfway = tween_oklch(&red, &green, 0.5);
+/// assert_eq!(Rgba::from(halfway).to_css(), "rgb(249, 149, 0)");
+/// let through_oklab = Oklch::from(tween_oklab(&red.into(), &green.into(), 0.5));
+/// assert!(through_oklab.c < halfway.c);
+///
+/// let (valid, set_valid) = signal(false);
+/// let border = AnimatedSignal::new(
+///     move || if valid.get() { green } else { red }.into(),
+///     tween_oklch,
+/// );
+/// # let _html =
+/// view! { <input style:border-color=move || border.get().to_css()/> }
+/// # .to_html();
+/// ```
+pub fn tween_oklch(from: &Oklch, to: &Oklch, progress: f64) -> Oklch {
+    let (from_hue, to_hue) = match (from.c < ACHROMATIC_THRESHOLD, to.c < ACHROMATIC_THRESHOLD) {
+        (true, false) => (to.h, to.h),
+        (false, true) => (from.h, from.h),
+        _ => (from.h, to.h),
+    };
+    let hue = tween_hue(from_hue, to_hue, progress, HueDirection::Shorter);
+
+    let from_alpha = from.alpha.clamp(0.0, 1.0);
+    let to_alpha = to.alpha.clamp(0.0, 1.0);
+    let alpha = from_alpha.tween(&to_alpha, progress);
+    if alpha <= 0.0 {
+        return Oklch::new(from.l.tween(&to.l, progress), 0.0, hue, alpha);
+    }
+    let channel =
+        |from: f64, to: f64| (from * from_alpha).tween(&(to * to_alpha), progress) / alpha;
+    Oklch::new(channel(from.l, to.l), channel(from.c, to.c), hue, alpha)
+}
+
+impl Tween for Oklch {
+    type Interpolated = Oklch;
+
+    fn tween(&self, other: &Self, progress: f64) -> Oklch {
+        tween_oklch(self, other, progress)
+    }
+}
+
+// Hues are added and subtracted as plain angles, which is consistent modulo a full turn, and the
+// CSS output wraps them into 0 - 360 degrees
+impl Add for Oklch {
+    type Output = Oklch;
+
+    fn add(self, rhs: Oklch) -> Oklch {
+        Oklch::new(
+            self.l + rhs.l,
+            self.c + rhs.c,
+            self.h + rhs.h,
+            self.alpha + rhs.alpha,
+        )
+    }
+}
+
+impl Sub for Oklch {
+    type Output = Oklch;
+
+    fn sub(self, rhs: Oklch) -> Oklch {
+        Oklch::new(
+            self.l - rhs.l,
+            self.c - rhs.c,
+            self.h - rhs.h,
+            self.alpha - rhs.alpha,
+        )
+    }
+}
+
+impl Mul<f64> for Oklch {
+    type Output = Oklch;
+
+    fn mul(self, rhs: f64) -> Oklch {
+        Oklch::new(self.l * rhs, self.c * rhs, self.h * rhs, self.alpha * rhs)
+    }
+}
+
+/// The way around the color wheel that an animated hue turns, like the hue interpolation methods
+/// of CSS
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum HueDirection {
+    /// Take the shorter arc, so 350° to 10° turns by 20° through 0°. This is the default
+    #[default]
+    Shorter,
+
+    /// Take the longer arc, so 350° to 10° turns backwards by 340°
+    Longer,
+
+    /// Always turn towards larger angles, through 0° if needed
+    Increasing,
+
+    /// Always turn towards smaller angles, through 0° if needed
+    Decreasing,
+}
+
+/// The hue at `progress` of the way from `from` to `to`, in degrees from 0 to 360, turning in
+/// `direction`. Equal hues never turn, not even the longer way.
+/// ```
+/// # use leptos_animation::color::{tween_hue, HueDirection};
+/// assert_eq!(tween_hue(350.0, 10.0, 0.5, HueDirection::Shorter), 0.0);
+/// assert_eq!(tween_hue(350.0, 10.0, 0.5, HueDirection::Longer), 180.0);
+/// assert_eq!(tween_hue(10.0, 350.0, 0.25, HueDirection::Increasing), 95.0);
+/// assert_eq!(tween_hue(10.0, 20.0, 0.5, HueDirection::Decreasing), 195.0);
+/// ```
+pub fn tween_hue(from: f64, to: f64, progress: f64, direction: HueDirection) -> f64 {
+    // The turn towards larger angles
+    let increasing = (to - from).rem_euclid(360.0);
+    let turn = match direction {
+        _ if increasing == 0.0 => 0.0,
+        HueDirection::Shorter if increasing > 180.0 => increasing - 360.0,
+        HueDirection::Longer if increasing < 180.0 => increasing - 360.0,
+        HueDirection::Decreasing => increasing - 360.0,
+        _ => increasing,
+    };
+    (from + turn * progress).rem_euclid(360.0)
+}
+
+/// A color in the HSL form of sRGB, as a hue `h` in degrees, a saturation `s` and a lightness `l`
+/// from 0.0 to 1.0 and an alpha from 0.0 to 1.0.
+///
+/// Animating the hue of an HSL color turns it around the color wheel, which is what color pickers
+/// and hue-cycling effects need. Animate HSL colors with [`tween_hsla`] and format them as a CSS
+/// `hsl()` color with [`to_css()`](Hsla::to_css), or convert them into an [`Rgba`].
+/// ```
+/// # use leptos_animation::color::{Hsla, Rgba};
+/// let teal = Hsla::new(180.0, 1.0, 0.25, 1.0);
+/// assert_eq!(teal.to_css(), "hsl(180, 100%, 25%)");
+/// assert_eq!(Rgba::from(teal).to_css(), "rgb(0, 128, 128)");
+/// assert_eq!(Hsla::from_hex("#ff000080").unwrap().to_css(), "hsla(0, 100%, 50%, 0.502)");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Hsla {
+    /// The hue angle in degrees, 0 is red, 120 green and 240 blue
+    pub h: f64,
+    /// The saturation, 0.0 is grey
+    pub s: f64,
+    /// The lightness, 0.0 is black and 1.0 is white
+    pub l: f64,
+    /// The opacity, 0.0 is fully transparent
+    pub a: f64,
+}
+
+impl Hsla {
+    /// A color from its hue in degrees, saturation, lightness and an alpha from 0.0 to 1.0
+    pub const fn new(h: f64, s: f64, l: f64, a: f64) -> Hsla {
+        Hsla { h, s, l, a }
+    }
+
+    /// Parses a CSS hex color, see [`Rgba::from_hex()`]
+    pub fn from_hex(hex: &str) -> Option<Hsla> {
+        Rgba::from_hex(hex).map(Hsla::from)
+    }
+
+    /// The same color with another alpha
+    pub fn with_alpha(self, a: f64) -> Hsla {
+        Hsla { a, ..self }
+    }
+
+    /// The color as a CSS `hsl()` or, if it is not opaque, `hsla()` color
+    pub fn to_css(&self) -> String {
+        self.to_string()
+    }
+
+    /// Whether the color is a grey, black or white, whose hue is meaningless
+    fn is_achromatic(&self) -> bool {
+        self.s < ACHROMATIC_THRESHOLD
+            || self.l < ACHROMATIC_THRESHOLD
+            || self.l > 1.0 - ACHROMATIC_THRESHOLD
+    }
+}
+
+impl From<Rgba> for Hsla {
+    fn from(color: Rgba) -> Hsla {
+        let max = color.r.max(color.g).max(color.b);
+        let min = color.r.min(color.g).min(color.b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+        if delta <= 0.0 {
+            return Hsla::new(0.0, 0.0, l, color.a);
+        }
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let sector = if max == color.r {
+            (color.g - color.b) / delta
+        } else if max == color.g {
+            (color.b - color.r) / delta + 2.0
+        } else {
+            (color.r - color.g) / delta + 4.0
+        };
+        Hsla::new((sector * 60.0).rem_euclid(360.0), s, l, color.a)
+    }
+}
+
+impl From<Hsla> for Rgba {
+    /// Converts to sRGB, see <https://www.w3.org/TR/css-color-4/#hsl-to-rgb>
+    fn from(color: Hsla) -> Rgba {
+        let amount = color.s * color.l.min(1.0 - color.l);
+        let channel = |n: f64| {
+            let k = (n + color.h / 30.0).rem_euclid(12.0);
+            color.l - amount * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        };
+        Rgba::new(channel(0.0), channel(8.0), channel(4.0), color.a)
+    }
+}
+
+impl fmt::Display for Hsla {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let h = round_to(self.h.rem_euclid(360.0), 2);
+        let [s, l] = [self.s, self.l].map(|value| round_to(value.clamp(0.0, 1.0) * 100.0, 2));
+        let a = self.a.clamp(0.0, 1.0);
+        if a >= 1.0 {
+            write!(f, "hsl({h}, {s}%, {l}%)")
+        } else {
+            write!(f, "hsla({h}, {s}%, {l}%, {})", round_to(a, 3))
+        }
+    }
+}
+
+/// Interpolates between two HSL colors, turning the hue the shorter way around the color wheel,
+/// see [`tween_hsla_with()`] to choose the direction.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::color::{tween_hsla, Hsla};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let magenta = Hsla::new(350.0, 0.8, 0.5, 1.0);
+/// let orange = Hsla::new(10.0, 0.8, 0.5, 1.0);
+/// // Turns through red instead of all the way around through green and blue
+/// assert_eq!(tween_hsla(&magenta, &orange, 0.5).h, 0.0);
+///
+/// let (warning, set_warning) = signal(false);
+/// let badge = AnimatedSignal::new(
+///     move || if warning.get() { orange } else { magenta }.into(),
+///     tween_hsla,
+/// );
+/// # let _html =
+/// view! { <span style:background-color=move || badge.get().to_css()></span> }
+/// # .to_html();
+/// ```
+pub fn tween_hsla(from: &Hsla, to: &Hsla, progress: f64) -> Hsla {
+    tween_hsla_with(from, to, progress, HueDirection::Shorter)
+}
+
+/// Interpolates between two HSL colors, turning the hue in `direction`. Like CSS, a grey end takes
+/// on the hue of the other end and the saturation and lightness are premultiplied by the alpha.
+///
+/// A closure that picks the direction can be passed to
+/// [`AnimatedSignal::new()`](crate::AnimatedSignal::new) as the tween function.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::color::{tween_hsla_with, Hsla, HueDirection};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// // A rainbow that keeps turning the same way every time the hue advances
+/// let (hue, set_hue) = signal(0.0);
+/// let rainbow = AnimatedSignal::new(
+///     move || Hsla::new(hue.get(), 1.0, 0.5, 1.0).into(),
+///     |from, to, progress| tween_hsla_with(from, to, progress, HueDirection::Increasing),
+/// );
+/// ```
+pub fn tween_hsla_with(from: &Hsla, to: &Hsla, progress: f64, direction: HueDirection) -> Hsla {
+    let (from_hue, to_hue) = match (from.is_achromatic(), to.is_achromatic()) {
+        (true, false) => (to.h, to.h),
+        (false, true) => (from.h, from.h),
+        _ => (from.h, to.h),
+    };
+    let hue = tween_hue(from_hue, to_hue, progress, direction);
+
+    let from_alpha = from.a.clamp(0.0, 1.0);
+    let to_alpha = to.a.clamp(0.0, 1.0);
+    let alpha = from_alpha.tween(&to_alpha, progress);
+    if alpha <= 0.0 {
+        return Hsla::new(
+            hue,
+            from.s.tween(&to.s, progress),
+            from.l.tween(&to.l, progress),
+            alpha,
+        );
+    }
+    let channel =
+        |from: f64, to: f64| (from * from_alpha).tween(&(to * to_alpha), progress) / alpha;
+    Hsla::new(hue, channel(from.s, to.s), channel(from.l, to.l), alpha)
+}
+
+impl Tween for Hsla {
+    type Interpolated = Hsla;
+
+    fn tween(&self, other: &Self, progress: f64) -> Hsla {
+        tween_hsla(self, other, progress)
+    }
+}
+
+// Like those of `Oklch`, hues are added and subtracted as plain angles
+impl Add for Hsla {
+    type Output = Hsla;
+
+    fn add(self, rhs: Hsla) -> Hsla {
+        Hsla::new(
+            self.h + rhs.h,
+            self.s + rhs.s,
+            self.l + rhs.l,
+            self.a + rhs.a,
+        )
+    }
+}
+
+impl Sub for Hsla {
+    type Output = Hsla;
+
+    fn sub(self, rhs: Hsla) -> Hsla {
+        Hsla::new(
+            self.h - rhs.h,
+            self.s - rhs.s,
+            self.l - rhs.l,
+            self.a - rhs.a,
+        )
+    }
+}
+
+impl Mul<f64> for Hsla {
+    type Output = Hsla;
+
+    fn mul(self, rhs: f64) -> Hsla {
+        Hsla::new(self.h * rhs, self.s * rhs, self.l * rhs, self.a * rhs)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn signs_are_no_hex_digits() {
+        assert_eq!(Rgba::from_hex("#+fff00"), None);
+        assert_eq!(Rgba::from_hex("+f+f+f"), None);
+    }
+
+    proptest! {
+        #[test]
+        fn hex_colors_read_back(r: u8, g: u8, b: u8, a: u8) {
+            let hex = format!("#{r:02x}{g:02x}{b:02x}{a:02x}");
+            prop_assert_eq!(Rgba::from_hex(&hex), Some(Rgba::rgba8(r, g, b, a)));
+            let hex = format!("{r:02X}{g:02X}{b:02X}");
+            prop_assert_eq!(Rgba::from_hex(&hex), Some(Rgba::rgb8(r, g, b)));
+        }
+
+        #[test]
+        fn short_hex_colors_repeat_their_digits(r in 0..16u8, g in 0..16u8, b in 0..16u8) {
+            let hex = format!("#{r:x}{g:x}{b:x}");
+            prop_assert_eq!(Rgba::from_hex(&hex), Some(Rgba::rgb8(r * 17, g * 17, b * 17)));
+        }
+
+        #[test]
+        fn hex_colors_with_other_characters_are_rejected(
+            hex in "[0-9a-f]{0,7}",
+            other in "[+\\- g-zG-Z.,]|[^0-9a-fA-F#]",
+            index in 0..8usize,
+        ) {
+            let mut hex = hex;
+            hex.insert_str(index.min(hex.len()), &other);
+            prop_assert_eq!(Rgba::from_hex(&hex), None);
+            prop_assert_eq!(Rgba::from_hex(&format!("#{hex}")), None);
+        }
+
+        #[test]
+        fn any_text_parses_without_panicking(hex in "#?\\PC{0,10}") {
+            let _ = Rgba::from_hex(&hex);
+            let _ = Hsla::from_hex(&hex);
+        }
+
+        #[test]
+        fn colors_tween_between_their_channels(
+            from: (u8, u8, u8),
+            to: (u8, u8, u8),
+            progress in 0.0..=1.0f64,
+        ) {
+            let from = Rgba::rgb8(from.0, from.1, from.2);
+            let to = Rgba::rgb8(to.0, to.1, to.2);
+            let value = from.tween(&to, progress);
+            let channels = [(value.r, from.r, to.r), (value.g, from.g, to.g), (value.b, from.b, to.b)];
+            for (value, from, to) in channels {
+                prop_assert!(value >= from.min(to) - 1e-9 && value <= from.max(to) + 1e-9);
+            }
+        }
+    }
+}
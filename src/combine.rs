@@ -0,0 +1,97 @@
+use leptos::prelude::*;
+
+/// Creates a derived signal from multiple inputs that updates at most once per animation frame.
+///
+/// The `track` callback should subscribe to all inputs, the `compute` callback calculates the output
+/// from the untracked values of the inputs. Animated signals all move on to their next value in the
+/// same frame, so the output is recalculated once when it is read after that frame, no matter how
+/// many of the inputs have changed, and shows the values of the inputs of that same frame.
+/// This avoids redundant recalculations of expensive view math while several animations are playing.
+///
+/// It is usually more convenient to use the [`combine!`](crate::combine!) macro, which generates both callbacks.
+pub fn combine<U>(
+    track: impl Fn() + Send + Sync + 'static,
+    compute: impl Fn() -> U + Send + Sync + 'static,
+) -> Signal<U>
+where
+    U: PartialEq + Send + Sync + 'static,
+{
+    Memo::new_owning(move |prev: Option<U>| {
+        track();
+        let value = compute();
+        let changed = prev.as_ref() != Some(&value);
+        (value, changed)
+    })
+    .into()
+}
+
+#[doc(hidden)]
+pub use leptos::prelude::{GetUntracked as __GetUntracked, Track as __Track};
+
+/// Combines multiple (animated) signals into a single derived signal that updates at most once
+/// per animation frame, even when several of the inputs change. See [`combine()`](crate::combine::combine).
+///
+/// The first argument is a tuple of signals, the second a closure that receives their values.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::{combine, AnimatedSignal, tween_default};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (x, set_x) = signal(3.0);
+/// let (y, set_y) = signal(4.0);
+/// let animated_x = AnimatedSignal::new(move || x.get().into(), tween_default);
+/// let animated_y = AnimatedSignal::new(move || y.get().into(), tween_default);
+///
+/// let distance = combine!((animated_x, animated_y), |x: f64, y: f64| (x * x + y * y).sqrt());
+/// assert_eq!(distance.get_untracked(), 5.0);
+/// ```
+#[macro_export]
+macro_rules! combine {
+    (($($input:expr),+ $(,)?), $compute:expr) => {
+        $crate::combine::combine(
+            move || {
+                use $crate::combine::__Track;
+                $($input.track();)+
+            },
+            move || {
+                use $crate::combine::__GetUntracked;
+                ($compute)($($input.get_untracked()),+)
+            },
+        )
+    };
+}
+
+#[cfg(all(test, not(feature = "disable-animations")))]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::testing::TestAnimationHarness;
+    use crate::{easing, tween_default, AnimatedSignal};
+
+    #[test]
+    fn combined_values_follow_their_inputs_in_the_same_frame() {
+        let harness = TestAnimationHarness::new();
+        let (target, set_target) = signal(0.0);
+        let linear = move || (target.get(), Duration::from_secs(1), easing::LINEAR).into();
+        let x: AnimatedSignal<f64, f64> = AnimatedSignal::new(linear, tween_default);
+        let y: AnimatedSignal<f64, f64> = AnimatedSignal::new(linear, tween_default);
+        let computed = StoredValue::new(0);
+        let sum = combine!((x, y), move |x: f64, y: f64| {
+            computed.update_value(|computed| *computed += 1);
+            x + y
+        });
+        harness.observe(sum);
+
+        set_target.set(100.0);
+        harness.step();
+        let computed_before = computed.get_value();
+        for _ in 0..5 {
+            // A frame without running the effects afterwards, as a view reads the values in the frame
+            harness.advance(Duration::from_millis(100));
+            harness.context().flush_now();
+            assert!(x.get_untracked() > 0.0);
+            assert_eq!(sum.get_untracked(), x.get_untracked() + y.get_untracked());
+        }
+        assert_eq!(computed.get_value() - computed_before, 5);
+    }
+}
@@ -0,0 +1,171 @@
+//! The free function API of earlier versions of this crate, and of the Leptos versions they were
+//! built for.
+//!
+//! These functions are kept so downstream crates can upgrade without rewriting all of their call
+//! sites at once. They are deprecated and will be removed in a future release; disable the
+//! `compat` feature to make sure nothing depends on them anymore.
+
+use std::ops::Sub;
+
+// The reactive primitives of Leptos 0.6, which Leptos 0.7 still ships as deprecated functions.
+// They are the same functions as in `leptos::prelude`, so importing both preludes is fine
+#[allow(deprecated)]
+pub use leptos::prelude::{
+    create_effect, create_memo, create_rw_signal, create_signal, create_trigger, store_value,
+};
+
+use crate::{AnimatedSignal, AnimationContext, AnimationTarget};
+
+/// Sets up an AnimationContext for this scope and all child scopes. For normal use you only
+/// need to call this once in a root component of the application.
+#[deprecated(note = "please use `AnimationContext::provide()` instead")]
+pub fn provide_animation_context() -> AnimationContext {
+    AnimationContext::provide()
+}
+
+/// This method can be used instead of `provide` when you are in a non-web environment such as
+/// a desktop application. *For web environments it is recommended to use the normal `provide` instead*
+///
+/// There are two extra callbacks that have to be correctly called and implemented in order
+/// for this library to correctly function.
+///
+/// The callback given in the argument has to call some function that triggers an animation frame
+/// request. For example, in the `winit` crate this would be calling [`Window::request_redraw()`](https://docs.rs/winit/latest/winit/window/struct.Window.html#method.request_redraw).
+/// This callback will be called at most once per animation frame.
+///
+/// The callback returned from this function should be called when the animation frame from the
+/// previous callback has arrived.
+/// For example, in the `winit` crate this should be called when the [`WindowEvent::RedrawRequested`](https://docs.rs/winit/latest/winit/event/enum.WindowEvent.html#variant.RedrawRequested) event happens
+/// Extraneous calls to this callback are ignored.
+///
+/// ````
+/// # #![allow(deprecated)]
+/// # // Lots of boilerplate to simulate winit environment
+/// # use leptos::prelude::*;
+/// # use leptos_animation::provide_animation_context_with_custom_request_animation_frame;
+/// # struct Window {}
+/// # impl Window { fn request_redraw(&self) {} }
+/// # let window = Window {};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// # struct EventLoop {};
+/// # impl EventLoop { fn run(&self, f: impl Fn(Event, ())) {} }
+/// # let event_loop = EventLoop {};
+/// # enum WindowEvent { RedrawRequested }
+/// # enum Event { WindowEvent { event: WindowEvent}, Other }
+/// let (_, on_redraw_requested) =
+///         provide_animation_context_with_custom_request_animation_frame(move || {
+///             window.request_redraw();
+///         });
+///
+/// event_loop.run(move |event, elwt| match event {
+///         Event::WindowEvent {
+///             event: WindowEvent::RedrawRequested,
+///             ..
+///         } => on_redraw_requested(),
+///         _ => {}
+/// });
+///
+/// ````
+#[deprecated(
+    note = "please use `AnimationContext::provide_with_custom_request_animation_frame()` instead"
+)]
+pub fn provide_animation_context_with_custom_request_animation_frame(
+    callback: impl Fn() + 'static,
+) -> (AnimationContext, impl Fn()) {
+    AnimationContext::provide_with_custom_request_animation_frame(callback)
+}
+
+/// Create a derived signal that animated the value of the input signals.
+/// Takes as input a reactive source callback function and a tween function.
+///
+/// The source callback function is run in a reactive context and is expected to take the value of one or more input
+/// signals and return an `AnimationTarget` value. An `AnimationTarget` specifies a target value to
+/// animate towards and details about the duration, easing and animation of how to animate towards it.
+/// There are shortcut methods to create an `AnimationTarget` with default values, see
+/// [`AnimationTarget`] for details.
+///
+/// The tween callback specifies how to interpolate between two input values. As input it takes three
+/// arguments: `from`, `to` and `progress`. Where `from` and `to` are the values from the input signal
+/// and the `progress` is a value between 0.0 - 1.0. The easing is already applied to the `progress`.
+/// The tween function is expected to do a linear interpolation between `from` & `to` and return the
+/// result.
+///
+/// If the input is in any way numeric or supports the `Add`, `Sub` and `Mul<f64>` traits it is recommended
+/// to use the [`tween_default`](crate::tween_default) function as input which performs a simple `(to - from) * progress + from`.
+///
+/// If you are dealing with structs that are composed of numbers (for example a `Position { x: f64, y: f64 }`)
+/// you can use the [derive_more](https://docs.rs/crate/derive_more/latest) crate to implement the necessary traits.
+/// This way you can still use the `tween_default` function.
+///
+/// This function is generic over two types: `T` and `I`.
+/// * `T` is the type of values that are animated between. Animations are always from a `T` towards another `T`
+/// * `I` is the type of the interpolated values between values of type `T`.
+///
+/// In simple cases `I` is the same as `T` such as animating between `f64`'s. But they can also be different
+/// if for example the `T` is an enum which cannot represent 'in-between' values by itself.
+///
+/// Updates to the derived signal only happen on browser animation frames and only when there are animations
+/// running. If you are dealing with a HTML Canvas it is recommended to use a `create_effect()` to draw on the
+/// canvas and subscribe directly to the animated signals.
+/// All animated signals update simultaneously on animation frames so even if you subscribe to multiple animated
+/// input signals the effect will never run more than 60fps.
+///
+/// # Additive animations
+///
+/// This library uses an additive animation system. This means that multiple animations with different
+/// targets and different durations can play simultaneously without them interrupting each other.
+///
+/// Internally all animations are towards 0. For example if we start an animation from 0 to 100, this is
+/// converted to an animation from -100 to 0 which gets added to the final 100 value.
+///
+/// If then a second animation is started from 100 to 1000 it gets converted to an animation from -900 to 0.
+/// Both the -100 to 0 and the -900 to 0 animation value get added to the final 1000 value until both settle on 1000 as they reach 0.
+///
+/// This allows for all animations to play to completion even if animations are started before the previous animation is finished.
+///
+/// # Examples
+/// ```
+/// # #![allow(deprecated)]
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (value, set_value) = create_signal(42.0);
+///
+/// // Simple default animation
+/// let animated_value = create_animated_signal(move || value.get().into(), tween_default);
+///
+/// // Custom duration
+/// let slow_value = create_animated_signal(move || (value.get(), Duration::from_secs_f64(5.0)).into(), tween_default::<f64, f64>);
+///
+/// // Custom duration, easing & mode
+/// let custom_value = create_animated_signal(
+///         move || AnimationTarget {
+///             target: value.get(),
+///             duration: Duration::from_secs_f64(1.5),
+///             easing: easing::ELASTIC_IN_OUT,
+///             mode: AnimationMode::ReplaceOrStart,
+///             ..Default::default()
+///         },
+///         tween_default);
+///
+/// // Custom tween function
+/// let tween_value = create_animated_signal(
+///         move || value.get().into(),
+///         |from, to, progress| {
+///             (to - from) * progress + from
+///         });
+/// ```
+#[deprecated(note = "please use `AnimatedSignal::new()` instead")]
+pub fn create_animated_signal<T, I>(
+    source: impl Fn() -> AnimationTarget<T> + 'static,
+    tween: fn(&T, &T, f64) -> I,
+) -> AnimatedSignal<T, I>
+where
+    T: 'static,
+    T: Clone,
+    I: Clone,
+    I: Sub<I, Output = I>,
+{
+    AnimatedSignal::new(source, tween)
+}
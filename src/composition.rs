@@ -0,0 +1,71 @@
+use std::ops::{Div, Mul};
+
+#[cfg(not(feature = "disable-animations"))]
+use leptos::prelude::*;
+
+use crate::AnimatedSignal;
+
+/// The `Composition` specifies how the values of simultaneously running animations are combined
+/// into a single output value.
+///
+/// The default is [`Additive`](Composition::Additive), see the
+/// additive animations section of [`AnimatedSignal::new()`] for how that works.
+/// Addition is the wrong operation for some types, like scale factors or transforms. For those a
+/// custom pair of functions can be given that do the same in terms of another operation:
+/// * `uncompose(value, target)` calculates how far the value of an animation is removed from its target
+/// * `compose(accumulated, offset)` applies such an offset to the accumulated output value
+///
+/// `compose(target, uncompose(value, target))` is expected to equal `value`. For an additive
+/// composition these are `value - target` and `accumulated + offset`, for a multiplicative composition
+/// `value / target` and `accumulated * offset`.
+///
+/// For types where the operation is not commutative, like transformation matrices, the order
+/// within `compose` decides whether the offsets are applied in world space (`offset * accumulated`)
+/// or in local space (`accumulated * offset`).
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Composition<I> {
+    /// Offsets of running animations are subtracted from the target value
+    #[default]
+    Additive,
+
+    /// Offsets of running animations are calculated and applied with a custom pair of functions
+    Custom {
+        /// Applies an offset to the accumulated value
+        compose: fn(&I, &I) -> I,
+        /// Calculates the offset of a value with respect to a target
+        uncompose: fn(&I, &I) -> I,
+    },
+}
+
+impl<I> Composition<I> {
+    /// A composition that multiplies the offsets of running animations, for scale factors and the like
+    pub fn multiplicative() -> Composition<I>
+    where
+        I: Clone + Mul<I, Output = I> + Div<I, Output = I>,
+    {
+        Composition::Custom {
+            compose: |accumulated, offset| accumulated.clone() * offset.clone(),
+            uncompose: |value, target| value.clone() / target.clone(),
+        }
+    }
+}
+
+impl<T, I> AnimatedSignal<T, I> {
+    /// Sets the [`Composition`] which is used to combine simultaneously running animations
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, composition::Composition, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (zoom, set_zoom) = signal(1.0);
+    /// let animated_zoom = AnimatedSignal::new(move || zoom.get().into(), tween_default)
+    ///     .with_composition(Composition::multiplicative());
+    /// ```
+    pub fn with_composition(self, composition: Composition<I>) -> Self {
+        // Signals without animations have nothing to apply the setting to
+        #[cfg(not(feature = "disable-animations"))]
+        self.composition.set_value(composition);
+        #[cfg(feature = "disable-animations")]
+        let _ = composition;
+        self
+    }
+}
@@ -0,0 +1,60 @@
+//! Settings that shape the animations of a signal without being a reason to animate, such as a
+//! speed preference.
+
+#[cfg(not(feature = "disable-animations"))]
+use std::rc::Rc;
+
+use leptos::prelude::*;
+
+use crate::{AnimatedSignal, AnimationTarget};
+
+/// Applies the current configuration to a target before it starts animating
+#[cfg(not(feature = "disable-animations"))]
+pub(crate) type ConfigureFn<T> = Rc<dyn Fn(&mut AnimationTarget<T>)>;
+
+impl<T, I> AnimatedSignal<T, I> {
+    /// Adjusts every target of this signal with `apply` and the current value of `config`, for
+    /// settings such as the duration or easing of the animations. Reading the settings in the
+    /// source instead makes every change of them a change of the source, which starts an
+    /// animation towards the same target. The configuration is only read when a target is applied,
+    /// so changing it never starts an animation and only affects the animations started after it.
+    ///
+    /// Targets are adjusted before the context and the [`AnimationBudget`](crate::budget::AnimationBudget)
+    /// adapt them, so for example reduced motion still applies. A later call replaces the
+    /// configuration of an earlier one.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (open, set_open) = signal(false);
+    /// // A setting of the user, which speeds up all animations
+    /// let (speed, set_speed) = signal(1.0);
+    /// let height = AnimatedSignal::new(move || if open.get() { 200.0 } else { 0.0 }.into(), tween_default)
+    ///     .with_config(speed, |speed: &f64, target| {
+    ///         target.duration = target.duration.div_f64(*speed);
+    ///     });
+    ///
+    /// // Nothing moves, the next time the panel opens it takes half as long
+    /// set_speed.set(2.0);
+    /// set_open.set(true);
+    /// ```
+    pub fn with_config<C: Send + Sync + 'static>(
+        self,
+        config: impl Into<Signal<C>>,
+        apply: impl Fn(&C, &mut AnimationTarget<T>) + 'static,
+    ) -> Self {
+        // Signals without animations have nothing to apply the setting to
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let config = config.into();
+            let configure: ConfigureFn<T> = Rc::new(move |animation_target| {
+                config.with_untracked(|config| apply(config, animation_target));
+            });
+            self.configure.set_value(Some(configure));
+        }
+        #[cfg(feature = "disable-animations")]
+        let _ = (config, apply);
+        self
+    }
+}
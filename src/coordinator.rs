@@ -0,0 +1,122 @@
+//! A single animation frame loop per window, shared by every [`AnimationContext`](crate::AnimationContext) on the page.
+//!
+//! With islands or other forms of partial hydration every island has its own reactive root, so
+//! every island provides its own `AnimationContext`. Instead of each of them requesting their own
+//! animation frames, all contexts register with this coordinator which requests at most one
+//! animation frame from each window and calls back every context that asked for it.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use leptos::prelude::*;
+use leptos::wasm_bindgen::closure::Closure;
+use leptos::wasm_bindgen::JsCast;
+
+type FrameCallback = Box<dyn FnOnce()>;
+
+enum FrameHandle {
+    /// A frame requested from the window of the application
+    Main(AnimationFrameRequestHandle),
+    /// A frame requested from another window such as an iframe or popup
+    Window(i32),
+}
+
+struct Coordinator {
+    /// The window this coordinator requests frames from, `None` for the window of the application
+    window: Option<web_sys::Window>,
+    handle: Option<FrameHandle>,
+    callbacks: Vec<(u64, FrameCallback)>,
+}
+
+thread_local! {
+    static COORDINATORS: RefCell<Vec<Coordinator>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A new unique id for a context registering with the coordinator
+pub(crate) fn next_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Calls `callback` on the next animation frame of `window`, sharing the frame request with all
+/// other contexts of that window
+pub(crate) fn request(
+    id: u64,
+    window: Option<&web_sys::Window>,
+    callback: impl FnOnce() + 'static,
+) {
+    COORDINATORS.with_borrow_mut(|coordinators| {
+        let index = match coordinators
+            .iter()
+            .position(|coordinator| coordinator.window.as_ref() == window)
+        {
+            Some(index) => index,
+            None => {
+                coordinators.push(Coordinator {
+                    window: window.cloned(),
+                    handle: None,
+                    callbacks: Vec::new(),
+                });
+                coordinators.len() - 1
+            }
+        };
+
+        let coordinator = &mut coordinators[index];
+        coordinator.callbacks.push((id, Box::new(callback)));
+        if coordinator.handle.is_none() {
+            coordinator.handle = Some(match window {
+                None => FrameHandle::Main(
+                    request_animation_frame_with_handle(move || on_frame(None)).unwrap(),
+                ),
+                Some(window) => {
+                    let frame_window = window.clone();
+                    let closure = Closure::once_into_js(move || on_frame(Some(&frame_window)));
+                    FrameHandle::Window(
+                        window
+                            .request_animation_frame(closure.unchecked_ref())
+                            .unwrap(),
+                    )
+                }
+            });
+        }
+    });
+}
+
+/// Removes the pending callback of a context, cancelling the frame request if nobody else needs it
+pub(crate) fn cancel(id: u64) {
+    COORDINATORS.with_borrow_mut(|coordinators| {
+        for coordinator in coordinators.iter_mut() {
+            coordinator.callbacks.retain(|(other, _)| *other != id);
+            if coordinator.callbacks.is_empty() {
+                match coordinator.handle.take() {
+                    Some(FrameHandle::Main(handle)) => handle.cancel(),
+                    Some(FrameHandle::Window(handle)) => {
+                        if let Some(window) = &coordinator.window {
+                            let _ = window.cancel_animation_frame(handle);
+                        }
+                    }
+                    None => {}
+                }
+            }
+        }
+        // Forget windows nobody animates in anymore, so closed popups can be garbage collected
+        coordinators.retain(|coordinator| coordinator.handle.is_some());
+    });
+}
+
+fn on_frame(window: Option<&web_sys::Window>) {
+    // Callbacks may request the next frame, so release the borrow before calling them
+    let callbacks = COORDINATORS.with_borrow_mut(|coordinators| {
+        coordinators
+            .iter_mut()
+            .find(|coordinator| coordinator.window.as_ref() == window)
+            .map(|coordinator| {
+                coordinator.handle = None;
+                std::mem::take(&mut coordinator.callbacks)
+            })
+            .unwrap_or_default()
+    });
+    for (_, callback) in callbacks {
+        callback();
+    }
+}
@@ -0,0 +1,374 @@
+//! CSS renditions of simple animations, so entrance animations play before the wasm has loaded.
+
+use std::fmt::Write;
+use std::ops::Deref;
+use std::time::Duration;
+
+use leptos::prelude::*;
+use leptos::wasm_bindgen::JsCast;
+
+use crate::{Animation, AnimationContext, AnimationTarget, Easing, RepeatMode};
+
+/// The number of samples of an easing in the CSS `linear()` function that approximates it
+const EASING_SAMPLES: usize = 20;
+
+struct CssTrack {
+    property: String,
+    duration: Duration,
+    delay: Duration,
+    repeat: RepeatMode,
+    /// The offset, value and the easing of the segment that starts at this offset
+    stops: Vec<(f64, String, Easing)>,
+}
+
+/// An animation of CSS properties that is rendered as a CSS animation into a `<style>` tag.
+///
+/// A server-rendered page can show its above-the-fold entrance animations right away, without
+/// waiting for the wasm to load and hydrate. Every property is animated from a value towards an
+/// [`AnimationTarget`], with the same duration, easing, delay, keyframes and repetitions as the
+/// animation engine would use (the mode does not apply). Easings are sampled into the CSS
+/// `linear()` timing function.
+///
+/// Render [`style()`](CssAnimation::style) once and put [`class()`](CssAnimation::class) on the
+/// animated element. The class is removed once the component has been hydrated, so any animated
+/// signals take over from there. An [`Entrance`] continues exactly where the CSS animation was at
+/// that moment.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::css::CssAnimation;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let fade_in = CssAnimation::new("hero-fade-in")
+///     .property("opacity", 0.0, (1.0, Duration::from_millis(600)), |opacity| opacity.to_string())
+///     .property(
+///         "transform",
+///         20.0,
+///         AnimationTarget::from((0.0, Duration::from_millis(600), Duration::from_millis(100), easing::BACK_OUT))
+///             .keyframe(0.5, -5.0, easing::LINEAR),
+///         |offset| format!("translateY({offset}px)"),
+///     );
+///
+/// assert!(fade_in.css().contains("@keyframes hero-fade-in-0"));
+/// # let _html =
+/// view! {
+///     {fade_in.style()}
+///     <h1 class=fade_in.class()>"Welcome"</h1>
+/// }
+/// # .to_html();
+/// ```
+pub struct CssAnimation {
+    name: String,
+    tracks: Vec<CssTrack>,
+}
+
+impl CssAnimation {
+    /// An animation without properties. The `name` is used both as the class name and as the
+    /// prefix of the keyframe rules, so it has to be unique within the page.
+    pub fn new(name: impl Into<String>) -> CssAnimation {
+        CssAnimation {
+            name: name.into(),
+            tracks: Vec::new(),
+        }
+    }
+
+    /// Animates `property` from `from` towards `target`, with `format` turning values into CSS
+    pub fn property<T>(
+        mut self,
+        property: impl Into<String>,
+        from: T,
+        target: impl Into<AnimationTarget<T>>,
+        format: impl Fn(&T) -> String,
+    ) -> Self {
+        let target = target.into();
+
+        // A keyframe easing applies to the segment ending at it, CSS needs the one starting at it
+        let mut stops = Vec::with_capacity(target.keyframes.len() + 2);
+        let mut offset = 0.0;
+        let mut value = format(&from);
+        for keyframe in &target.keyframes {
+            stops.push((offset, value, keyframe.easing.clone()));
+            offset = keyframe.offset;
+            value = format(&keyframe.value);
+        }
+        stops.push((offset, value, target.easing.clone()));
+        stops.push((1.0, format(&target.target), target.easing.clone()));
+
+        self.tracks.push(CssTrack {
+            property: property.into(),
+            duration: target.duration,
+            delay: target.delay,
+            repeat: target.repeat,
+            stops,
+        });
+        self
+    }
+
+    /// The class that applies the animation to an element
+    pub fn class_name(&self) -> &str {
+        &self.name
+    }
+
+    /// The class of the animated element, which is removed once the component was hydrated.
+    /// Without hydration (during server-side rendering) it keeps the class name.
+    pub fn class(&self) -> Signal<String> {
+        let class = RwSignal::new(self.name.clone());
+        Effect::new(move |_| class.set(String::new()));
+        class.into()
+    }
+
+    /// How far the CSS animation has progressed in the document, including its delay. This is
+    /// `None` if it is not running, such as when the class was never applied.
+    pub fn elapsed(&self) -> Option<Duration> {
+        #[cfg(target_arch = "wasm32")]
+        let document = web_sys::window().and_then(|window| window.document());
+        #[cfg(not(target_arch = "wasm32"))]
+        let document: Option<web_sys::Document> = None;
+
+        let prefix = format!("{}-", self.name);
+        document?
+            .get_animations()
+            .iter()
+            .filter_map(|animation| animation.dyn_into::<web_sys::CssAnimation>().ok())
+            .filter(|animation| animation.animation_name().starts_with(&prefix))
+            .filter_map(|animation| animation.current_time())
+            .map(|milliseconds| Duration::from_secs_f64(milliseconds.max(0.0) / 1000.0))
+            .max()
+    }
+
+    /// The keyframe rules and the class rule of the animation
+    pub fn css(&self) -> String {
+        let mut css = String::new();
+        let mut animations = Vec::with_capacity(self.tracks.len());
+        for (index, track) in self.tracks.iter().enumerate() {
+            let keyframes = format!("{}-{index}", self.name);
+            let _ = write!(css, "@keyframes {keyframes} {{");
+            for (offset, value, easing) in &track.stops {
+                let _ = write!(
+                    css,
+                    " {}% {{ {}: {value}; animation-timing-function: {}; }}",
+                    format_number(offset * 100.0),
+                    track.property,
+                    css_easing(easing)
+                );
+            }
+            css.push_str(" }\n");
+
+            let iterations = match track.repeat.count() {
+                Some(count) => count.to_string(),
+                None => "infinite".to_string(),
+            };
+            let direction = match track.repeat.alternates() {
+                true => "alternate",
+                false => "normal",
+            };
+            animations.push(format!(
+                "{keyframes} {}s linear {}s {iterations} {direction} both",
+                format_number(track.duration.as_secs_f64()),
+                format_number(track.delay.as_secs_f64()),
+            ));
+        }
+        let _ = writeln!(
+            css,
+            ".{} {{ animation: {}; }}",
+            self.name,
+            animations.join(", ")
+        );
+        css
+    }
+
+    /// A `<style>` tag containing the [`css()`](CssAnimation::css)
+    pub fn style(&self) -> impl IntoView {
+        view! { <style>{self.css()}</style> }
+    }
+}
+
+/// A CSS `linear()` timing function that approximates `easing`
+fn css_easing(easing: &Easing) -> String {
+    css_linear(|progress| easing.ease(progress))
+}
+
+/// A CSS `linear()` timing function that approximates the timing function `ease`
+pub(crate) fn css_linear(ease: impl Fn(f64) -> f64) -> String {
+    let points: Vec<String> = (0..=EASING_SAMPLES)
+        .map(|sample| format_number(ease(sample as f64 / EASING_SAMPLES as f64)))
+        .collect();
+    format!("linear({})", points.join(", "))
+}
+
+/// Formats a number with up to four decimals and without trailing zeros
+pub(crate) fn format_number(value: f64) -> String {
+    let formatted = format!("{value:.4}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" => "0".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+/// An entrance animation of a single CSS property that plays from the first paint of a
+/// server-rendered page and continues seamlessly once the page has been hydrated.
+///
+/// During server-side rendering the element gets the class of a [`CssAnimation`] and an inline
+/// style with the value the animation starts from, so the browser plays the CSS animation while
+/// the wasm is still loading. When the component is hydrated the entrance measures how far the CSS
+/// animation has come, removes the class and continues the animation from exactly that point
+/// without restarting or jumping. Without server-side rendering the animation simply starts when
+/// the component is mounted.
+///
+/// The animated value is available by dereferencing to a signal, and
+/// [`inline_style()`](Entrance::inline_style) renders it for the `style` attribute.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::css::Entrance;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let fade_in = Entrance::new(
+///     "hero-fade-in",
+///     "opacity",
+///     0.0,
+///     (1.0, Duration::from_millis(600)),
+///     tween_default,
+///     |opacity: &f64| opacity.to_string(),
+/// );
+///
+/// // Before hydration the value stays at the start
+/// assert_eq!(fade_in.get_untracked(), 0.0);
+/// # let _html =
+/// view! {
+///     {fade_in.style()}
+///     <h1 class=fade_in.class() style=fade_in.inline_style()>"Welcome"</h1>
+/// }
+/// # .to_html();
+/// ```
+pub struct Entrance<T: 'static, I: 'static> {
+    css: StoredValue<CssAnimation>,
+    class: Signal<String>,
+    property: StoredValue<String>,
+    format: fn(&I) -> String,
+    signal: Signal<I, LocalStorage>,
+    animation: StoredValue<Option<Animation<T, I>>, LocalStorage>,
+}
+
+impl<T, I> Clone for Entrance<T, I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, I> Copy for Entrance<T, I> {}
+
+impl<T: Clone + 'static, I: Clone + 'static> Entrance<T, I> {
+    /// Animates `property` from `from` towards `target` when the page loads. The `name` has to be
+    /// unique within the page, see [`CssAnimation::new()`], and `format` turns values into CSS.
+    pub fn new(
+        name: impl Into<String>,
+        property: impl Into<String>,
+        from: T,
+        target: impl Into<AnimationTarget<T>>,
+        tween: fn(&T, &T, f64) -> I,
+        format: fn(&I) -> String,
+    ) -> Entrance<T, I> {
+        let context: AnimationContext = use_context().expect(
+            "No AnimationContext present, call AnimationContext::provide() in a parent scope",
+        );
+        let property = property.into();
+        let target = target.into();
+        let css = CssAnimation::new(name).property(
+            property.clone(),
+            from.clone(),
+            target.clone(),
+            move |value| format(&tween(value, value, 1.0)),
+        );
+        let from_i = tween(&from, &from, 1.0);
+
+        let animation: StoredValue<Option<Animation<T, I>>, LocalStorage> =
+            StoredValue::new_local(None);
+        let started = Trigger::new();
+        let class = RwSignal::new(css.class_name().to_string());
+        let css = StoredValue::new(css);
+
+        // Effects only run on the client, after hydration
+        Effect::new(move |_| {
+            let elapsed = css.with_value(CssAnimation::elapsed).unwrap_or_default();
+            let now = context.now();
+            let started_at = now.checked_sub(elapsed).unwrap_or(now);
+            animation.set_value(Some(Animation {
+                from: from.clone(),
+                to: target.target.clone(),
+                to_i: tween(&target.target, &target.target, 1.0),
+                start: started_at + target.delay,
+                duration: target.duration,
+                easing: target.easing.clone(),
+                keyframes: target.keyframes.clone(),
+                repeat: target.repeat,
+                from_rest: false,
+                #[cfg(not(feature = "disable-animations"))]
+                on_complete: None,
+            }));
+            // Hand over from the CSS animation to the inline style in the same frame
+            class.set(String::new());
+            started.notify();
+        });
+
+        let signal = Signal::derive_local(move || {
+            started.track();
+            context.animation_frame.track();
+            let now = context.now();
+            animation.with_value(|animation| match animation {
+                Some(animation) => {
+                    if !animation.is_finished(now) {
+                        context.request_animation_frame();
+                        animation.value(&tween, now)
+                    } else {
+                        animation.to_i.clone()
+                    }
+                }
+                None => from_i.clone(),
+            })
+        });
+
+        Entrance {
+            css,
+            class: class.into(),
+            property: StoredValue::new(property),
+            format,
+            signal,
+            animation,
+        }
+    }
+
+    /// A `<style>` tag with the CSS animation, see [`CssAnimation::style()`]
+    pub fn style(&self) -> impl IntoView {
+        self.css.with_value(CssAnimation::style)
+    }
+
+    /// The class of the animated element, which is removed once the wasm engine has taken over
+    pub fn class(&self) -> Signal<String> {
+        self.class
+    }
+
+    /// The animated property with its current value, for the `style` attribute of the element
+    pub fn inline_style(&self) -> Signal<String, LocalStorage> {
+        let this = *self;
+        Signal::derive_local(move || {
+            let value = (this.format)(&this.signal.get());
+            this.property
+                .with_value(|property| format!("{property}: {value}"))
+        })
+    }
+
+    /// Whether the animation has been handed over to the wasm engine
+    pub fn is_hydrated(&self) -> bool {
+        self.animation.with_value(Option::is_some)
+    }
+}
+
+impl<T, I> Deref for Entrance<T, I> {
+    type Target = Signal<I, LocalStorage>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.signal
+    }
+}
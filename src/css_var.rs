@@ -0,0 +1,65 @@
+//! Animated values written straight into CSS custom properties, for stylesheets that derive many
+//! properties from a single animated value.
+
+use std::fmt::Display;
+
+use leptos::html::ElementType;
+use leptos::prelude::*;
+use leptos::wasm_bindgen::JsCast;
+
+use crate::AnimatedSignal;
+
+/// Writes the value of `animated_signal` into the CSS custom property `name` of the element behind
+/// `node_ref` on every frame in which it changes, such as `--progress`. The property is set on the
+/// style of the element directly instead of going through a view, so nothing of the view is
+/// rendered again and the stylesheet can use the value with `var()` in as many properties as it
+/// wants. The value is written with its `Display` implementation, frames that write the same text
+/// leave the element alone.
+///
+/// The property is first written once the element is mounted.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos::html::Div;
+/// # use leptos_animation::*;
+/// # use leptos_animation::css_var::use_animated_css_var;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (uploaded, set_uploaded) = signal(0.0);
+/// let progress = AnimatedSignal::new(move || (uploaded.get(), Duration::from_millis(300)).into(), tween_default::<f64, f64>);
+///
+/// let bar = NodeRef::<Div>::new();
+/// // .bar { width: calc(var(--progress) * 100%); opacity: calc(0.5 + var(--progress) / 2); }
+/// use_animated_css_var(bar, "--progress", progress);
+/// set_uploaded.set(0.4);
+/// # let _html =
+/// view! { <div node_ref=bar class="bar"></div> }
+/// # .to_html();
+/// ```
+pub fn use_animated_css_var<E, T, I>(
+    node_ref: NodeRef<E>,
+    name: impl Into<String>,
+    animated_signal: AnimatedSignal<T, I>,
+) where
+    E: ElementType + 'static,
+    E::Output: JsCast + Clone + 'static,
+    T: 'static,
+    I: Display + 'static,
+{
+    let name = name.into();
+    // The element and the value that was last written into it
+    Effect::new(
+        move |written: Option<Option<(web_sys::HtmlElement, String)>>| {
+            let element: web_sys::HtmlElement = node_ref.get()?.unchecked_into();
+            let value = animated_signal.with(ToString::to_string);
+            let unchanged = written
+                .flatten()
+                .is_some_and(|(written_element, written_value)| {
+                    written_element == element && written_value == value
+                });
+            if !unchanged {
+                let _ = element.style().set_property(&name, &value);
+            }
+            Some((element, value))
+        },
+    );
+}
@@ -0,0 +1,123 @@
+//! Attenuating one animated value while another one is active, like the side-chain ducking of a
+//! mixing desk that turns the music down while someone speaks.
+
+use std::ops::Mul;
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use crate::tokens::{DurationToken, MotionTokens};
+use crate::{easing, tween_default, AnimatedSignal, AnimationMode, AnimationTarget, Easing};
+
+/// How much and how quickly a ducked value is attenuated, see [`AnimatedSignal::ducked_by()`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ducking {
+    /// The fraction by which the value is attenuated while ducked, from 0.0 for not at all to 1.0
+    /// for silencing it completely. Defaults to 0.6
+    pub depth: f64,
+
+    /// The time in which the value is attenuated once the side chain becomes active. Defaults to
+    /// [`DurationToken::Fast`] of the [`MotionTokens`] of the current scope
+    pub attack: Duration,
+
+    /// The easing of the attenuation. Defaults to [`QUAD_OUT`](easing::QUAD_OUT)
+    pub attack_easing: Easing,
+
+    /// The time in which the value eases back once the side chain is no longer active. Defaults
+    /// to [`DurationToken::Slow`] of the [`MotionTokens`] of the current scope
+    pub release: Duration,
+
+    /// The easing of the release. Defaults to [`CUBIC_IN_OUT`](easing::CUBIC_IN_OUT)
+    pub release_easing: Easing,
+}
+
+impl Default for Ducking {
+    fn default() -> Self {
+        let tokens = MotionTokens::current();
+        Ducking {
+            depth: 0.6,
+            attack: tokens.duration(DurationToken::Fast),
+            attack_easing: easing::QUAD_OUT,
+            release: tokens.duration(DurationToken::Slow),
+            release_easing: easing::CUBIC_IN_OUT,
+        }
+    }
+}
+
+impl Ducking {
+    /// Attenuates by `depth` with the default timing
+    pub fn new(depth: f64) -> Ducking {
+        Ducking {
+            depth,
+            ..Ducking::default()
+        }
+    }
+
+    /// The gain while the side chain is active
+    fn ducked_gain(&self) -> f64 {
+        1.0 - self.depth.clamp(0.0, 1.0)
+    }
+}
+
+impl<T, I: Clone + Mul<f64, Output = I> + 'static> AnimatedSignal<T, I> {
+    /// The value of this signal, attenuated while `side_chain` is animating. The value is scaled
+    /// by a gain that eases down to `1.0 - depth` when the side chain starts animating and eases
+    /// back to 1.0 once it has settled, so the attenuation never jumps even when the side chain
+    /// starts and stops in quick succession.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # use leptos_animation::ducking::Ducking;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (open, set_open) = signal(false);
+    /// let modal: AnimatedSignal<f64, f64> = AnimatedSignal::new(move || if open.get() { 1.0 } else { 0.0 }.into(), tween_default);
+    ///
+    /// // A decorative background wobble that calms down while the modal opens or closes
+    /// let (wobble, set_wobble) = signal(10.0);
+    /// let background: AnimatedSignal<f64, f64> = AnimatedSignal::new(move || wobble.get().into(), tween_default);
+    /// let calm_background = background.ducked_by(&modal, Ducking::new(0.8));
+    /// assert_eq!(calm_background.get_untracked(), 10.0);
+    /// ```
+    pub fn ducked_by<T2, I2>(
+        &self,
+        side_chain: &AnimatedSignal<T2, I2>,
+        ducking: Ducking,
+    ) -> Signal<I, LocalStorage> {
+        let active = side_chain.is_animating();
+        self.ducked_while(move || active.get(), ducking)
+    }
+
+    /// The value of this signal, attenuated while `active` returns `true`, see
+    /// [`ducked_by()`](AnimatedSignal::ducked_by). `active` is tracked, so it can read any signal,
+    /// such as whether a sound is playing or a modal is open.
+    pub fn ducked_while(
+        &self,
+        active: impl Fn() -> bool + 'static,
+        ducking: Ducking,
+    ) -> Signal<I, LocalStorage> {
+        let gain: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+            move || -> AnimationTarget<f64> {
+                match active() {
+                    true => (
+                        ducking.ducked_gain(),
+                        ducking.attack,
+                        ducking.attack_easing.clone(),
+                        AnimationMode::ReplaceWithVelocity,
+                    )
+                        .into(),
+                    false => (
+                        1.0,
+                        ducking.release,
+                        ducking.release_easing.clone(),
+                        AnimationMode::ReplaceWithVelocity,
+                    )
+                        .into(),
+                }
+            },
+            tween_default,
+        );
+        let signal = self.animated_signal;
+        Signal::derive_local(move || signal.get() * gain.get())
+    }
+}
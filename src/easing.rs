@@ -1,5 +1,5 @@
-use ::std::f64::consts::PI;
 use crate::Easing;
+use ::std::f64::consts::{LN_2, PI};
 
 const C1: f64 = 1.70158;
 const C2: f64 = C1 * 1.525;
@@ -8,234 +8,666 @@ const C4: f64 = (2.0 * PI) / 3.0;
 const C5: f64 = (2.0 * PI) / 4.5;
 
 /// <https://easings.net/#easeInBack>
-pub const BACK_IN: Easing = |t: f64| -> f64 {
-    C3 * t * t * t - C1 * t * t
-};
+pub const BACK_IN: Easing = Easing::from_fns(
+    |t: f64| -> f64 { C3 * t * t * t - C1 * t * t },
+    |t: f64| -> f64 { 3.0 * C3 * t * t - 2.0 * C1 * t },
+);
 
 /// <https://easings.net/#easeOutBack>
-pub const BACK_OUT: Easing = |t: f64| -> f64 {
-    1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
-};
+pub const BACK_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 { 1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2) },
+    |t: f64| -> f64 { 3.0 * C3 * (t - 1.0).powi(2) + 2.0 * C1 * (t - 1.0) },
+);
 
 /// <https://easings.net/#easeInOutBack>
-pub const BACK_IN_OUT: Easing = |t: f64| -> f64 {
-    if t < 0.5 {
-        ((2.0 * t).powi(2) * ((C2 + 1.0) * 2.0 * t - C2)) / 2.0
-    } else {
-        ((2.0 * t - 2.0).powi(2) * ((C2 + 1.0) * (t * 2.0 - 2.0) + C2) + 2.0) / 2.0
-    }
-};
+pub const BACK_IN_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            ((2.0 * t).powi(2) * ((C2 + 1.0) * 2.0 * t - C2)) / 2.0
+        } else {
+            ((2.0 * t - 2.0).powi(2) * ((C2 + 1.0) * (t * 2.0 - 2.0) + C2) + 2.0) / 2.0
+        }
+    },
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            12.0 * (C2 + 1.0) * t * t - 4.0 * C2 * t
+        } else {
+            3.0 * (C2 + 1.0) * (2.0 * t - 2.0).powi(2) + 2.0 * C2 * (2.0 * t - 2.0)
+        }
+    },
+);
 
 /// <https://easings.net/#easeInBounce>
-pub const BOUNCE_IN: Easing = |t: f64| -> f64 {
-    1.0 - BOUNCE_OUT(1.0 - t)
-};
+pub const BOUNCE_IN: Easing = Easing::from_fns(
+    |t: f64| -> f64 { 1.0 - BOUNCE_OUT.ease(1.0 - t) },
+    |t: f64| -> f64 { bounce_out_derivative(1.0 - t) },
+);
 
 /// <https://easings.net/#easeOutBounce>
-pub const BOUNCE_OUT: Easing = |t: f64| -> f64 {
+pub const BOUNCE_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        const N1: f64 = 7.5625;
+        const D1: f64 = 2.75;
+        if t < 1.0 / D1 {
+            N1 * t * t
+        } else if t < 2.0 / D1 {
+            N1 * (t - 1.5 / D1).powi(2) + 0.75
+        } else if t < 2.5 / D1 {
+            N1 * (t - 2.25 / D1).powi(2) + 0.9375
+        } else {
+            N1 * (t - 2.625 / D1).powi(2) + 0.984375
+        }
+    },
+    |t: f64| -> f64 { bounce_out_derivative(t) },
+);
+
+fn bounce_out_derivative(t: f64) -> f64 {
     const N1: f64 = 7.5625;
     const D1: f64 = 2.75;
     if t < 1.0 / D1 {
-        return N1 * t * t;
+        2.0 * N1 * t
     } else if t < 2.0 / D1 {
-        return N1 * (t - 1.5 / D1).powi(2) + 0.75;
+        2.0 * N1 * (t - 1.5 / D1)
     } else if t < 2.5 / D1 {
-        return N1 * (t - 2.25 / D1).powi(2) + 0.9375;
+        2.0 * N1 * (t - 2.25 / D1)
     } else {
-        return N1 * (t - 2.625 / D1).powi(2) + 0.984375;
+        2.0 * N1 * (t - 2.625 / D1)
     }
-};
+}
 
 /// <https://easings.net/#easeInOutBounce>
-pub const BOUNCE_IN_OUT: Easing = |t: f64| -> f64 {
-    if t < 0.5 {
-        (1.0 - BOUNCE_OUT(1.0 - 2.0 * t)) / 2.0
-    } else {
-        (1.0 + BOUNCE_OUT(2.0 * t - 1.0)) / 2.0
-    }
-};
+pub const BOUNCE_IN_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            (1.0 - BOUNCE_OUT.ease(1.0 - 2.0 * t)) / 2.0
+        } else {
+            (1.0 + BOUNCE_OUT.ease(2.0 * t - 1.0)) / 2.0
+        }
+    },
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            bounce_out_derivative(1.0 - 2.0 * t)
+        } else {
+            bounce_out_derivative(2.0 * t - 1.0)
+        }
+    },
+);
 
 /// <https://easings.net/#easeInCirc>
-pub const CIRC_IN: Easing = |t: f64| -> f64 {
-    1.0 - (1.0 - t.powi(2)).sqrt()
-};
+pub const CIRC_IN: Easing = Easing::from_fns(
+    |t: f64| -> f64 { 1.0 - (1.0 - t.powi(2)).sqrt() },
+    |t: f64| -> f64 { t / (1.0 - t.powi(2)).sqrt() },
+);
 
 /// <https://easings.net/#easeOutCirc>
-pub const CIRC_OUT: Easing = |t: f64| -> f64 {
-    (1.0 - (t - 1.0).powi(2)).sqrt()
-};
+pub const CIRC_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 { (1.0 - (t - 1.0).powi(2)).sqrt() },
+    |t: f64| -> f64 { (1.0 - t) / (1.0 - (t - 1.0).powi(2)).sqrt() },
+);
 
 /// <https://easings.net/#easeInOutCirc>
-pub const CIRC_IN_OUT: Easing = |t: f64| -> f64 {
-    if t < 0.5 {
-        (1.0 - (1.0 - (2.0 * t).powi(2)).sqrt()) / 2.0
-    } else {
-        ((1.0 - (-2.0 * t + 2.0).powi(2)).sqrt() + 1.0) / 2.0
-    }
-};
+pub const CIRC_IN_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            (1.0 - (1.0 - (2.0 * t).powi(2)).sqrt()) / 2.0
+        } else {
+            ((1.0 - (-2.0 * t + 2.0).powi(2)).sqrt() + 1.0) / 2.0
+        }
+    },
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            2.0 * t / (1.0 - (2.0 * t).powi(2)).sqrt()
+        } else {
+            (2.0 - 2.0 * t) / (1.0 - (2.0 - 2.0 * t).powi(2)).sqrt()
+        }
+    },
+);
 
 /// <https://easings.net/#easeInCubic>
-pub const CUBIC_IN: Easing = |t: f64| -> f64 {
-    t * t * t
-};
+pub const CUBIC_IN: Easing = Easing::from_fns(
+    |t: f64| -> f64 { t * t * t },
+    |t: f64| -> f64 { 3.0 * t * t },
+);
 
 /// <https://easings.net/#easeOutCubic>
-pub const CUBIC_OUT: Easing = |t: f64| -> f64 {
-    1.0 - (1.0 - t).powi(3)
-};
+pub const CUBIC_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 { 1.0 - (1.0 - t).powi(3) },
+    |t: f64| -> f64 { 3.0 * (1.0 - t).powi(2) },
+);
 
 /// <https://easings.net/#easeInOutCubic>
-pub const CUBIC_IN_OUT: Easing = |t: f64| -> f64 {
-    if t < 0.5 {
-        4.0 * t * t * t
-    } else {
-        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
-    }
-};
+pub const CUBIC_IN_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            4.0 * t * t * t
+        } else {
+            1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+        }
+    },
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            12.0 * t * t
+        } else {
+            3.0 * (-2.0 * t + 2.0).powi(2)
+        }
+    },
+);
 
 /// <https://easings.net/#easeInElastic>
-pub const ELASTIC_IN: Easing = |t: f64| -> f64 {
-    if t <= 0.0 {
-        0.0
-    } else if 1.0 <= t {
-        1.0
-    } else {
-        -2f64.powf(10.0 * t - 10.0) * ((t * 10.0 - 10.75) * C4).sin()
-    }
-};
+pub const ELASTIC_IN: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if t <= 0.0 {
+            0.0
+        } else if 1.0 <= t {
+            1.0
+        } else {
+            -2f64.powf(10.0 * t - 10.0) * ((t * 10.0 - 10.75) * C4).sin()
+        }
+    },
+    |t: f64| -> f64 {
+        if !(0.0..=1.0).contains(&t) {
+            0.0
+        } else {
+            let angle = (t * 10.0 - 10.75) * C4;
+            -2f64.powf(10.0 * t - 10.0) * (10.0 * LN_2 * angle.sin() + 10.0 * C4 * angle.cos())
+        }
+    },
+);
 
 /// <https://easings.net/#easeOutElastic>
-pub const ELASTIC_OUT: Easing = |t: f64| -> f64 {
-    if t <= 0.0 {
-        0.0
-    } else if 1.0 <= t {
-        1.0
-    } else {
-        2f64.powf(-10.0 * t) * ((t * 10.0 - 0.75) * C4).sin() + 1.0
-    }
-};
+pub const ELASTIC_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if t <= 0.0 {
+            0.0
+        } else if 1.0 <= t {
+            1.0
+        } else {
+            2f64.powf(-10.0 * t) * ((t * 10.0 - 0.75) * C4).sin() + 1.0
+        }
+    },
+    |t: f64| -> f64 {
+        if !(0.0..=1.0).contains(&t) {
+            0.0
+        } else {
+            let angle = (t * 10.0 - 0.75) * C4;
+            2f64.powf(-10.0 * t) * (10.0 * C4 * angle.cos() - 10.0 * LN_2 * angle.sin())
+        }
+    },
+);
 
 /// <https://easings.net/#easeInOutElastic>
-pub const ELASTIC_IN_OUT: Easing = |t: f64| -> f64 {
-    if t <= 0.0 {
-        0.0
-    } else if 1.0 <= t {
-        1.0
-    } else if t < 0.5 {
-        -(2f64.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * C5).sin()) / 2.0
-    } else {
-        (2f64.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * C5).sin()) / 2.0 + 1.0
-    }
-};
+pub const ELASTIC_IN_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if t <= 0.0 {
+            0.0
+        } else if 1.0 <= t {
+            1.0
+        } else if t < 0.5 {
+            -(2f64.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * C5).sin()) / 2.0
+        } else {
+            (2f64.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * C5).sin()) / 2.0 + 1.0
+        }
+    },
+    |t: f64| -> f64 {
+        let angle = (20.0 * t - 11.125) * C5;
+        if !(0.0..=1.0).contains(&t) {
+            0.0
+        } else if t < 0.5 {
+            -2f64.powf(20.0 * t - 10.0) * (20.0 * LN_2 * angle.sin() + 20.0 * C5 * angle.cos())
+                / 2.0
+        } else {
+            2f64.powf(-20.0 * t + 10.0) * (20.0 * C5 * angle.cos() - 20.0 * LN_2 * angle.sin())
+                / 2.0
+        }
+    },
+);
 
 /// <https://easings.net/#easeInExpo>
-pub const EXPO_IN: Easing = |t: f64| -> f64 {
-    if t <= 0.0 {
-        0.0
-    } else {
-        2f64.powf(10.0 * t - 10.0)
-    }
-};
+pub const EXPO_IN: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if t <= 0.0 {
+            0.0
+        } else {
+            2f64.powf(10.0 * t - 10.0)
+        }
+    },
+    |t: f64| -> f64 {
+        if t < 0.0 {
+            0.0
+        } else {
+            10.0 * LN_2 * 2f64.powf(10.0 * t - 10.0)
+        }
+    },
+);
 
 /// <https://easings.net/#easeOutExpo>
-pub const EXPO_OUT: Easing = |t: f64| -> f64 {
-    if 1.0 <= t {
-        1.0
-    } else {
-        1.0 - 2f64.powf(-10.0 * t)
-    }
-};
+pub const EXPO_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if 1.0 <= t {
+            1.0
+        } else {
+            1.0 - 2f64.powf(-10.0 * t)
+        }
+    },
+    |t: f64| -> f64 {
+        if 1.0 < t {
+            0.0
+        } else {
+            10.0 * LN_2 * 2f64.powf(-10.0 * t)
+        }
+    },
+);
 
 /// <https://easings.net/#easeInOutExpo>
-pub const EXPO_IN_OUT: Easing = |t: f64| -> f64 {
-    if t <= 0.0 {
-        0.0
-    } else if 1.0 <= t {
-        1.0
-    } else if t < 0.5 {
-        2f64.powf(20.0 * t - 10.0) / 2.0
-    } else {
-        (2.0 - 2f64.powf(-20.0 * t + 10.0)) / 2.0
-    }
-};
-
-pub const LINEAR: Easing = |t: f64| -> f64 {
-    t
-};
+pub const EXPO_IN_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if t <= 0.0 {
+            0.0
+        } else if 1.0 <= t {
+            1.0
+        } else if t < 0.5 {
+            2f64.powf(20.0 * t - 10.0) / 2.0
+        } else {
+            (2.0 - 2f64.powf(-20.0 * t + 10.0)) / 2.0
+        }
+    },
+    |t: f64| -> f64 {
+        if !(0.0..=1.0).contains(&t) {
+            0.0
+        } else if t < 0.5 {
+            10.0 * LN_2 * 2f64.powf(20.0 * t - 10.0)
+        } else {
+            10.0 * LN_2 * 2f64.powf(-20.0 * t + 10.0)
+        }
+    },
+);
+
+pub const LINEAR: Easing = Easing::from_fns(|t: f64| -> f64 { t }, |_: f64| -> f64 { 1.0 });
 
 /// A linear easing that goes from `1.0` to `0.0`.
-pub const REVERSE: Easing = |t: f64| -> f64 {
-    1.0 - t
-};
+pub const REVERSE: Easing = Easing::from_fns(|t: f64| -> f64 { 1.0 - t }, |_: f64| -> f64 { -1.0 });
 
 /// <https://easings.net/#easeInQuad>
-pub const QUAD_IN: Easing = |t: f64| -> f64 {
-    t * t
-};
+pub const QUAD_IN: Easing =
+    Easing::from_fns(|t: f64| -> f64 { t * t }, |t: f64| -> f64 { 2.0 * t });
 
 /// <https://easings.net/#easeOutQuad>
-pub const QUAD_OUT: Easing = |t: f64| -> f64 {
-    1.0 - (1.0 - t).powi(2)
-};
+pub const QUAD_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 { 1.0 - (1.0 - t).powi(2) },
+    |t: f64| -> f64 { 2.0 * (1.0 - t) },
+);
 
 /// <https://easings.net/#easeInOutQuad>
-pub const QUAD_IN_OUT: Easing = |t: f64| -> f64 {
-    if t < 0.5 {
-        2.0 * t * t
-    } else {
-        1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
-    }
-};
+pub const QUAD_IN_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            2.0 * t * t
+        } else {
+            1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+        }
+    },
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            4.0 * t
+        } else {
+            2.0 * (-2.0 * t + 2.0)
+        }
+    },
+);
 
 /// <https://easings.net/#easeInQuart>
-pub const QUART_IN: Easing = |t: f64| -> f64 {
-    t * t * t * t
-};
+pub const QUART_IN: Easing = Easing::from_fns(
+    |t: f64| -> f64 { t * t * t * t },
+    |t: f64| -> f64 { 4.0 * t * t * t },
+);
 
 /// <https://easings.net/#easeOutQuart>
-pub const QUART_OUT: Easing = |t: f64| -> f64 {
-    1.0 - (1.0 - t).powi(4)
-};
+pub const QUART_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 { 1.0 - (1.0 - t).powi(4) },
+    |t: f64| -> f64 { 4.0 * (1.0 - t).powi(3) },
+);
 
 /// <https://easings.net/#easeInOutQuart>
-pub const QUART_IN_OUT: Easing = |t: f64| -> f64 {
-    if t < 0.5 {
-        8.0 * t * t * t * t
-    } else {
-        1.0 - (-2.0 * t + 2.0).powi(4) / 2.0
-    }
-};
+pub const QUART_IN_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            8.0 * t * t * t * t
+        } else {
+            1.0 - (-2.0 * t + 2.0).powi(4) / 2.0
+        }
+    },
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            32.0 * t * t * t
+        } else {
+            4.0 * (-2.0 * t + 2.0).powi(3)
+        }
+    },
+);
 
 /// <https://easings.net/#easeInQuint>
-pub const QUINT_IN: Easing = |t: f64| -> f64 {
-    t * t * t * t * t
-};
+pub const QUINT_IN: Easing = Easing::from_fns(
+    |t: f64| -> f64 { t * t * t * t * t },
+    |t: f64| -> f64 { 5.0 * t * t * t * t },
+);
 
 /// <https://easings.net/#easeOutQuint>
-pub const QUINT_OUT: Easing = |t: f64| -> f64 {
-    1.0 - (1.0 - t).powi(5)
-};
+pub const QUINT_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 { 1.0 - (1.0 - t).powi(5) },
+    |t: f64| -> f64 { 5.0 * (1.0 - t).powi(4) },
+);
 
 /// <https://easings.net/#easeInOutQuint>
-pub const QUINT_IN_OUT: Easing = |t: f64| -> f64 {
-    if t < 0.5 {
-        16.0 * t * t * t * t * t
-    } else {
-        1.0 - (-2.0 * t + 2.0).powi(5) / 2.0
-    }
-};
+pub const QUINT_IN_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            16.0 * t * t * t * t * t
+        } else {
+            1.0 - (-2.0 * t + 2.0).powi(5) / 2.0
+        }
+    },
+    |t: f64| -> f64 {
+        if t < 0.5 {
+            80.0 * t * t * t * t
+        } else {
+            5.0 * (-2.0 * t + 2.0).powi(4)
+        }
+    },
+);
 
 /// <https://easings.net/#easeInSine>
-pub const SINE_IN: Easing = |t: f64| -> f64 {
-    1.0 - (t * PI / 2.0).cos()
-};
+pub const SINE_IN: Easing = Easing::from_fns(
+    |t: f64| -> f64 { 1.0 - (t * PI / 2.0).cos() },
+    |t: f64| -> f64 { PI / 2.0 * (t * PI / 2.0).sin() },
+);
 
 /// <https://easings.net/#easeOutSine>
-pub const SINE_OUT: Easing = |t: f64| -> f64 {
-    (t * PI / 2.0).sin()
-};
+pub const SINE_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 { (t * PI / 2.0).sin() },
+    |t: f64| -> f64 { PI / 2.0 * (t * PI / 2.0).cos() },
+);
 
 /// <https://easings.net/#easeInOutSine>
-pub const SINE_IN_OUT: Easing = |t: f64| -> f64 {
-    -((PI * t).cos() - 1.0) / 2.0
-};
+pub const SINE_IN_OUT: Easing = Easing::from_fns(
+    |t: f64| -> f64 { -((PI * t).cos() - 1.0) / 2.0 },
+    |t: f64| -> f64 { PI / 2.0 * (PI * t).sin() },
+);
+
+/// Numerically inverts a monotonic easing with a binary search: for a given eased value it returns
+/// the linear progress at which the easing reaches that value. This can be used to convert a desired
+/// value back into a position on the timeline, for example for scrubbing or to synchronize two
+/// animations that use different easings.
+///
+/// Values outside of the range of the easing are clamped to `0.0` or `1.0`.
+/// The result is unspecified for easings that are not monotonic, such as [`ELASTIC_OUT`] or [`BACK_IN`].
+/// ```
+/// # use leptos_animation::easing;
+/// let inverse = easing::invert(easing::QUAD_IN);
+/// assert!((inverse(0.25) - 0.5).abs() < 1e-9);
+/// ```
+pub fn invert(easing: Easing) -> impl Fn(f64) -> f64 + Clone {
+    move |value: f64| -> f64 {
+        let increasing = easing.ease(1.0) >= easing.ease(0.0);
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..64 {
+            let middle = (low + high) / 2.0;
+            if (easing.ease(middle) < value) == increasing {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        (low + high) / 2.0
+    }
+}
+
+/// Wraps an easing in a lookup table of `resolution` samples that are linearly interpolated, for very
+/// hot paths such as hundreds of simultaneous animations with [`ELASTIC_IN_OUT`].
+/// Looking up a value costs the same for every easing, trading a little memory and accuracy for speed.
+/// The table is computed once when calling this function and shared between all clones of the result.
+/// The derivative of the result is the slope of the table, so animations that take over the velocity
+/// of an interrupted one don't evaluate the wrapped easing either.
+///
+/// This only pays off for easings built on trigonometric or exponential functions such as the
+/// elastic and sine easings. Polynomial easings like [`BOUNCE_OUT`] or [`BACK_IN`] are faster to
+/// evaluate directly, see the `easing_lut` benchmark.
+///
+/// The error is largest for easings with sharp corners such as the bounces of [`BOUNCE_IN_OUT`].
+/// A resolution of 256 keeps it below 0.01 for all easings of this module, and below 0.001 for
+/// easings without corners, except where the circular easings such as [`CIRC_IN`] turn vertical:
+/// there the table is off by up to a quarter of the rise of the last segment, about 0.02.
+/// ```
+/// # use leptos_animation::easing;
+/// let elastic = easing::lut(easing::ELASTIC_OUT, 256);
+/// assert!((elastic.ease(0.3) - easing::ELASTIC_OUT.ease(0.3)).abs() < 1e-3);
+/// assert_eq!(elastic.ease(1.0), 1.0);
+/// ```
+pub fn lut(easing: Easing, resolution: usize) -> Easing {
+    let resolution = resolution.max(1);
+    let table: std::sync::Arc<[f64]> = (0..=resolution)
+        .map(|sample| easing.ease(sample as f64 / resolution as f64))
+        .collect();
+
+    let scale = resolution as f64;
+    let last = resolution - 1;
+    // The samples around `t` and how far `t` lies between them
+    let segment = move |t: f64| {
+        let position = t.clamp(0.0, 1.0) * scale;
+        let index = (position as usize).min(last);
+        (index, position - index as f64)
+    };
+    let slopes = std::sync::Arc::clone(&table);
+    Easing::new(move |t| {
+        let (index, fraction) = segment(t);
+        let [from, to] = [table[index], table[index + 1]];
+        from + (to - from) * fraction
+    })
+    .with_derivative(move |t| {
+        let (index, _) = segment(t);
+        (slopes[index + 1] - slopes[index]) * scale
+    })
+}
+
+/// Where the jumps of [`steps()`] happen, matching the `<step-position>` of the CSS `steps()` function
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StepPosition {
+    /// The first jump happens right at the start, CSS `jump-start` or `start`
+    JumpStart,
+    /// The last jump happens right at the end, CSS `jump-end` or `end`
+    #[default]
+    JumpEnd,
+    /// Neither at the start nor at the end, so both the start and the end value are held for a
+    /// step, CSS `jump-none`
+    JumpNone,
+    /// Both at the start and at the end, so `n + 1` jumps happen, CSS `jump-both`
+    JumpBoth,
+}
+
+/// An easing that moves in `n` discrete steps instead of continuously, with the semantics of the
+/// CSS `steps()` function. Useful for sprite sheets, ticking clocks and segmented progress bars.
+///
+/// `n` is at least 1, or 2 with [`StepPosition::JumpNone`], smaller values are raised to that.
+/// Like any closure the result converts into an [`Easing`].
+/// ```
+/// # use std::time::Duration;
+/// # use leptos_animation::{AnimationTarget, Easing};
+/// # use leptos_animation::easing::{self, StepPosition};
+/// let four_frames = easing::steps(4, StepPosition::JumpEnd);
+/// assert_eq!(four_frames(0.0), 0.0);
+/// assert_eq!(four_frames(0.3), 0.25);
+/// assert_eq!(four_frames(1.0), 1.0);
+///
+/// let first_frame_right_away = easing::steps(4, StepPosition::JumpStart);
+/// assert_eq!(first_frame_right_away(0.0), 0.25);
+///
+/// // Play a sprite sheet of 12 frames
+/// let sprite: AnimationTarget<f64> = (12.0, Duration::from_millis(600), Easing::from(easing::steps(12, StepPosition::JumpEnd))).into();
+/// ```
+pub fn steps(n: u32, position: StepPosition) -> impl Fn(f64) -> f64 + Copy + Send + Sync {
+    let steps = match position {
+        StepPosition::JumpNone => n.max(2),
+        _ => n.max(1),
+    } as f64;
+    let jumps = match position {
+        StepPosition::JumpStart | StepPosition::JumpEnd => steps,
+        StepPosition::JumpNone => steps - 1.0,
+        StepPosition::JumpBoth => steps + 1.0,
+    };
+    move |t: f64| -> f64 {
+        let mut step = (t * steps).floor();
+        if matches!(position, StepPosition::JumpStart | StepPosition::JumpBoth) {
+            step += 1.0;
+        }
+        if t >= 0.0 && step < 0.0 {
+            step = 0.0;
+        }
+        if t <= 1.0 && step > jumps {
+            step = jumps;
+        }
+        step / jumps
+    }
+}
+
+/// An easing that linearly interpolates a lookup table of eased values, for curves exported from
+/// motion design tools such as the keyframe velocity graphs of After Effects.
+///
+/// The samples are spread evenly over the duration, the first one at the start and the last one at
+/// the end. They are used as they are, so curves that overshoot keep doing so. Without samples the
+/// easing is linear, a single sample holds its value. The derivative is the slope of the segment the
+/// progress is in.
+/// ```
+/// # use leptos_animation::easing;
+/// let exported = easing::from_samples(&[0.0, 0.1, 0.4, 0.8, 1.0]);
+/// assert_eq!(exported.ease(0.5), 0.4);
+/// assert!((exported.ease(0.625) - 0.6).abs() < 1e-9);
+/// assert!((exported.derivative(0.625) - 1.6).abs() < 1e-9);
+/// ```
+pub fn from_samples(samples: &[f64]) -> Easing {
+    match samples {
+        [] => return LINEAR,
+        [value] => {
+            let value = *value;
+            return Easing::new(move |_| value).with_derivative(|_| 0.0);
+        }
+        _ => {}
+    }
+    let table: std::sync::Arc<[f64]> = samples.into();
+    let scale = (table.len() - 1) as f64;
+    let last = table.len() - 2;
+    let segment = move |t: f64| -> (usize, f64) {
+        let position = t.clamp(0.0, 1.0) * scale;
+        let index = (position as usize).min(last);
+        (index, position - index as f64)
+    };
+
+    let values = table.clone();
+    let slopes = table;
+    Easing::new(move |t: f64| -> f64 {
+        let (index, fraction) = segment(t);
+        let [from, to] = [values[index], values[index + 1]];
+        from + (to - from) * fraction
+    })
+    .with_derivative(move |t: f64| -> f64 {
+        let (index, _) = segment(t);
+        (slopes[index + 1] - slopes[index]) * scale
+    })
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EASINGS: [Easing; 31] = [
+        BACK_IN,
+        BACK_OUT,
+        BACK_IN_OUT,
+        BOUNCE_IN,
+        BOUNCE_OUT,
+        BOUNCE_IN_OUT,
+        CIRC_IN,
+        CIRC_OUT,
+        CIRC_IN_OUT,
+        CUBIC_IN,
+        CUBIC_OUT,
+        CUBIC_IN_OUT,
+        ELASTIC_IN,
+        ELASTIC_OUT,
+        ELASTIC_IN_OUT,
+        EXPO_IN,
+        EXPO_OUT,
+        EXPO_IN_OUT,
+        LINEAR,
+        QUAD_IN,
+        QUAD_OUT,
+        QUAD_IN_OUT,
+        QUART_IN,
+        QUART_OUT,
+        QUART_IN_OUT,
+        QUINT_IN,
+        QUINT_OUT,
+        QUINT_IN_OUT,
+        SINE_IN,
+        SINE_OUT,
+        SINE_IN_OUT,
+    ];
+
+    #[test]
+    fn easings_start_at_zero_and_end_at_one() {
+        for (index, easing) in EASINGS.iter().enumerate() {
+            assert!(
+                easing.ease(0.0).abs() < 1e-9,
+                "easing {index} starts at {}",
+                easing.ease(0.0)
+            );
+            assert!(
+                (easing.ease(1.0) - 1.0).abs() < 1e-9,
+                "easing {index} ends at {}",
+                easing.ease(1.0)
+            );
+        }
+        assert_eq!(REVERSE.ease(0.0), 1.0);
+        assert_eq!(REVERSE.ease(1.0), 0.0);
+    }
+
+    proptest! {
+        #[test]
+        fn lookup_tables_follow_their_easing(index in 0..EASINGS.len(), t in 0.0..=1.0f64) {
+            let easing = EASINGS[index].clone();
+            let table = lut(easing.clone(), 256);
+            // The vertical ends of the circular easings rise too steeply for any table, so the
+            // error may grow with the rise of the segment
+            let tolerance = 0.01 + table.derivative(t).abs() / 256.0;
+            prop_assert!((table.ease(t) - easing.ease(t)).abs() < tolerance);
+        }
+
+        #[test]
+        fn lookup_tables_follow_the_derivative_of_smooth_easings(t in 0.0..=1.0f64) {
+            let table = lut(SINE_IN_OUT, 256);
+            prop_assert!((table.derivative(t) - SINE_IN_OUT.derivative(t)).abs() < 0.01);
+        }
+
+        #[test]
+        fn steps_stay_within_zero_and_one(n in 0..100u32, t in 0.0..=1.0f64) {
+            for position in [
+                StepPosition::JumpStart,
+                StepPosition::JumpEnd,
+                StepPosition::JumpNone,
+                StepPosition::JumpBoth,
+            ] {
+                let value = steps(n, position)(t);
+                prop_assert!((0.0..=1.0).contains(&value), "{position:?} is at {value}");
+            }
+        }
+
+        #[test]
+        fn inverted_easings_undo_their_easing(index in 0..EASINGS.len(), t in 0.0..=1.0f64) {
+            let easing = EASINGS[index].clone();
+            // Only monotonic easings can be inverted, unlike the back, bounce and elastic easings
+            prop_assume!(!matches!(index, 0..=5 | 12..=14));
+            let t_again = invert(easing.clone())(easing.ease(t));
+            prop_assert!((easing.ease(t_again) - easing.ease(t)).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Tweens that can fail, such as morphing between two SVG paths with incompatible commands.
+
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::ops::Sub;
+use std::rc::Rc;
+
+use leptos::logging::error;
+
+use crate::animation_target::AnimationSource;
+use crate::AnimatedSignal;
+
+/// What an animated signal shows when its tween returns an error
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TweenFallback {
+    /// Show the target value, as if the animation had finished
+    #[default]
+    SnapToTarget,
+
+    /// Keep showing the last value the tween produced successfully, or the target value if there is none
+    KeepPrevious,
+
+    /// Log the error to the console and show the target value
+    Report,
+}
+
+impl<T: Clone + 'static, I: Clone + Sub<I, Output = I> + 'static> AnimatedSignal<T, I> {
+    /// Like [`AnimatedSignal::new()`], but with a tween that returns an error for values it can't
+    /// interpolate between, instead of panicking or producing nonsense. The `fallback` decides what
+    /// is shown instead of the failed interpolation.
+    ///
+    /// To show the target value the tween is called with `progress` 1.0 and `from` and `to` both set
+    /// to the target, which is expected to always succeed. If it fails the signal panics.
+    ///
+    /// Note that with several overlapping animations the tween is called for each one of them,
+    /// so `KeepPrevious` keeps the last value produced by any of them.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::AnimatedSignal;
+    /// # use leptos_animation::fallible::TweenFallback;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (value, set_value) = signal(1.0);
+    ///
+    /// // Square roots of negative values can't be shown
+    /// let root = AnimatedSignal::new_fallible(
+    ///     move || value.get().into(),
+    ///     |from: &f64, to: &f64, progress| {
+    ///         let value = (to - from) * progress + from;
+    ///         if value < 0.0 {
+    ///             Err("negative value")
+    ///         } else {
+    ///             Ok(value.sqrt())
+    ///         }
+    ///     },
+    ///     TweenFallback::SnapToTarget,
+    /// );
+    ///
+    /// assert_eq!(root.get_untracked(), 1.0);
+    /// ```
+    pub fn new_fallible<Marker, E: Debug + 'static>(
+        source: impl AnimationSource<T, Marker>,
+        tween: fn(&T, &T, f64) -> Result<I, E>,
+        fallback: TweenFallback,
+    ) -> AnimatedSignal<T, I> {
+        let previous: Rc<RefCell<Option<I>>> = Rc::default();
+
+        let snap = move |to: &T| {
+            tween(to, to, 1.0).unwrap_or_else(|error| {
+                panic!("Tween failed for the target value itself: {error:?}")
+            })
+        };
+
+        Self::new_with_tween(
+            source.into_signal(),
+            Rc::new(move |from, to, progress| match tween(from, to, progress) {
+                Ok(value) => {
+                    if fallback == TweenFallback::KeepPrevious {
+                        *previous.borrow_mut() = Some(value.clone());
+                    }
+                    value
+                }
+                Err(tween_error) => match fallback {
+                    TweenFallback::SnapToTarget => snap(to),
+                    TweenFallback::KeepPrevious => {
+                        let previous = previous.borrow().clone();
+                        previous.unwrap_or_else(|| snap(to))
+                    }
+                    TweenFallback::Report => {
+                        error!("Animation tween failed: {tween_error:?}");
+                        snap(to)
+                    }
+                },
+            }),
+        )
+    }
+}
@@ -0,0 +1,181 @@
+//! Composite values whose fields are animated independently, each with its own target.
+
+use std::ops::{Add, Mul, Sub};
+
+use leptos::prelude::*;
+
+/// Derives a struct with an [`AnimationTarget`] for every field of a struct, named after it with
+/// `Targets` appended, that implements [`FieldTargets`] with the struct as its value. The fields
+/// of the targets have the names and the visibility of the fields of the struct, and the fields
+/// have to be [`Field`]s. Available with the `derive` feature.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::fields::{animate_fields, FieldTargets};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// #[derive(FieldTargets, Clone, Copy, Debug, PartialEq)]
+/// struct Position {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// let (pointer, set_pointer) = signal((0.0f64, 0.0f64));
+/// let position = animate_fields(move || {
+///     let (x, y) = pointer.get();
+///     PositionTargets {
+///         x: AnimationTarget::from((x, AnimationMode::ReplaceOrStart)),
+///         y: AnimationTarget::from(((y / 40.0).round() * 40.0, AnimationMode::Snap)),
+///     }
+/// });
+///
+/// assert_eq!(position.get_untracked(), Position { x: 0.0, y: 0.0 });
+/// ```
+#[cfg(feature = "derive")]
+pub use leptos_animation_derive::FieldTargets;
+
+/// What `#[derive(FieldTargets)]` expands to, so crates that use it don't need to name leptos
+#[doc(hidden)]
+pub mod __private {
+    pub use leptos::prelude::{Get, LocalStorage, Signal};
+}
+
+use crate::animation_target::OnComplete;
+use crate::{tween_default, AnimatedSignal, AnimationTarget};
+
+/// A field of a composite value that is animated with [`tween_default`]
+pub trait Field:
+    Copy
+    + PartialEq
+    + Send
+    + Sync
+    + Add<Self, Output = Self>
+    + Sub<Self, Output = Self>
+    + Mul<f64, Output = Self>
+    + 'static
+{
+}
+
+impl<T> Field for T where
+    T: Copy
+        + PartialEq
+        + Send
+        + Sync
+        + Add<T, Output = T>
+        + Sub<T, Output = T>
+        + Mul<f64, Output = T>
+        + 'static
+{
+}
+
+/// An [`AnimationTarget`] for every field of a composite value, see [`animate_fields()`]. It is
+/// implemented for tuples of up to 4 targets, and derived for structs with the `derive` feature.
+pub trait FieldTargets: Sized + 'static {
+    /// The animated values of the fields
+    type Value;
+
+    /// Animates every field of `source` with its own animated signal
+    fn animate(source: Signal<Self, LocalStorage>) -> Signal<Self::Value, LocalStorage>;
+}
+
+macro_rules! impl_field_targets {
+    ($($field:ident $value:ident $index:tt),*) => {
+        impl<$($field: Field),*> FieldTargets for ($(AnimationTarget<$field>,)*) {
+            type Value = ($($field,)*);
+
+            fn animate(source: Signal<Self, LocalStorage>) -> Signal<Self::Value, LocalStorage> {
+                $(
+                    let $value = animate_field(source, |targets: &Self| targets.$index.clone());
+                )*
+                Signal::derive_local(move || ($($value.get(),)*))
+            }
+        }
+    };
+}
+
+/// Animates the field of the targets of `source` that `field` picks, as the implementations of
+/// [`FieldTargets`] do for every field. Only a change of the target of the field starts an
+/// animation, where a new [`on_complete()`](AnimationTarget::on_complete) callback counts as a
+/// change.
+pub fn animate_field<S: 'static, F: Field>(
+    source: Signal<S, LocalStorage>,
+    field: impl Fn(&S) -> AnimationTarget<F> + Send + Sync + 'static,
+) -> AnimatedSignal<F, F> {
+    let target = Memo::new_with_compare(
+        move |_| source.with(&field),
+        |previous, target| {
+            previous != target
+                || previous.zip(target).is_some_and(|(previous, target)| {
+                    !OnComplete::same(&previous.on_complete, &target.on_complete)
+                })
+        },
+    );
+    AnimatedSignal::new(target, tween_default)
+}
+
+impl_field_targets!(A a 0, B b 1);
+impl_field_targets!(A a 0, B b 1, C c 2);
+impl_field_targets!(A a 0, B b 1, C c 2, D d 3);
+
+/// Animates the fields of a composite value independently. The source returns a tuple with a
+/// separate [`AnimationTarget`] for every field, so each field has its own duration, easing and
+/// [`AnimationMode`](crate::AnimationMode), and the output is the tuple of their animated values.
+///
+/// This suits drag interactions on a single value, for example an element that follows the pointer
+/// horizontally but snaps between rows vertically. A field only starts a new animation when its own
+/// target changes. Structs of fields can derive [`FieldTargets`] to be animated as a whole.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::fields::animate_fields;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (pointer, set_pointer) = signal((0.0f64, 0.0f64));
+///
+/// let position = animate_fields(move || {
+///     let (x, y) = pointer.get();
+///     (
+///         AnimationTarget::from((x, AnimationMode::ReplaceOrStart)),
+///         AnimationTarget::from(((y / 40.0).round() * 40.0, AnimationMode::Snap)),
+///     )
+/// });
+///
+/// assert_eq!(position.get_untracked(), (0.0, 0.0));
+/// ```
+pub fn animate_fields<F: FieldTargets>(
+    source: impl Fn() -> F + 'static,
+) -> Signal<F::Value, LocalStorage> {
+    F::animate(Signal::derive_local(source))
+}
+
+#[cfg(all(test, not(feature = "disable-animations")))]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::testing::TestAnimationHarness;
+
+    #[test]
+    fn new_callbacks_of_unchanged_targets_still_run() {
+        let harness = TestAnimationHarness::new();
+        let (armed, set_armed) = signal(false);
+        let completed = Arc::new(AtomicBool::new(false));
+        let callback_completed = Arc::clone(&completed);
+        let position = animate_fields(move || {
+            let x = AnimationTarget::from(100.0);
+            let x = match armed.get() {
+                true => {
+                    let completed = Arc::clone(&callback_completed);
+                    x.on_complete(move || completed.store(true, Ordering::SeqCst))
+                }
+                false => x,
+            };
+            (x, AnimationTarget::from(0.0))
+        });
+        harness.observe(position);
+
+        set_armed.set(true);
+        harness.run_until_settled();
+        assert!(completed.load(Ordering::SeqCst));
+        assert_eq!(position.get_untracked(), (100.0, 0.0));
+    }
+}
@@ -0,0 +1,87 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+#[cfg(not(feature = "disable-animations"))]
+use leptos::prelude::*;
+
+use crate::AnimatedSignal;
+
+/// A caller of [`AnimatedSignal::finished()`] waiting for the animations to settle
+#[derive(Default)]
+pub(crate) struct Waiter {
+    done: bool,
+    waker: Option<Waker>,
+}
+
+pub(crate) type SharedWaiter = Arc<Mutex<Waiter>>;
+
+/// Resolves the futures of all `waiters`
+pub(crate) fn resolve(waiters: Vec<SharedWaiter>) {
+    for waiter in waiters {
+        if let Ok(mut waiter) = waiter.lock() {
+            waiter.done = true;
+            if let Some(waker) = waiter.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+struct Finished(SharedWaiter);
+
+impl Future for Finished {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        match self.0.lock() {
+            Ok(mut waiter) if !waiter.done => {
+                waiter.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            _ => Poll::Ready(()),
+        }
+    }
+}
+
+impl<T, I> AnimatedSignal<T, I> {
+    /// A future that resolves on the first animation frame in which no animation of this signal is
+    /// running anymore, so async code can wait for an exit animation before it moves on. Because
+    /// it waits for a frame, targets that were set just before the call are taken into account.
+    /// It also resolves when the signal is disposed.
+    ///
+    /// Like the value of the signal, the animations only advance while the signal is observed,
+    /// for example by the view that shows it.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (open, set_open) = signal(true);
+    /// let opacity = AnimatedSignal::new(move || if open.get() { 1.0 } else { 0.0 }.into(), tween_default);
+    ///
+    /// let close = async move {
+    ///     set_open.set(false);
+    ///     opacity.finished().await;
+    ///     // Navigate away once the modal has faded out
+    /// };
+    /// ```
+    pub fn finished(&self) -> impl Future<Output = ()> {
+        let waiter = SharedWaiter::default();
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            if self
+                .finished_waiters
+                .try_update_value(|waiters| waiters.push(waiter.clone()))
+                .is_none()
+            {
+                resolve(vec![waiter.clone()]);
+            }
+            self.context.request_animation_frame();
+        }
+        // Without animations the signal is always at its target
+        #[cfg(feature = "disable-animations")]
+        resolve(vec![waiter.clone()]);
+        Finished(waiter)
+    }
+}
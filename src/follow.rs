@@ -0,0 +1,93 @@
+use std::ops::{Add, Mul, Sub};
+use std::time::Duration;
+
+use instant::Instant;
+use leptos::prelude::*;
+
+use crate::AnimationContext;
+
+/// The exponent at which the gap has closed to 5% after the ease time
+const EASE_EXPONENT: f64 = 3.0;
+
+/// The number of ease times after the last change of the target at which the output snaps to it
+const SETTLE_EASE_TIMES: u32 = 4;
+
+struct FollowState<T> {
+    value: T,
+    target: T,
+    updated: Instant,
+    target_changed: Instant,
+}
+
+/// Creates a signal that smoothly chases a target which changes continuously, such as the pointer
+/// position, a measured element size or another animated signal.
+///
+/// Unlike an [`AnimatedSignal`](crate::AnimatedSignal), which plays a separate animation for every
+/// new target, the output follows the target on every frame and closes 95% of the distance within
+/// `ease_time`, with the motion slowing down as it gets closer. Changes of the target while it is
+/// being chased are absorbed without restarting anything. Once the target has stood still for a
+/// while the output settles on it exactly and stops requesting animation frames.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::follow::follow;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (pointer_x, set_pointer_x) = signal(0.0);
+///
+/// // A tooltip that trails behind the pointer
+/// let tooltip_x = follow(move || pointer_x.get() + 12.0, Duration::from_millis(150));
+/// assert_eq!(tooltip_x.get_untracked(), 12.0);
+/// # let _html =
+/// view! { <div style:left=move || format!("{}px", tooltip_x.get())>"Tooltip"</div> }
+/// # .to_html();
+/// ```
+pub fn follow<T>(target: impl Fn() -> T + 'static, ease_time: Duration) -> Signal<T, LocalStorage>
+where
+    T: Copy + PartialEq + Add<T, Output = T> + Sub<T, Output = T> + Mul<f64, Output = T> + 'static,
+{
+    let context: AnimationContext = use_context()
+        .expect("No AnimationContext present, call AnimationContext::provide() in a parent scope");
+
+    let initial = untrack(&target);
+    let now = context.now();
+    let state = StoredValue::new_local(FollowState {
+        value: initial,
+        target: initial,
+        updated: now,
+        target_changed: now,
+    });
+    let settle_time = ease_time * SETTLE_EASE_TIMES;
+
+    Signal::derive_local(move || {
+        context.animation_frame.track();
+        let target = target();
+        let now = context.now();
+        state
+            .try_update_value(|state| {
+                let elapsed = now.saturating_duration_since(state.updated);
+                state.updated = now;
+                if target != state.target {
+                    state.target = target;
+                    state.target_changed = now;
+                }
+                if state.value == target {
+                    return target;
+                }
+                if now.saturating_duration_since(state.target_changed) >= settle_time {
+                    state.value = target;
+                    return target;
+                }
+                let closed = match ease_time.is_zero() {
+                    true => 1.0,
+                    false => {
+                        1.0 - (-EASE_EXPONENT * elapsed.as_secs_f64() / ease_time.as_secs_f64())
+                            .exp()
+                    }
+                };
+                state.value = state.value + (target - state.value) * closed;
+                context.request_animation_frame();
+                state.value
+            })
+            .unwrap_or(target)
+    })
+}
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+#[cfg(not(feature = "disable-animations"))]
+use instant::Instant;
+
+use leptos::prelude::*;
+
+use crate::{AnimatedSignal, AnimationContext, AnimationContextState};
+
+/// The lowest time between two updates of the output of an animated signal
+#[cfg(not(feature = "disable-animations"))]
+#[derive(Default)]
+pub(crate) struct FrameInterval {
+    interval: Option<Duration>,
+    /// The real time of the last update of the output
+    last_update: Option<Instant>,
+}
+
+#[cfg(not(feature = "disable-animations"))]
+impl FrameInterval {
+    /// The time between updates, `None` to update on every frame
+    pub(crate) fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    /// The time to wait until the output may update again, `None` if it may update at `real_now`,
+    /// which then counts as the last update
+    pub(crate) fn wait(&mut self, real_now: Instant) -> Option<Duration> {
+        let interval = self.interval?;
+        if let Some(last_update) = self.last_update {
+            let wait = (last_update + interval).saturating_duration_since(real_now);
+            if !wait.is_zero() {
+                return Some(wait);
+            }
+        }
+        self.last_update = Some(real_now);
+        None
+    }
+}
+
+impl AnimationContext {
+    /// Requests a frame that is not needed before `delay` has passed. The scheduler waits without
+    /// waking up in between if it can, unless another frame is requested in the meantime.
+    pub(crate) fn request_frame_after(&self, delay: Duration) {
+        if delay.is_zero() {
+            self.request_animation_frame();
+            return;
+        }
+        let at = self.real_now() + delay;
+        match self.state.get_value() {
+            AnimationContextState::AnimationFrameRequested => return,
+            AnimationContextState::FrameRequestedAt(requested) if requested <= at => return,
+            AnimationContextState::FrameRequestedAt(_) => self
+                .scheduler
+                .with_value(|scheduler| scheduler.cancel_frame()),
+            AnimationContextState::NoAnimationFrameRequested => {}
+        }
+
+        let this = *self;
+        self.state
+            .set_value(AnimationContextState::FrameRequestedAt(at));
+        self.steady_time
+            .update_value(|steady_time| steady_time.expect_gap(at));
+        self.count_frame_request();
+        self.scheduler.with_value(|scheduler| {
+            scheduler.request_frame_after(delay, Box::new(move || this.on_animation_frame()))
+        });
+    }
+}
+
+impl<T, I> AnimatedSignal<T, I> {
+    /// Updates the output of this signal at most once per `interval` instead of on every frame,
+    /// for animations that play over minutes or hours such as a color scheme that follows the
+    /// sunset. While only such signals are animating, the context sleeps between their updates
+    /// instead of waking up on every frame of the display, if its [`Scheduler`](crate::scheduler::Scheduler)
+    /// supports waiting. The animation itself is not affected, every update shows the value at
+    /// the time of the update.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # use leptos_animation::color::{tween_rgba, Rgba};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (night, set_night) = signal(false);
+    /// let background = AnimatedSignal::new(
+    ///     move || {
+    ///         let color = match night.get() {
+    ///             true => Rgba::rgb8(20, 24, 48),
+    ///             false => Rgba::rgb8(250, 248, 240),
+    ///         };
+    ///         (color, Duration::from_secs(2 * 60 * 60), easing::LINEAR).into()
+    ///     },
+    ///     tween_rgba,
+    /// )
+    /// // Nobody notices a two hour fade changing once every five seconds
+    /// .with_frame_interval(Duration::from_secs(5));
+    /// ```
+    pub fn with_frame_interval(self, interval: Duration) -> Self {
+        // Signals without animations have nothing to apply the setting to
+        #[cfg(not(feature = "disable-animations"))]
+        self.frame_interval.update_value(|frame_interval| {
+            frame_interval.interval = Some(interval).filter(|interval| !interval.is_zero());
+        });
+        #[cfg(feature = "disable-animations")]
+        let _ = interval;
+        self
+    }
+}
@@ -0,0 +1,327 @@
+//! Gradients whose stops are animated, for animated backgrounds and the fills of charts.
+
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+use crate::color::Rgba;
+use crate::css::format_number;
+use crate::style::Deg;
+use crate::tween::Tween;
+
+/// A color stop of a [`Gradient`]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GradientStop {
+    /// The color at the stop
+    pub color: Rgba,
+    /// The position of the stop along the gradient, 0.0 is the start and 1.0 the end
+    pub position: f64,
+}
+
+/// The shape of a [`Gradient`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientShape {
+    /// A `linear-gradient()` in the direction of the angle, 0° points up and 90° to the right
+    Linear(Deg),
+    /// A circular `radial-gradient()` from the center outwards
+    Radial,
+    /// A `conic-gradient()` around the center, starting at the angle
+    Conic(Deg),
+}
+
+impl Default for GradientShape {
+    fn default() -> Self {
+        // The direction of `linear-gradient()` without an angle
+        GradientShape::Linear(Deg(180.0))
+    }
+}
+
+/// A CSS gradient with any number of color stops, animated as a single value with
+/// [`tween_gradient`] and written out with [`to_css()`](Gradient::to_css).
+///
+/// The stops are added in the order of their positions. Gradients with a different number of stops
+/// animate into each other too: the gradient with fewer stops gets extra stops that lie on its own
+/// colors, so it looks exactly the same, and which then move apart to become the extra stops of the
+/// other gradient. Going the other way, the extra stops merge into their neighbours.
+/// ```
+/// # use leptos_animation::color::Rgba;
+/// # use leptos_animation::gradient::Gradient;
+/// # use leptos_animation::style::Deg;
+/// let sunset = Gradient::linear(Deg(90.0))
+///     .stop(Rgba::from_hex("#ff8000").unwrap(), 0.0)
+///     .stop(Rgba::from_hex("#8000ff").unwrap(), 1.0);
+/// assert_eq!(
+///     sunset.to_css(),
+///     "linear-gradient(90deg, rgb(255, 128, 0) 0%, rgb(128, 0, 255) 100%)"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Gradient {
+    /// The shape of the gradient
+    pub shape: GradientShape,
+    /// The color stops, in the order of their positions
+    pub stops: Vec<GradientStop>,
+}
+
+impl Gradient {
+    /// A `linear-gradient()` in the direction of `angle`, without stops
+    pub fn linear(angle: Deg) -> Gradient {
+        Gradient {
+            shape: GradientShape::Linear(angle),
+            stops: Vec::new(),
+        }
+    }
+
+    /// A circular `radial-gradient()` from the center outwards, without stops
+    pub fn radial() -> Gradient {
+        Gradient {
+            shape: GradientShape::Radial,
+            stops: Vec::new(),
+        }
+    }
+
+    /// A `conic-gradient()` starting at `angle`, without stops
+    pub fn conic(angle: Deg) -> Gradient {
+        Gradient {
+            shape: GradientShape::Conic(angle),
+            stops: Vec::new(),
+        }
+    }
+
+    /// Adds a stop of `color` at `position`, from 0.0 at the start to 1.0 at the end
+    pub fn stop(mut self, color: Rgba, position: f64) -> Gradient {
+        self.stops.push(GradientStop { color, position });
+        self
+    }
+
+    /// The gradient as a CSS image, for `background-image` or the `fill` of a chart. A gradient
+    /// without stops is written as `none`.
+    pub fn to_css(&self) -> String {
+        self.to_string()
+    }
+
+    /// Both gradients with the same number of stops, see [`Gradient`]
+    fn align(&self, other: &Gradient) -> (Vec<GradientStop>, Vec<GradientStop>) {
+        match (self.stops.len(), other.stops.len()) {
+            (from, to) if from == to => (self.stops.clone(), other.stops.clone()),
+            (0, _) => (transparent(&other.stops), other.stops.clone()),
+            (_, 0) => (self.stops.clone(), transparent(&self.stops)),
+            (from, to) if from < to => (
+                with_stops_of(&self.stops, &other.stops),
+                other.stops.clone(),
+            ),
+            _ => (self.stops.clone(), with_stops_of(&other.stops, &self.stops)),
+        }
+    }
+
+    /// Combines both gradients stop by stop, after giving them the same stops
+    fn zip(&self, rhs: &Gradient, f: impl Fn(f64, f64) -> f64) -> Gradient {
+        let shape = match (self.shape, rhs.shape) {
+            (GradientShape::Linear(Deg(a)), GradientShape::Linear(Deg(b))) => {
+                GradientShape::Linear(Deg(f(a, b)))
+            }
+            (GradientShape::Conic(Deg(a)), GradientShape::Conic(Deg(b))) => {
+                GradientShape::Conic(Deg(f(a, b)))
+            }
+            (shape, _) => shape,
+        };
+        let (from, to) = self.align(rhs);
+        let stops = from
+            .iter()
+            .zip(&to)
+            .map(|(a, b)| GradientStop {
+                color: Rgba::new(
+                    f(a.color.r, b.color.r),
+                    f(a.color.g, b.color.g),
+                    f(a.color.b, b.color.b),
+                    f(a.color.a, b.color.a),
+                ),
+                position: f(a.position, b.position),
+            })
+            .collect();
+        Gradient { shape, stops }
+    }
+}
+
+/// The stops in transparent versions of their colors, for a gradient without stops to fade from
+fn transparent(stops: &[GradientStop]) -> Vec<GradientStop> {
+    stops
+        .iter()
+        .map(|stop| GradientStop {
+            color: stop.color.with_alpha(0.0),
+            ..*stop
+        })
+        .collect()
+}
+
+/// The gradient of `stops` with as many stops as `other`, looking the same. The first and last
+/// stops stay where they are, the others are matched to the stops of `other` spread out evenly,
+/// and the stops of `other` in between get a stop at the same relative place between the matched
+/// stops, in the color that the gradient shows there.
+fn with_stops_of(stops: &[GradientStop], other: &[GradientStop]) -> Vec<GradientStop> {
+    if let [only] = stops {
+        return vec![*only; other.len()];
+    }
+    let last = stops.len() - 1;
+    let matched =
+        |index: usize| (index as f64 * (other.len() - 1) as f64 / last as f64).round() as usize;
+    let mut aligned = Vec::with_capacity(other.len());
+    for (index, pair) in stops.windows(2).enumerate() {
+        let (start, end) = (matched(index), matched(index + 1));
+        aligned.push(pair[0]);
+        for inserted in start + 1..end {
+            let span = other[end].position - other[start].position;
+            let fraction = match span.abs() > f64::EPSILON {
+                true => (other[inserted].position - other[start].position) / span,
+                false => (inserted - start) as f64 / (end - start) as f64,
+            };
+            aligned.push(GradientStop {
+                color: mix_premultiplied(&pair[0].color, &pair[1].color, fraction),
+                position: pair[0].position.tween(&pair[1].position, fraction),
+            });
+        }
+    }
+    aligned.push(stops[last]);
+    aligned
+}
+
+/// Mixes two colors the way browsers draw the colors between two stops of a gradient, in sRGB
+/// premultiplied by the alpha
+fn mix_premultiplied(from: &Rgba, to: &Rgba, fraction: f64) -> Rgba {
+    let premultiplied = |color: &Rgba| [color.r * color.a, color.g * color.a, color.b * color.a];
+    let [from_r, from_g, from_b] = premultiplied(from);
+    let [to_r, to_g, to_b] = premultiplied(to);
+    let a = from.a.tween(&to.a, fraction);
+    if a <= 0.0 {
+        return Rgba::new(0.0, 0.0, 0.0, a);
+    }
+    Rgba::new(
+        from_r.tween(&to_r, fraction) / a,
+        from_g.tween(&to_g, fraction) / a,
+        from_b.tween(&to_b, fraction) / a,
+        a,
+    )
+}
+
+impl fmt::Display for Gradient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.stops.is_empty() {
+            return write!(f, "none");
+        }
+        match self.shape {
+            GradientShape::Linear(Deg(angle)) => {
+                write!(f, "linear-gradient({}deg", format_number(angle))?
+            }
+            GradientShape::Radial => write!(f, "radial-gradient(circle")?,
+            GradientShape::Conic(Deg(angle)) => {
+                write!(f, "conic-gradient(from {}deg", format_number(angle))?
+            }
+        }
+        for stop in &self.stops {
+            write!(
+                f,
+                ", {} {}%",
+                stop.color,
+                format_number(stop.position * 100.0)
+            )?;
+        }
+        // CSS gradients need at least two stops, a single one fills the whole gradient
+        if let [only] = self.stops.as_slice() {
+            write!(
+                f,
+                ", {} {}%",
+                only.color,
+                format_number(only.position * 100.0)
+            )?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Interpolates between two gradients stop by stop: the colors in linear light like
+/// [`tween_rgba`](crate::color::tween_rgba), and the positions and angles linearly. When the
+/// gradients have a different number of stops, stops are inserted into or merged out of the one
+/// with fewer, see [`Gradient`]. Gradients of different shapes switch shape halfway.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::color::Rgba;
+/// # use leptos_animation::gradient::{tween_gradient, Gradient};
+/// # use leptos_animation::style::Deg;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let black = Rgba::rgb8(0, 0, 0);
+/// let white = Rgba::rgb8(255, 255, 255);
+/// let red = Rgba::rgb8(255, 0, 0);
+/// let fade = Gradient::linear(Deg(90.0)).stop(black, 0.0).stop(white, 1.0);
+/// let banded = Gradient::linear(Deg(90.0)).stop(black, 0.0).stop(red, 0.25).stop(white, 1.0);
+///
+/// // The fade gets a grey stop at a quarter, where the red band grows from
+/// assert_eq!(
+///     tween_gradient(&fade, &banded, 0.0).to_css(),
+///     "linear-gradient(90deg, rgb(0, 0, 0) 0%, rgb(64, 64, 64) 25%, rgb(255, 255, 255) 100%)"
+/// );
+///
+/// let (highlighted, set_highlighted) = signal(false);
+/// let background = AnimatedSignal::new(
+///     move || if highlighted.get() { banded.clone() } else { fade.clone() }.into(),
+///     tween_gradient,
+/// );
+/// # let _html =
+/// view! { <div style:background-image=move || background.get().to_css()></div> }
+/// # .to_html();
+/// ```
+pub fn tween_gradient(from: &Gradient, to: &Gradient, progress: f64) -> Gradient {
+    let shape = match (from.shape, to.shape) {
+        (GradientShape::Linear(Deg(a)), GradientShape::Linear(Deg(b))) => {
+            GradientShape::Linear(Deg(a.tween(&b, progress)))
+        }
+        (GradientShape::Conic(Deg(a)), GradientShape::Conic(Deg(b))) => {
+            GradientShape::Conic(Deg(a.tween(&b, progress)))
+        }
+        (from, to) => match progress < 0.5 {
+            true => from,
+            false => to,
+        },
+    };
+    let (from, to) = from.align(to);
+    let stops = from
+        .iter()
+        .zip(&to)
+        .map(|(from, to)| GradientStop {
+            color: from.color.tween(&to.color, progress),
+            position: from.position.tween(&to.position, progress),
+        })
+        .collect();
+    Gradient { shape, stops }
+}
+
+impl Tween for Gradient {
+    type Interpolated = Gradient;
+
+    fn tween(&self, other: &Self, progress: f64) -> Gradient {
+        tween_gradient(self, other, progress)
+    }
+}
+
+impl Add for Gradient {
+    type Output = Gradient;
+
+    fn add(self, rhs: Gradient) -> Gradient {
+        self.zip(&rhs, |a, b| a + b)
+    }
+}
+
+impl Sub for Gradient {
+    type Output = Gradient;
+
+    fn sub(self, rhs: Gradient) -> Gradient {
+        self.zip(&rhs, |a, b| a - b)
+    }
+}
+
+impl Mul<f64> for Gradient {
+    type Output = Gradient;
+
+    fn mul(self, rhs: f64) -> Gradient {
+        self.zip(&self, |a, _| a * rhs)
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use crate::{AnimatedSignal, AnimationContext};
+
+type HistoryFn = Box<dyn Fn() -> Vec<HistoryEntry<String>>>;
+
+/// The upper bounds of the buckets of [`SignalCost::buckets`], frames above the last bound go
+/// into the last bucket
+pub const COST_BUCKETS: [Duration; 5] = [
+    Duration::from_micros(100),
+    Duration::from_micros(500),
+    Duration::from_millis(1),
+    Duration::from_millis(2),
+    Duration::from_millis(4),
+];
+
+/// A single value emitted by an animated signal
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry<I> {
+    /// The time at which the value was emitted, relative to the creation of the `AnimationContext`
+    pub time: Duration,
+
+    /// The emitted value
+    pub value: I,
+}
+
+/// A recording of the most recent values emitted by an animated signal.
+/// Created with [`AnimatedSignal::history()`].
+#[derive(Copy, Clone)]
+pub struct SignalHistory<I: 'static> {
+    context: AnimationContext,
+    entries: StoredValue<VecDeque<HistoryEntry<I>>, LocalStorage>,
+}
+
+impl<I: Clone + Debug> SignalHistory<I> {
+    /// All recorded values, oldest first
+    pub fn entries(&self) -> Vec<HistoryEntry<I>> {
+        self.entries
+            .with_value(|entries| entries.iter().cloned().collect())
+    }
+
+    /// Removes all recorded values
+    pub fn clear(&self) {
+        self.entries.update_value(VecDeque::clear);
+    }
+
+    /// Registers this history under `name` in the [`Inspector`] of the animation context
+    pub fn inspect(self, name: impl Into<String>) -> Self {
+        let history: HistoryFn = Box::new(move || {
+            self.entries
+                .try_with_value(|entries| {
+                    entries
+                        .iter()
+                        .map(|entry| HistoryEntry {
+                            time: entry.time,
+                            value: format!("{:?}", entry.value),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        });
+        self.context
+            .inspector
+            .histories
+            .update_value(|histories| histories.push((name.into(), history)));
+        self
+    }
+}
+
+impl<I> Dispose for SignalHistory<I> {
+    fn dispose(self) {
+        self.entries.dispose();
+    }
+}
+
+/// The time an animated signal spent evaluating its value, registered with
+/// [`AnimatedSignal::profile()`]. Costs are measured with `performance.now()`, which browsers
+/// round to coarse steps, so the buckets are more meaningful than single frames.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignalCost {
+    /// The name the signal was registered under
+    pub name: String,
+
+    /// The number of frames in which the signal was evaluated
+    pub frames: u64,
+
+    /// The time spent evaluating over all frames
+    pub total: Duration,
+
+    /// The time spent in the most expensive frame
+    pub worst_frame: Duration,
+
+    /// The number of frames per cost bucket, with the bounds of [`COST_BUCKETS`] and a last
+    /// bucket for the frames above them
+    pub buckets: [u64; COST_BUCKETS.len() + 1],
+}
+
+impl SignalCost {
+    fn empty(name: String) -> SignalCost {
+        SignalCost {
+            name,
+            frames: 0,
+            total: Duration::ZERO,
+            worst_frame: Duration::ZERO,
+            buckets: Default::default(),
+        }
+    }
+
+    /// The average time spent evaluating per frame
+    pub fn average(&self) -> Duration {
+        match self.frames {
+            0 => Duration::ZERO,
+            frames => self.total.div_f64(frames as f64),
+        }
+    }
+
+    fn add_frame(&mut self, cost: Duration) {
+        self.frames += 1;
+        self.total += cost;
+        self.worst_frame = self.worst_frame.max(cost);
+        let bucket = COST_BUCKETS.partition_point(|bound| *bound < cost);
+        self.buckets[bucket] += 1;
+    }
+}
+
+struct CostSlot {
+    cost: SignalCost,
+    /// The frame and the time spent in it so far, added to `cost` once the frame has passed
+    current_frame: Option<(u64, Duration)>,
+}
+
+impl CostSlot {
+    /// The cost including the current frame
+    fn cost(&self) -> SignalCost {
+        let mut cost = self.cost.clone();
+        if let Some((_, current)) = self.current_frame {
+            cost.add_frame(current);
+        }
+        cost
+    }
+}
+
+/// The `Inspector` is a registry of the debugging information of all animated signals in an
+/// `AnimationContext`. It is only available with the `inspector` feature and can be retrieved with
+/// [`AnimationContext::inspector()`].
+#[derive(Copy, Clone)]
+pub struct Inspector {
+    histories: StoredValue<Vec<(String, HistoryFn)>, LocalStorage>,
+    costs: StoredValue<Vec<CostSlot>>,
+    frame: StoredValue<u64>,
+}
+
+impl Inspector {
+    pub(crate) fn new() -> Inspector {
+        Inspector {
+            histories: StoredValue::new_local(Vec::new()),
+            costs: StoredValue::new(Vec::new()),
+            frame: StoredValue::new(0),
+        }
+    }
+
+    /// The evaluation costs of all signals registered with [`AnimatedSignal::profile()`], the
+    /// most expensive first. This is a heatmap of where the animation frames spend their time.
+    pub fn heatmap(&self) -> Vec<SignalCost> {
+        let mut costs: Vec<SignalCost> = self
+            .costs
+            .with_value(|costs| costs.iter().map(CostSlot::cost).collect());
+        costs.sort_by_key(|cost| std::cmp::Reverse(cost.total));
+        costs
+    }
+
+    /// Forgets all measured costs, keeping the registered signals
+    pub fn reset_costs(&self) {
+        self.costs.update_value(|costs| {
+            for slot in costs {
+                slot.cost = SignalCost::empty(std::mem::take(&mut slot.cost.name));
+                slot.current_frame = None;
+            }
+        });
+    }
+
+    #[cfg(not(feature = "disable-animations"))]
+    fn register_cost(&self, name: String) -> usize {
+        self.costs
+            .try_update_value(|costs| {
+                costs.push(CostSlot {
+                    cost: SignalCost::empty(name),
+                    current_frame: None,
+                });
+                costs.len() - 1
+            })
+            .unwrap_or_default()
+    }
+
+    /// Starts attributing evaluation costs to the next frame
+    pub(crate) fn begin_frame(&self) {
+        self.frame.try_update_value(|frame| *frame += 1);
+    }
+
+    /// Adds the cost of one evaluation of the signal in `slot` to the current frame
+    #[cfg(not(feature = "disable-animations"))]
+    pub(crate) fn record_cost(&self, slot: usize, cost: Duration) {
+        let Some(frame) = self.frame.try_get_value() else {
+            return;
+        };
+        self.costs.try_update_value(|costs| {
+            let Some(slot) = costs.get_mut(slot) else {
+                return;
+            };
+            match &mut slot.current_frame {
+                Some((current, current_cost)) if *current == frame => *current_cost += cost,
+                current_frame => {
+                    if let Some((_, previous_cost)) = current_frame.replace((frame, cost)) {
+                        slot.cost.add_frame(previous_cost);
+                    }
+                }
+            }
+        });
+    }
+
+    /// The names of all signal histories registered with [`SignalHistory::inspect()`]
+    pub fn names(&self) -> Vec<String> {
+        self.histories
+            .with_value(|histories| histories.iter().map(|(name, _)| name.clone()).collect())
+    }
+
+    /// The recorded history of the signal registered under `name`, with its values formatted with `Debug`
+    pub fn history(&self, name: &str) -> Option<Vec<HistoryEntry<String>>> {
+        self.histories.with_value(|histories| {
+            histories
+                .iter()
+                .find(|(history_name, _)| history_name == name)
+                .map(|(_, history)| history())
+        })
+    }
+}
+
+impl AnimationContext {
+    /// The [`Inspector`] holding the debugging information of this context
+    pub fn inspector(&self) -> Inspector {
+        self.inspector
+    }
+}
+
+impl<T, I: Clone + Debug> AnimatedSignal<T, I> {
+    /// Starts recording the last `n_frames` values emitted by this signal along with their timestamps.
+    /// This is useful to debug flickering or glitches on retargeting after they happened.
+    /// Register the history with [`SignalHistory::inspect()`] to make it available in the [`Inspector`].
+    ///
+    /// Only available with the `inspector` feature.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimationContext, AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = AnimationContext::provide();
+    /// let (x, set_x) = signal(0.0);
+    /// let animated_x = AnimatedSignal::new(move || x.get().into(), tween_default);
+    ///
+    /// animated_x.history(120).inspect("sidebar x");
+    ///
+    /// // Later, for example from the browser console or a debug panel
+    /// let history = context.inspector().history("sidebar x");
+    /// ```
+    pub fn history(&self, n_frames: usize) -> SignalHistory<I> {
+        let context: AnimationContext = use_context().expect(
+            "No AnimationContext present, call AnimationContext::provide() in a parent scope",
+        );
+        let entries = StoredValue::new_local(VecDeque::with_capacity(n_frames));
+        let signal = self.animated_signal;
+
+        Effect::new(move |_| {
+            let value = signal.get();
+            entries.update_value(|entries: &mut VecDeque<HistoryEntry<I>>| {
+                entries.push_back(HistoryEntry {
+                    time: context.now() - context.epoch,
+                    value,
+                });
+                while entries.len() > n_frames {
+                    entries.pop_front();
+                }
+            });
+        });
+
+        SignalHistory { context, entries }
+    }
+}
+
+impl<T, I> AnimatedSignal<T, I> {
+    /// Starts measuring the time this signal spends evaluating its value in every frame, and
+    /// registers the measurements under `name` in the [`Inspector::heatmap()`].
+    ///
+    /// Only available with the `inspector` feature.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimationContext, AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = AnimationContext::provide();
+    /// let (x, set_x) = signal(0.0);
+    /// let animated_x = AnimatedSignal::new(move || x.get().into(), tween_default);
+    /// animated_x.profile("sidebar x");
+    ///
+    /// animated_x.get_untracked();
+    /// let heatmap = context.inspector().heatmap();
+    /// assert_eq!(heatmap[0].name, "sidebar x");
+    /// assert_eq!(heatmap[0].frames, 1);
+    /// ```
+    pub fn profile(&self, name: impl Into<String>) {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let slot = self.context.inspector.register_cost(name.into());
+            self.profile.set_value(Some(slot));
+        }
+        // Without animations there are no frames to measure
+        #[cfg(feature = "disable-animations")]
+        let _ = name;
+    }
+}
@@ -0,0 +1,337 @@
+//! CSS lengths with units, for animating layout properties such as `width`, `margin` and `gap`.
+
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+use crate::css::format_number;
+use crate::style::Px;
+use crate::tween::Tween;
+
+/// A unit of a [`CssLength`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LengthUnit {
+    /// `px`
+    Px,
+    /// `%` of the containing block
+    Percent,
+    /// `em`, the font size of the element
+    Em,
+    /// `rem`, the font size of the root element
+    Rem,
+    /// `vw`, a percent of the width of the viewport
+    Vw,
+    /// `vh`, a percent of the height of the viewport
+    Vh,
+    /// `vmin`, a percent of the smaller side of the viewport
+    Vmin,
+    /// `vmax`, a percent of the larger side of the viewport
+    Vmax,
+}
+
+const UNITS: [LengthUnit; 8] = [
+    LengthUnit::Px,
+    LengthUnit::Percent,
+    LengthUnit::Em,
+    LengthUnit::Rem,
+    LengthUnit::Vw,
+    LengthUnit::Vh,
+    LengthUnit::Vmin,
+    LengthUnit::Vmax,
+];
+
+impl LengthUnit {
+    /// The unit as it is written after a number in CSS
+    pub fn suffix(self) -> &'static str {
+        match self {
+            LengthUnit::Px => "px",
+            LengthUnit::Percent => "%",
+            LengthUnit::Em => "em",
+            LengthUnit::Rem => "rem",
+            LengthUnit::Vw => "vw",
+            LengthUnit::Vh => "vh",
+            LengthUnit::Vmin => "vmin",
+            LengthUnit::Vmax => "vmax",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Whether a term of a sum is too small to write, such as the rounding errors of additive animations
+fn is_negligible(term: f64) -> bool {
+    term.abs() < 1e-9
+}
+
+/// A CSS length in one of the units of [`LengthUnit`], or a sum of lengths in different units.
+///
+/// Lengths in the same unit interpolate in that unit. Units whose ratio is only known to the
+/// browser, such as `px` and `%`, interpolate into a [`Calc`](CssLength::Calc) that shrinks the
+/// one while growing the other, written out as `calc()`, just as browsers interpolate them.
+/// ```
+/// # use leptos_animation::length::CssLength;
+/// # use leptos_animation::tween::Tween;
+/// assert_eq!(CssLength::Rem(1.0).tween(&CssLength::Rem(2.0), 0.5).to_css(), "1.5rem");
+/// assert_eq!(CssLength::Px(100.0).tween(&CssLength::Percent(50.0), 0.0).to_css(), "100px");
+/// assert_eq!(
+///     CssLength::Px(100.0).tween(&CssLength::Percent(50.0), 0.25).to_css(),
+///     "calc(75px + 12.5%)"
+/// );
+/// assert_eq!(CssLength::Px(100.0).tween(&CssLength::Percent(50.0), 1.0).to_css(), "50%");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CssLength {
+    /// Pixels
+    Px(f64),
+    /// A percentage of the containing block
+    Percent(f64),
+    /// The font size of the element
+    Em(f64),
+    /// The font size of the root element
+    Rem(f64),
+    /// A percentage of the width of the viewport
+    Vw(f64),
+    /// A percentage of the height of the viewport
+    Vh(f64),
+    /// A percentage of the smaller side of the viewport
+    Vmin(f64),
+    /// A percentage of the larger side of the viewport
+    Vmax(f64),
+    /// A sum of lengths in different units, as lengths in different units are in between
+    Calc(CalcLength),
+}
+
+/// A sum of lengths in different units, written as `calc()`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CalcLength {
+    terms: [f64; UNITS.len()],
+}
+
+impl CalcLength {
+    /// The part of the sum in `unit`
+    pub fn get(&self, unit: LengthUnit) -> f64 {
+        self.terms[unit.index()]
+    }
+}
+
+impl Default for CssLength {
+    fn default() -> Self {
+        CssLength::Px(0.0)
+    }
+}
+
+impl From<Px> for CssLength {
+    fn from(Px(px): Px) -> Self {
+        CssLength::Px(px)
+    }
+}
+
+impl CssLength {
+    /// A length of `value` in `unit`
+    pub fn new(value: f64, unit: LengthUnit) -> CssLength {
+        match unit {
+            LengthUnit::Px => CssLength::Px(value),
+            LengthUnit::Percent => CssLength::Percent(value),
+            LengthUnit::Em => CssLength::Em(value),
+            LengthUnit::Rem => CssLength::Rem(value),
+            LengthUnit::Vw => CssLength::Vw(value),
+            LengthUnit::Vh => CssLength::Vh(value),
+            LengthUnit::Vmin => CssLength::Vmin(value),
+            LengthUnit::Vmax => CssLength::Vmax(value),
+        }
+    }
+
+    /// The value and unit of a length in a single unit, `None` for a [`Calc`](CssLength::Calc)
+    pub fn unit_value(&self) -> Option<(f64, LengthUnit)> {
+        match *self {
+            CssLength::Px(value) => Some((value, LengthUnit::Px)),
+            CssLength::Percent(value) => Some((value, LengthUnit::Percent)),
+            CssLength::Em(value) => Some((value, LengthUnit::Em)),
+            CssLength::Rem(value) => Some((value, LengthUnit::Rem)),
+            CssLength::Vw(value) => Some((value, LengthUnit::Vw)),
+            CssLength::Vh(value) => Some((value, LengthUnit::Vh)),
+            CssLength::Vmin(value) => Some((value, LengthUnit::Vmin)),
+            CssLength::Vmax(value) => Some((value, LengthUnit::Vmax)),
+            CssLength::Calc(_) => None,
+        }
+    }
+
+    /// The length as a CSS value, such as `12px` or `calc(50% - 8px)`
+    pub fn to_css(&self) -> String {
+        self.to_string()
+    }
+
+    fn terms(&self) -> [f64; UNITS.len()] {
+        if let CssLength::Calc(calc) = self {
+            return calc.terms;
+        }
+        let mut terms = [0.0; UNITS.len()];
+        if let Some((value, unit)) = self.unit_value() {
+            terms[unit.index()] = value;
+        }
+        terms
+    }
+
+    /// The sum of `terms`, in a single unit when only one of them is left
+    fn from_terms(terms: [f64; UNITS.len()]) -> CssLength {
+        let mut non_zero = UNITS
+            .iter()
+            .zip(terms)
+            .filter(|(_, term)| !is_negligible(*term));
+        match (non_zero.next(), non_zero.next()) {
+            (None, _) => CssLength::Px(0.0),
+            (Some((unit, value)), None) => CssLength::new(value, *unit),
+            _ => CssLength::Calc(CalcLength { terms }),
+        }
+    }
+
+    /// Combines both lengths in their unit when they share one, or unit by unit otherwise
+    fn zip(&self, other: &CssLength, f: impl Fn(f64, f64) -> f64) -> CssLength {
+        match (self.unit_value(), other.unit_value()) {
+            (Some((a, unit)), Some((b, other_unit))) if unit == other_unit => {
+                CssLength::new(f(a, b), unit)
+            }
+            _ => {
+                let (a, b) = (self.terms(), other.terms());
+                CssLength::from_terms(std::array::from_fn(|index| f(a[index], b[index])))
+            }
+        }
+    }
+}
+
+impl fmt::Display for CssLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((value, unit)) = self.unit_value() {
+            return write!(f, "{}{}", format_number(value), unit.suffix());
+        }
+        let terms = self.terms();
+        // An empty sum, such as the default `CalcLength`, is no length at all
+        if terms.iter().all(|term| is_negligible(*term)) {
+            return write!(f, "0px");
+        }
+        write!(f, "calc(")?;
+        let mut first = true;
+        for (unit, term) in UNITS
+            .iter()
+            .zip(terms)
+            .filter(|(_, term)| !is_negligible(*term))
+        {
+            match (first, term < 0.0) {
+                (true, _) => write!(f, "{}", format_number(term))?,
+                (false, true) => write!(f, " - {}", format_number(-term))?,
+                (false, false) => write!(f, " + {}", format_number(term))?,
+            }
+            write!(f, "{}", unit.suffix())?;
+            first = false;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Interpolates between two lengths, see [`CssLength`]
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::length::{tween_length, CssLength};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (expanded, set_expanded) = signal(false);
+/// // A sidebar that takes a third of the page when it is expanded
+/// let width = AnimatedSignal::new(
+///     move || match expanded.get() {
+///         true => CssLength::Percent(33.0),
+///         false => CssLength::Rem(4.0),
+///     }
+///     .into(),
+///     tween_length,
+/// );
+/// # let _html =
+/// view! { <aside style:width=move || width.get().to_css()></aside> }
+/// # .to_html();
+/// ```
+pub fn tween_length(from: &CssLength, to: &CssLength, progress: f64) -> CssLength {
+    from.zip(to, |a, b| a.tween(&b, progress))
+}
+
+impl Tween for CssLength {
+    type Interpolated = CssLength;
+
+    fn tween(&self, other: &Self, progress: f64) -> CssLength {
+        tween_length(self, other, progress)
+    }
+}
+
+impl Add for CssLength {
+    type Output = CssLength;
+
+    fn add(self, rhs: CssLength) -> CssLength {
+        self.zip(&rhs, |a, b| a + b)
+    }
+}
+
+impl Sub for CssLength {
+    type Output = CssLength;
+
+    fn sub(self, rhs: CssLength) -> CssLength {
+        self.zip(&rhs, |a, b| a - b)
+    }
+}
+
+impl Mul<f64> for CssLength {
+    type Output = CssLength;
+
+    fn mul(self, rhs: f64) -> CssLength {
+        self.zip(&self, |a, _| a * rhs)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn length() -> impl Strategy<Value = CssLength> {
+        (-1000..1000i32, 0..UNITS.len())
+            .prop_map(|(value, unit)| CssLength::new(value as f64, UNITS[unit]))
+    }
+
+    #[test]
+    fn empty_sums_write_zero() {
+        assert_eq!(CssLength::Calc(CalcLength::default()).to_css(), "0px");
+        let rounding_error = CalcLength {
+            terms: [1e-12, 0.0, 0.0, 0.0, 0.0, 0.0, -1e-12, 0.0],
+        };
+        assert_eq!(CssLength::Calc(rounding_error).to_css(), "0px");
+    }
+
+    proptest! {
+        #[test]
+        fn tweens_start_and_end_on_their_lengths(from in length(), to in length()) {
+            prop_assert_eq!(from.tween(&to, 0.0).terms(), from.terms());
+            prop_assert_eq!(from.tween(&to, 1.0).terms(), to.terms());
+        }
+
+        #[test]
+        fn lengths_in_one_unit_stay_in_that_unit(
+            from in -1000.0..1000.0f64,
+            to in -1000.0..1000.0f64,
+            progress in 0.0..=1.0f64,
+        ) {
+            let value = CssLength::Rem(from).tween(&CssLength::Rem(to), progress);
+            prop_assert_eq!(value.unit_value().map(|(_, unit)| unit), Some(LengthUnit::Rem));
+        }
+
+        #[test]
+        fn offsets_add_back_up(a in length(), b in length()) {
+            prop_assert_eq!((a + b - b).terms(), a.terms());
+        }
+
+        #[test]
+        fn lengths_write_valid_css(from in length(), to in length(), progress in 0.0..=1.0f64) {
+            let css = from.tween(&to, progress).to_css();
+            prop_assert!(!css.is_empty() && css != "calc()", "{css}");
+            prop_assert!(!css.contains("NaN"));
+        }
+    }
+}
@@ -1,28 +1,119 @@
 use instant::Instant;
 use std::cmp::PartialEq;
+#[cfg(not(feature = "disable-animations"))]
+use std::collections::VecDeque;
+#[cfg(feature = "disable-animations")]
+use std::marker::PhantomData;
 use std::ops::{Add, Deref, Mul};
-use std::{collections::VecDeque, ops::Sub, time::Duration};
+#[cfg(not(feature = "disable-animations"))]
+use std::panic::Location;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::{ops::Sub, time::Duration};
 
+use analytics::StatsState;
+use animation_target::{AnimationSource, OnComplete};
+#[cfg(not(feature = "disable-animations"))]
+use budget::AnimationBudget;
+use clock::{Clock, SteadyTime};
+#[cfg(not(feature = "disable-animations"))]
+use coalescing::UpdatePolicy;
+#[cfg(not(feature = "disable-animations"))]
+use composition::Composition;
+#[cfg(not(feature = "disable-animations"))]
+use config::ConfigureFn;
+#[cfg(not(feature = "disable-animations"))]
+use frame_rate::FrameInterval;
 use leptos::prelude::*;
+use quality::{QualityState, QualityTier};
+use random::{Randomness, Rng};
+use scheduler::{CallbackScheduler, Scheduler};
+#[cfg(not(feature = "disable-animations"))]
+use time::saturating_mul_f64;
+#[cfg(not(feature = "disable-animations"))]
+use watchdog::{AnimationWatchdog, Watch};
 
+mod accessibility;
+pub mod analytics;
+pub mod angle;
 pub mod animation_target;
+pub mod budget;
+mod clock;
+pub mod coalescing;
+pub mod color;
+pub mod combine;
+#[cfg(feature = "compat")]
+mod compat;
+pub mod composition;
+mod config;
+mod coordinator;
+pub mod css;
+pub mod css_var;
+pub mod ducking;
 pub mod easing;
+pub mod fallible;
+pub mod fields;
+mod finished;
+pub mod follow;
+mod frame_rate;
+pub mod gradient;
+#[cfg(feature = "inspector")]
+pub mod inspector;
+pub mod length;
+pub mod monotonic;
+pub mod number;
+#[cfg(feature = "disable-animations")]
+mod pass_through;
+pub mod path;
+mod phase;
+pub mod property;
+pub mod quality;
+pub mod quat;
+pub mod random;
+pub mod rect;
+mod render;
+pub mod scenario;
+pub mod scheduler;
+pub mod series;
+mod shimmer;
+pub mod space;
+pub mod sprite;
+pub mod style;
+pub mod testing;
+pub mod text;
+pub mod threshold;
+pub mod tilt;
+pub mod time;
+pub mod timeline;
+pub mod tokens;
+pub mod transform;
+pub mod tween;
+pub mod variants;
+pub mod watchdog;
+mod window;
+pub mod worker;
+pub mod wrapping;
+
+pub use clock::DEFAULT_MAX_FRAME_DELTA;
+#[cfg(feature = "compat")]
+#[allow(deprecated)]
+pub use compat::*;
 
 #[derive(Clone)]
 enum AnimationContextState {
     NoAnimationFrameRequested,
-    AnimationFrameRequested(AnimationFrameRequestHandle),
-    CustomAnimationFrameRequested,
+    AnimationFrameRequested,
+    /// A frame was requested that is not needed before the given real time
+    FrameRequestedAt(Instant),
 }
 
 /// The `AnimationContext` handles updating all animated values and calls to `window.request_animation_frame()`.
-/// It is required to provide one in a parent context before calling [`create_animated_signal()`]
+/// It is required to provide one in a parent context before calling [`AnimatedSignal::new()`]
 /// ```
-/// # use leptos::*;
+/// # use leptos::prelude::*;
 /// # use leptos_animation::AnimationContext;
-/// # let runtime = create_runtime();
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
 ///  AnimationContext::provide();
-/// # runtime.dispose();
 /// ```
 #[derive(Copy, Clone)]
 pub struct AnimationContext {
@@ -30,33 +121,109 @@ pub struct AnimationContext {
     /// the `window.request_animation_frame()` callback. It is not necessary to notify or track
     /// this trigger yourself, it will happen automatically when animated signals exist.
     pub animation_frame: Trigger,
+    /// The number of times `animation_frame` was notified, so animated signals can tell the runs
+    /// of their tick in a new frame apart from the runs caused by changes of their source
+    frame: StoredValue<u64>,
+    epoch: Instant,
+    clock: StoredValue<Clock>,
+    steady_time: StoredValue<SteadyTime>,
+    /// Replaces the real time while a [`TestAnimationHarness`](testing::TestAnimationHarness) drives the clock
+    manual_time: StoredValue<Option<Instant>>,
     state: StoredValue<AnimationContextState>,
-    custom_request_animation_frame: StoredValue<Option<Box<dyn Fn()>>, LocalStorage>,
+    animating: StoredValue<bool>,
+    idle_callbacks: StoredValue<Vec<Box<dyn FnOnce()>>, LocalStorage>,
+    quality: StoredValue<QualityState>,
+    quality_tier: RwSignal<QualityTier>,
+    randomness: StoredValue<(Randomness, Rng)>,
+    stats: StoredValue<StatsState>,
+    #[cfg(feature = "inspector")]
+    inspector: inspector::Inspector,
+    scheduler: StoredValue<Box<dyn Scheduler>, LocalStorage>,
+    window: StoredValue<Option<web_sys::Window>, LocalStorage>,
+    visible: RwSignal<bool>,
 }
 impl AnimationContext {
     /// Sets up an AnimationContext for this scope and all child scopes. For normal use you only
-    /// need to call this once in a root component of the application./// Sets up an AnimationContext for this scope and all child scopes. For normal use you only
     /// need to call this once in a root component of the application.
+    ///
+    /// When using islands every island has to provide its own context. All contexts on a page share
+    /// a single animation frame loop, so this does not result in one loop per island.
     pub fn provide() -> AnimationContext {
+        Self::provide_in(None, scheduler::default_scheduler())
+    }
+
+    /// Sets up an AnimationContext for this scope and all child scopes that uses a custom
+    /// [`Scheduler`] to decide when animation frames happen, see the [`scheduler`] module.
+    pub fn provide_with_scheduler(scheduler: impl Scheduler) -> AnimationContext {
+        Self::provide_in(None, Box::new(scheduler))
+    }
+
+    /// Sets up an AnimationContext whose animations are shown in `window`, or in the window of
+    /// the application when `None`
+    fn provide_in(
+        window: Option<web_sys::Window>,
+        scheduler: Box<dyn Scheduler>,
+    ) -> AnimationContext {
         let animation_frame = Trigger::new();
+        let scheduler = StoredValue::new_local(scheduler);
         let state = StoredValue::new(AnimationContextState::NoAnimationFrameRequested);
+        let epoch = Instant::now();
 
         let animation_context = AnimationContext {
             animation_frame,
+            frame: StoredValue::new(0),
+            epoch,
+            clock: StoredValue::new(Clock::new(epoch)),
+            steady_time: StoredValue::new(SteadyTime::new(epoch)),
+            manual_time: StoredValue::new(None),
             state,
-            custom_request_animation_frame: StoredValue::new_local(None),
+            animating: StoredValue::new(false),
+            idle_callbacks: StoredValue::new_local(Vec::new()),
+            quality: StoredValue::new(QualityState::default()),
+            quality_tier: RwSignal::new(QualityTier::High),
+            randomness: {
+                let randomness = Randomness::default();
+                StoredValue::new((randomness, randomness.generator(0)))
+            },
+            stats: StoredValue::new(StatsState::default()),
+            #[cfg(feature = "inspector")]
+            inspector: inspector::Inspector::new(),
+            scheduler,
+            window: StoredValue::new_local(window),
+            visible: RwSignal::new(true),
         };
         provide_context(animation_context);
+        animation_context.track_visibility();
 
         on_cleanup(move || {
-            if let AnimationContextState::AnimationFrameRequested(handle) = state.get_value() {
-                handle.cancel()
+            if let Some(
+                AnimationContextState::AnimationFrameRequested
+                | AnimationContextState::FrameRequestedAt(_),
+            ) = state.try_get_value()
+            {
+                scheduler.try_with_value(|scheduler| scheduler.cancel_frame());
             }
         });
 
         animation_context
     }
 
+    /// Returns the AnimationContext of a parent scope, or provides a new one if there is none.
+    ///
+    /// This is useful in components that can be used both inside an island and as the root of
+    /// an island, where it is not known up front whether a context has already been provided.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::AnimationContext;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = AnimationContext::use_or_provide();
+    /// // A second call finds the context provided by the first one
+    /// let same_context = AnimationContext::use_or_provide();
+    /// ```
+    pub fn use_or_provide() -> AnimationContext {
+        use_context().unwrap_or_else(Self::provide)
+    }
+
     /// This method can be used instead of `provide` when you are in a non-web environment such as
     /// a desktop application. *For web environments it is recommended to use the normal `provide` instead*
     ///
@@ -74,12 +241,12 @@ impl AnimationContext {
     ///
     /// ````
     /// # // Lots of boilerplate to simulate winit environment
-    /// # use leptos::create_runtime;
+    /// # use leptos::prelude::*;
     /// # use leptos_animation::AnimationContext;
     /// # struct Window {}
     /// # impl Window { fn request_redraw(&self) {} }
     /// # let window = Window {};
-    /// # let runtime = create_runtime();
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
     /// # struct EventLoop {};
     /// # impl EventLoop { fn run(&self, f: impl Fn(Event, ())) {} }
     /// # let event_loop = EventLoop {};
@@ -99,26 +266,12 @@ impl AnimationContext {
     /// });
     ///
     /// ````
-
     pub fn provide_with_custom_request_animation_frame(
         callback: impl Fn() + 'static,
     ) -> (AnimationContext, impl Fn()) {
-        let animation_context = Self::provide();
-        animation_context
-            .custom_request_animation_frame
-            .set_value(Some(Box::new(callback)));
-
-        (animation_context, move || {
-            if !matches!(
-                animation_context.state.get_value(),
-                AnimationContextState::NoAnimationFrameRequested
-            ) {
-                animation_context
-                    .state
-                    .set_value(AnimationContextState::NoAnimationFrameRequested);
-                animation_context.animation_frame.notify();
-            }
-        })
+        let scheduler = CallbackScheduler::new(callback);
+        let on_frame = scheduler.on_frame();
+        (Self::provide_with_scheduler(scheduler), on_frame)
     }
 
     /// Manually request a new animation frame. It will result in a `notify()` on the
@@ -128,90 +281,139 @@ impl AnimationContext {
     /// Animated signals will call this automatically when they are running, it is not necessary
     /// to call this function unless you are doing something custom.
     pub fn request_animation_frame(&self) {
+        self.animating.set_value(true);
+        self.request_frame();
+    }
+
+    /// Schedules non-urgent work to run on an animation frame in which no animations are running.
+    /// If animations are currently playing the callback is deferred until all of them have
+    /// settled, so heavy work does not compete with the animation loop for CPU time.
+    ///
+    /// The callback is called once, outside of the reactive ownership tree.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::AnimationContext;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = expect_context::<AnimationContext>();
+    /// context.on_idle(|| {
+    ///     // Rebuild the search index once the page transition has finished
+    /// });
+    /// ```
+    pub fn on_idle(&self, callback: impl FnOnce() + 'static) {
+        self.idle_callbacks
+            .update_value(|callbacks| callbacks.push(Box::new(callback)));
+        self.request_frame();
+    }
+
+    /// Synchronously brings all animated values up to date, without waiting for the next animation frame.
+    ///
+    /// Animated signals and everything derived from them normally update once per animation frame, so
+    /// between frames memos and other derived values still hold the values of the previous frame. Call
+    /// this at the start of a critical input event (like `pointerdown`) so that for example hit-testing
+    /// against animated positions uses the values at the exact time of the event.
+    ///
+    /// This does not affect the regular animation frame loop.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::AnimationContext;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = AnimationContext::provide();
+    /// # let _html =
+    /// view! {
+    ///     <canvas on:pointerdown=move |event| {
+    ///         context.flush_now();
+    ///         // Hit test against the animated shapes
+    ///     }/>
+    /// }
+    /// # .to_html();
+    /// ```
+    pub fn flush_now(&self) {
+        self.notify_frame();
+    }
+
+    /// Updates all animated signals, as at the start of a new frame
+    fn notify_frame(&self) {
+        self.frame
+            .update_value(|frame| *frame = frame.wrapping_add(1));
+        self.animation_frame.notify();
+    }
+
+    /// Requests an animation frame without marking this frame as one in which animations run
+    fn request_frame(&self) {
         // Prevent multiple animation frame requests from existing simultaneously
-        if matches!(
-            self.state.get_value(),
-            AnimationContextState::NoAnimationFrameRequested
-        ) {
-            self.custom_request_animation_frame
-                .with_value(
-                    |custom_request_animation_frame| match custom_request_animation_frame {
-                        None => {
-                            let this = self.clone();
-                            self.state
-                                .set_value(AnimationContextState::AnimationFrameRequested(
-                                    request_animation_frame_with_handle(move || {
-                                        this.state.set_value(
-                                            AnimationContextState::NoAnimationFrameRequested,
-                                        );
-                                        this.animation_frame.notify();
-                                    })
-                                    .unwrap(),
-                                ))
-                        }
-                        Some(callback) => {
-                            self.state
-                                .set_value(AnimationContextState::CustomAnimationFrameRequested);
-                            callback()
-                        }
-                    },
-                );
+        let state = self.state.get_value();
+        if let AnimationContextState::FrameRequestedAt(_) = state {
+            // The frame is needed now, not later
+            self.scheduler
+                .with_value(|scheduler| scheduler.cancel_frame());
+        }
+        if !matches!(state, AnimationContextState::AnimationFrameRequested) {
+            let this = *self;
+            self.state
+                .set_value(AnimationContextState::AnimationFrameRequested);
+            self.count_frame_request();
+            self.scheduler.with_value(|scheduler| {
+                scheduler.request_frame(Box::new(move || this.on_animation_frame()))
+            });
         }
     }
-}
 
-/// Sets up an AnimationContext for this scope and all child scopes. For normal use you only
-/// need to call this once in a root component of the application.
-pub fn provide_animation_context() -> AnimationContext {
-    AnimationContext::provide()
-}
+    /// Called when a requested animation frame has arrived
+    fn on_animation_frame(&self) {
+        self.state
+            .set_value(AnimationContextState::NoAnimationFrameRequested);
 
-/// This method can be used instead of `provide` when you are in a non-web environment such as
-/// a desktop application. *For web environments it is recommended to use the normal `provide` instead*
-///
-/// There are two extra callbacks that have to be correctly called and implemented in order
-/// for this library to correctly function.
-///
-/// The callback given in the argument has to call some function that triggers an animation frame
-/// request. For example, in the `winit` crate this would be calling [`Window::request_redraw()`](https://docs.rs/winit/latest/winit/window/struct.Window.html#method.request_redraw).
-/// This callback will be called at most once per animation frame.
-///
-/// The callback returned from this function should be called when the animation frame from the
-/// previous callback has arrived.
-/// For example, in the `winit` crate this should be called when the [`WindowEvent::RedrawRequested`](https://docs.rs/winit/latest/winit/event/enum.WindowEvent.html#variant.RedrawRequested) event happens
-/// Extraneous calls to this callback are ignored.
-///
-/// ````
-/// # // Lots of boilerplate to simulate winit environment
-/// # use leptos::create_runtime;
-/// # use leptos_animation::AnimationContext;
-/// # struct Window {}
-/// # impl Window { fn request_redraw(&self) {} }
-/// # let window = Window {};
-/// # let runtime = create_runtime();
-/// # struct EventLoop {};
-/// # impl EventLoop { fn run(&self, f: impl Fn(Event, ())) {} }
-/// # let event_loop = EventLoop {};
-/// # enum WindowEvent { RedrawRequested }
-/// # enum Event { WindowEvent { event: WindowEvent}, Other }
-/// let (_, on_redraw_requested) =
-///         provide_animation_context_with_custom_request_animation_frame(move || {
-///             window.request_redraw();
-///         });
-///
-/// event_loop.run(move |event, elwt| match event {
-///         Event::WindowEvent {
-///             event: WindowEvent::RedrawRequested,
-///             ..
-///         } => on_redraw_requested(),
-///         _ => {}
-/// });
-///
-/// ````
-pub fn provide_animation_context_with_custom_request_animation_frame(
-    callback: impl Fn() + 'static,
-) -> (AnimationContext, impl Fn()) {
-    AnimationContext::provide_with_custom_request_animation_frame(callback)
+        // Animations request a new frame while they are being evaluated, so if no animation asked
+        // for this frame there is nothing running and it is safe to run the idle callbacks. Nothing
+        // moves while paused or hidden either, so they also run then.
+        let running = self.clock.with_value(Clock::is_running);
+        let was_animating = self.animating.get_value();
+        if was_animating && running {
+            if self
+                .idle_callbacks
+                .with_value(|callbacks| !callbacks.is_empty())
+            {
+                self.request_frame();
+            }
+        } else {
+            let callbacks = self.idle_callbacks.try_update_value(std::mem::take);
+            for callback in callbacks.into_iter().flatten() {
+                callback();
+            }
+        }
+
+        // Nothing moves while paused or hidden, the loop continues once the clock runs again
+        if !running {
+            return;
+        }
+
+        let real_now = self.real_now();
+        self.steady_time
+            .update_value(|steady_time| steady_time.frame(real_now));
+        self.animating.set_value(false);
+        let consecutive = was_animating && !self.take_resumed();
+        self.record_frame(consecutive);
+        self.count_dropped_frames(consecutive);
+        #[cfg(feature = "inspector")]
+        self.inspector.begin_frame();
+        self.notify_frame();
+    }
+
+    /// The current time as seen by the animations of this context
+    fn now(&self) -> Instant {
+        let real_now = self.steady_now();
+        self.clock
+            .try_with_value(|clock| clock.now(real_now))
+            .unwrap_or(real_now)
+    }
+
+    /// The time the clock follows, which is the real time unless a test drives it manually
+    pub(crate) fn real_now(&self) -> Instant {
+        self.manual_time
+            .try_get_value()
+            .flatten()
+            .unwrap_or_else(Instant::now)
+    }
 }
 
 /// An `AnimationTarget` is a target value for the animation system to ease towards to along with
@@ -232,13 +434,35 @@ pub fn provide_animation_context_with_custom_request_animation_frame(
 /// let _: AnimationTarget<u32> = (42, Duration::from_secs_f64(1.5)).into();
 /// ```
 ///
+/// A second duration after the first one is the delay before the animation starts:
+/// ```
+/// # use std::time::Duration;
+/// # use leptos_animation::{AnimationTarget, easing};
+/// // Animate for 1.5 seconds after waiting for 0.2 seconds
+/// let _: AnimationTarget<u32> = (42, Duration::from_secs_f64(1.5), Duration::from_secs_f64(0.2), easing::CUBIC_OUT).into();
+/// ```
+///
 /// If you want to use all the default animation options you can call `into()` directly on a target value:
 /// ```
 /// # use std::time::Duration;
 /// # use leptos_animation::AnimationTarget;
 /// let _: AnimationTarget<u32> = 42.into();
 /// ```
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+///
+/// Struct literals take the options they don't set from `..Default::default()`, or from
+/// `..AnimationTarget::from(target)` for values without a default, so they keep compiling when
+/// options are added:
+/// ```
+/// # use std::time::Duration;
+/// # use leptos_animation::{AnimationMode, AnimationTarget};
+/// let _: AnimationTarget<u32> = AnimationTarget {
+///     target: 42,
+///     duration: Duration::from_secs_f64(1.5),
+///     mode: AnimationMode::ReplaceOrStart,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug)]
 pub struct AnimationTarget<T> {
     /// The final value to animate towards to
     pub target: T,
@@ -249,10 +473,89 @@ pub struct AnimationTarget<T> {
     /// The easing method to apply during the animation. Defaults to [`SINE_OUT`](easing::SINE_OUT)
     pub easing: Easing,
 
+    /// The time to wait before the animation starts, during which the value stays where it was.
+    /// Defaults to no delay
+    pub delay: Duration,
+
     /// The mode specifies how to deal with running animation. Defaults to [`Start`](AnimationMode::Start).
     /// This can be used to add, overwrite or cancel running animations.
     /// See [`AnimationMode`] for more information
     pub mode: AnimationMode,
+
+    /// Values to pass through on the way to the target, turning a single animation into a
+    /// multi-stage one. Defaults to none, animating directly towards the target.
+    /// See [`Keyframe`] for more information
+    pub keyframes: Vec<Keyframe<T>>,
+
+    /// How often the animation plays, starting over from the beginning every time it reaches the
+    /// target. Defaults to playing once. See [`RepeatMode`] for more information
+    pub repeat: RepeatMode,
+
+    /// A callback that runs once the animation started for this target has finished. Defaults to
+    /// none, see [`AnimationTarget::on_complete()`]
+    pub on_complete: Option<OnComplete>,
+
+    /// Makes `target` and the keyframe values relative: when the target is applied, each of them
+    /// is combined with the value that the signal settles on by this function. Defaults to none,
+    /// see [`AnimationTarget::relative()`]
+    pub relative: Option<fn(&T, &T) -> T>,
+}
+
+impl<T: PartialEq> PartialEq for AnimationTarget<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.target == other.target
+            && self.duration == other.duration
+            && self.easing == other.easing
+            && self.delay == other.delay
+            && self.mode == other.mode
+            && self.keyframes == other.keyframes
+            && self.repeat == other.repeat
+            && match (self.relative, other.relative) {
+                (Some(a), Some(b)) => std::ptr::fn_addr_eq(a, b),
+                (a, b) => a.is_none() && b.is_none(),
+            }
+    }
+}
+
+impl<T: Eq> Eq for AnimationTarget<T> {}
+
+/// A value that an animation passes through at a given point of its duration.
+///
+/// The keyframes of an [`AnimationTarget`] split its animation into segments: from the starting
+/// value to the first keyframe, from keyframe to keyframe and from the last keyframe to the target.
+/// Every segment has its own easing, the easing of a keyframe applies to the segment that ends at
+/// it and the easing of the target to the last segment.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (scale, set_scale) = signal(1.0);
+///
+/// // Overshoot to 20% above the target at 60% of the duration before settling
+/// let animated_scale = AnimatedSignal::new(
+///     move || {
+///         let scale = scale.get();
+///         AnimationTarget::from((scale, easing::QUAD_IN_OUT)).keyframe(0.6, scale * 1.2, easing::CUBIC_OUT)
+///     },
+///     tween_default,
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Keyframe<T> {
+    /// The point in the duration of the animation at which the value is reached, from 0.0 to 1.0
+    pub offset: f64,
+
+    /// The value to pass through
+    pub value: T,
+
+    /// The easing of the segment that ends at this keyframe
+    pub easing: Easing,
+}
+
+impl<T: PartialEq> PartialEq for Keyframe<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset && self.value == other.value && self.easing == other.easing
+    }
 }
 
 /// The `AnimationMode` specifies how to handle new animation target values with respect to currently running animations
@@ -271,11 +574,220 @@ pub enum AnimationMode {
 
     /// Cancels any previous animation and sets the output directly to the target value
     Snap,
+
+    /// Start a new animation that sets off from rest, so the motion continues without a visible
+    /// hitch when a running animation is retargeted. The running animations keep their current
+    /// velocity and settle additively, while the new animation eases in from zero velocity even
+    /// if its easing starts abruptly, such as an ease-out.
+    ReplaceWithVelocity,
+}
+
+/// The `RepeatMode` specifies how often an animation plays. Every repetition starts over from the
+/// value the animation started from, which makes looping animations like pulses and spinners
+/// possible without a source that retriggers itself. Alternating repetitions reverse the direction
+/// of every other repetition instead, for effects that breathe or glow.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (loading, set_loading) = signal(false);
+///
+/// // Spin a loading indicator for as long as it is loading
+/// let rotation: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+///     move || match loading.get() {
+///         true => AnimationTarget::from((360.0, Duration::from_secs(1), easing::LINEAR))
+///             .repeat(RepeatMode::Infinite),
+///         false => (0.0, AnimationMode::Snap).into(),
+///     },
+///     tween_default,
+/// );
+/// set_loading.set(true);
+/// ```
+///
+/// A glow that slowly breathes in and out once it is switched on:
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (intensity, set_intensity) = signal(0.0);
+/// let glow: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+///     move || AnimationTarget::from((intensity.get(), Duration::from_secs(2), easing::SINE_IN_OUT))
+///         .repeat(RepeatMode::AlternateInfinite),
+///     tween_default,
+/// );
+/// set_intensity.set(1.0);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RepeatMode {
+    /// Play the animation this many times in total. `Count(1)` plays it once, this is the default
+    Count(u32),
+
+    /// Repeat the animation until it is replaced or a new animation is started on top of it
+    Infinite,
+
+    /// Play the animation this many times in total, going back towards the start on every
+    /// second repetition. An even count is rounded up to the next odd count, so the animation
+    /// always comes to rest at the target instead of jumping to it from where it started.
+    Alternate(u32),
+
+    /// Play the animation back and forth until it is replaced or a new animation is started on
+    /// top of it
+    AlternateInfinite,
+}
+
+impl RepeatMode {
+    /// The number of repetitions, `None` if the animation repeats infinitely
+    pub(crate) fn count(self) -> Option<u32> {
+        match self {
+            RepeatMode::Count(count) => Some(count.max(1)),
+            // The last repetition has to play forwards to end at the target
+            RepeatMode::Alternate(count) => Some(count.max(1) | 1),
+            RepeatMode::Infinite | RepeatMode::AlternateInfinite => None,
+        }
+    }
+
+    /// Whether every second repetition plays backwards
+    pub(crate) fn alternates(self) -> bool {
+        matches!(
+            self,
+            RepeatMode::Alternate(_) | RepeatMode::AlternateInfinite
+        )
+    }
+}
+
+impl Default for RepeatMode {
+    fn default() -> Self {
+        RepeatMode::Count(1)
+    }
 }
 
 /// An easing function is one that takes a value between 0.0 - 1.0 and maps it to another value between 0.0 and 1.0
 /// See `https://easings.net` for a list of implemented functions
-pub type Easing = fn(f64) -> f64;
+///
+/// An easing wraps either a plain function, like the predefined easings of the [`easing`] module,
+/// or a closure, so easings can be parameterized at runtime, for example with control points
+/// chosen by the user. Clones of an easing share the same closure and are cheap.
+///
+/// An easing can also supply its derivative, the rate at which the eased progress changes. The
+/// predefined easings have analytic derivatives, for other easings it is measured numerically.
+/// [`AnimatedSignal::velocity()`] and animations that start from rest use it.
+/// ```
+/// # use leptos_animation::{easing, Easing};
+/// let strength = 2.5;
+/// let custom = Easing::new(move |t| t.powf(strength));
+/// assert_eq!(custom.ease(1.0), 1.0);
+///
+/// let from_fn: Easing = (|t: f64| t * t).into();
+/// assert_eq!(from_fn.ease(0.5), 0.25);
+/// assert_eq!(easing::LINEAR.ease(0.5), 0.5);
+///
+/// // Measured numerically unless the derivative is supplied
+/// assert!((from_fn.derivative(0.5) - 1.0).abs() < 1e-6);
+/// let exact = Easing::new(move |t| t.powf(strength))
+///     .with_derivative(move |t| strength * t.powf(strength - 1.0));
+/// assert_eq!(exact.derivative(1.0), 2.5);
+/// assert_eq!(easing::QUAD_IN.derivative(0.5), 1.0);
+/// ```
+#[derive(Clone)]
+pub struct Easing {
+    ease: EasingFn,
+    derivative: Option<EasingFn>,
+}
+
+#[derive(Clone)]
+enum EasingFn {
+    Fn(fn(f64) -> f64),
+    Closure(Arc<dyn Fn(f64) -> f64 + Send + Sync>),
+}
+
+impl EasingFn {
+    fn call(&self, t: f64) -> f64 {
+        match self {
+            EasingFn::Fn(f) => f(t),
+            EasingFn::Closure(f) => f(t),
+        }
+    }
+}
+
+impl Easing {
+    /// Wraps a plain function, which is possible in constants
+    pub const fn from_fn(easing: fn(f64) -> f64) -> Easing {
+        Easing {
+            ease: EasingFn::Fn(easing),
+            derivative: None,
+        }
+    }
+
+    /// Wraps a plain function together with its derivative, which is possible in constants
+    pub const fn from_fns(easing: fn(f64) -> f64, derivative: fn(f64) -> f64) -> Easing {
+        Easing {
+            ease: EasingFn::Fn(easing),
+            derivative: Some(EasingFn::Fn(derivative)),
+        }
+    }
+
+    /// Wraps a closure
+    pub fn new(easing: impl Fn(f64) -> f64 + Send + Sync + 'static) -> Easing {
+        Easing {
+            ease: EasingFn::Closure(Arc::new(easing)),
+            derivative: None,
+        }
+    }
+
+    /// Supplies the derivative of the easing, replacing the numeric measurement
+    pub fn with_derivative(
+        self,
+        derivative: impl Fn(f64) -> f64 + Send + Sync + 'static,
+    ) -> Easing {
+        Easing {
+            derivative: Some(EasingFn::Closure(Arc::new(derivative))),
+            ..self
+        }
+    }
+
+    /// Maps the linear progress `t` to the eased progress
+    pub fn ease(&self, t: f64) -> f64 {
+        self.ease.call(t)
+    }
+
+    /// The rate of change of the eased progress at the linear progress `t`. Without an analytic
+    /// derivative it is measured with a central difference within 0.0 - 1.0.
+    pub fn derivative(&self, t: f64) -> f64 {
+        if let Some(derivative) = &self.derivative {
+            return derivative.call(t);
+        }
+        let t = t.clamp(0.0, 1.0);
+        let before = (t - DERIVATIVE_STEP).max(0.0);
+        let after = (t + DERIVATIVE_STEP).min(1.0);
+        (self.ease(after) - self.ease(before)) / (after - before)
+    }
+}
+
+impl<F: Fn(f64) -> f64 + Send + Sync + 'static> From<F> for Easing {
+    fn from(easing: F) -> Easing {
+        Easing::new(easing)
+    }
+}
+
+/// Easings are equal if they are the same function or clones of the same closure, regardless of
+/// their derivatives
+impl PartialEq for Easing {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.ease, &other.ease) {
+            (EasingFn::Fn(a), EasingFn::Fn(b)) => std::ptr::fn_addr_eq(*a, *b),
+            (EasingFn::Closure(a), EasingFn::Closure(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Debug for Easing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Easing")
+    }
+}
 
 struct Animation<T, I> {
     from: T,
@@ -284,18 +796,226 @@ struct Animation<T, I> {
     start: Instant,
     duration: Duration,
     easing: Easing,
+    keyframes: Vec<Keyframe<T>>,
+    repeat: RepeatMode,
+    /// Whether the animation starts with zero velocity regardless of its easing
+    from_rest: bool,
+    #[cfg(not(feature = "disable-animations"))]
+    on_complete: Option<OnComplete>,
 }
 
 impl<T, I> Animation<T, I> {
-    fn is_finished(&self) -> bool {
-        Instant::now() > self.start + self.duration
+    /// The duration of all repetitions together, `None` if the animation repeats infinitely
+    fn total_duration(&self) -> Option<Duration> {
+        self.repeat
+            .count()
+            .map(|count| self.duration.saturating_mul(count))
+    }
+
+    fn is_finished(&self, now: Instant) -> bool {
+        match self.total_duration() {
+            Some(total_duration) => self
+                .start
+                .checked_add(total_duration)
+                .is_some_and(|end| now > end),
+            None => false,
+        }
+    }
+
+    /// The progress of the current repetition without easing applied, clamped to 0.0 - 1.0
+    fn linear_progress(&self, now: Instant) -> f64 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        let elapsed = now.saturating_duration_since(self.start);
+        let mut repetitions = elapsed.as_secs_f64() / self.duration.as_secs_f64();
+        let mut progress = repetitions.fract();
+        if let Some(count) = self.repeat.count() {
+            if repetitions >= count as f64 {
+                // Stay at the end of the last repetition
+                repetitions = count as f64 - 1.0;
+                progress = 1.0;
+            }
+        }
+        if self.repeat.alternates() && repetitions as u64 % 2 == 1 {
+            1.0 - progress
+        } else {
+            progress
+        }
+    }
+
+    fn progress(&self, now: Instant) -> f64 {
+        self.ease(&self.easing, 0.0, self.linear_progress(now))
+    }
+
+    /// Applies `easing` to the progress within the segment that starts at offset `start`
+    fn ease(&self, easing: &Easing, start: f64, progress: f64) -> f64 {
+        let eased = easing.ease(progress);
+        if !self.from_rest || start > 0.0 {
+            return eased;
+        }
+        // Cancel the initial slope of the easing with a term that vanishes at both ends
+        eased - initial_slope(easing) * progress * (1.0 - progress) * (1.0 - progress)
+    }
+
+    /// The derivative of [`ease()`](Animation::ease) with respect to the progress
+    #[cfg(not(feature = "disable-animations"))]
+    fn ease_derivative(&self, easing: &Easing, start: f64, progress: f64) -> f64 {
+        let derivative = easing.derivative(progress);
+        if !self.from_rest || start > 0.0 {
+            return derivative;
+        }
+        derivative - initial_slope(easing) * (1.0 - progress) * (1.0 - 3.0 * progress)
+    }
+
+    /// The segment between two keyframes that the linear `progress` is in, as its start and end
+    /// values, its easing and its start and end offsets
+    fn segment(&self, progress: f64) -> (&T, &T, &Easing, f64, f64) {
+        let mut from = (0.0, &self.from);
+        for keyframe in &self.keyframes {
+            if progress < keyframe.offset {
+                return (
+                    from.1,
+                    &keyframe.value,
+                    &keyframe.easing,
+                    from.0,
+                    keyframe.offset,
+                );
+            }
+            from = (keyframe.offset, &keyframe.value);
+        }
+        (from.1, &self.to, &self.easing, from.0, 1.0)
+    }
+
+    /// The tweened value at time `now`, passing through the keyframes
+    fn value(&self, tween: &dyn Fn(&T, &T, f64) -> I, now: Instant) -> I {
+        if self.keyframes.is_empty() {
+            return tween(&self.from, &self.to, self.progress(now));
+        }
+
+        let progress = self.linear_progress(now);
+        let (from, to, easing, start, end) = self.segment(progress);
+        let segment = segment_progress(start, end, progress);
+        tween(from, to, self.ease(easing, start, segment))
+    }
+
+    /// Whether the animation is still waiting for its delay to pass
+    #[cfg(not(feature = "disable-animations"))]
+    fn is_pending(&self, now: Instant) -> bool {
+        now < self.start
+    }
+
+    /// Scales the duration by `factor` while keeping the current progress
+    #[cfg(not(feature = "disable-animations"))]
+    fn retime(&mut self, now: Instant, factor: f64) {
+        if self.is_pending(now) {
+            self.duration = saturating_mul_f64(self.duration, factor);
+            return;
+        }
+        // Scaling the elapsed time keeps both the progress and the current repetition
+        let elapsed = match self.total_duration() {
+            Some(total_duration) => now
+                .saturating_duration_since(self.start)
+                .min(total_duration),
+            None => now.saturating_duration_since(self.start),
+        };
+        self.duration = saturating_mul_f64(self.duration, factor);
+        self.start = now
+            .checked_sub(saturating_mul_f64(elapsed, factor))
+            .unwrap_or(self.start);
+    }
+
+    /// Moves the start to the beginning of the current repetition
+    #[cfg(not(feature = "disable-animations"))]
+    fn restart_repetition(&mut self, now: Instant) {
+        if self.duration.is_zero() || self.is_pending(now) {
+            return;
+        }
+        let elapsed = now.saturating_duration_since(self.start);
+        let mut repetitions = (elapsed.as_secs_f64() / self.duration.as_secs_f64()).floor();
+        // Keep the direction of alternating repetitions
+        if self.repeat.alternates() && repetitions as u64 % 2 == 1 {
+            repetitions -= 1.0;
+        }
+        if let Some(total_duration) = self.total_duration() {
+            if elapsed >= total_duration {
+                return;
+            }
+        }
+        self.start += saturating_mul_f64(self.duration, repetitions);
     }
 
-    fn progress(&self) -> f64 {
-        (self.easing)((Instant::now() - self.start).as_secs_f64() / self.duration.as_secs_f64())
+    /// Moves the start so that `position` has elapsed since it
+    #[cfg(not(feature = "disable-animations"))]
+    fn seek(&mut self, now: Instant, position: Duration) {
+        self.start = now.checked_sub(position).unwrap_or(self.start);
+    }
+
+    /// The time until the animation finishes, `None` if it repeats infinitely
+    #[cfg(not(feature = "disable-animations"))]
+    fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.total_duration().map(|total_duration| {
+            self.start
+                .checked_add(total_duration)
+                .map_or(Duration::MAX, |end| end.saturating_duration_since(now))
+        })
     }
 }
 
+#[cfg(not(feature = "disable-animations"))]
+impl<T, I: Sub<I, Output = I> + Mul<f64, Output = I>> Animation<T, I> {
+    /// The rate of change of the tweened value at time `now` per second, `None` while the value
+    /// does not change. The easing is differentiated exactly and only the tween is measured.
+    fn velocity(&self, tween: &dyn Fn(&T, &T, f64) -> I, now: Instant) -> Option<I> {
+        if self.duration.is_zero() || self.is_pending(now) || self.is_finished(now) {
+            return None;
+        }
+        let progress = self.linear_progress(now);
+        let (from, to, easing, start, end) = self.segment(progress);
+        if end <= start {
+            return None;
+        }
+        let repetitions =
+            now.saturating_duration_since(self.start).as_secs_f64() / self.duration.as_secs_f64();
+        let direction = match self.repeat.alternates() && repetitions as u64 % 2 == 1 {
+            true => -1.0,
+            false => 1.0,
+        };
+        let segment = segment_progress(start, end, progress);
+        let rate = direction / self.duration.as_secs_f64() / (end - start)
+            * self.ease_derivative(easing, start, segment);
+        if rate == 0.0 || !rate.is_finite() {
+            return None;
+        }
+        let eased = self.ease(easing, start, segment);
+        let slope = (tween(from, to, eased) - tween(from, to, eased - DERIVATIVE_STEP))
+            * (1.0 / DERIVATIVE_STEP);
+        Some(slope * rate)
+    }
+}
+
+/// The slope of `easing` at the start, which animations that start from rest cancel
+fn initial_slope(easing: &Easing) -> f64 {
+    match easing.derivative(0.0) {
+        slope if slope.is_finite() => slope,
+        // Easings such as circular ones start vertically, use the slope over the first step
+        _ => (easing.ease(DERIVATIVE_STEP) - easing.ease(0.0)) / DERIVATIVE_STEP,
+    }
+}
+
+/// The step of progress over which derivatives are measured when no analytic one is known
+const DERIVATIVE_STEP: f64 = 1e-4;
+
+/// The progress within the segment from offset `start` to `end` of an animation
+fn segment_progress(start: f64, end: f64, progress: f64) -> f64 {
+    if end <= start {
+        1.0
+    } else {
+        ((progress - start) / (end - start)).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(not(feature = "disable-animations"))]
 enum AnimationStatus<T, I> {
     /// No animation running
     Static(T),
@@ -315,132 +1035,111 @@ enum AnimationStatus<T, I> {
     },
 }
 
-impl<T: Clone, I> AnimationStatus<T, I> {
-    fn remove_finished_animations(&mut self) {
+#[cfg(not(feature = "disable-animations"))]
+impl<T, I> AnimationStatus<T, I> {
+    fn animation_count(&self) -> usize {
         match self {
-            AnimationStatus::Static(_) => {}
-            AnimationStatus::Snap(value) => *self = AnimationStatus::Static(value.clone()),
-            AnimationStatus::Running { to, animations, .. } => {
-                animations.retain(|animation| !animation.is_finished());
-                if animations.len() == 0 {
-                    *self = AnimationStatus::Snap(to.clone());
-                }
+            AnimationStatus::Static(_) | AnimationStatus::Snap(_) => 0,
+            AnimationStatus::Running { animations, .. } => animations.len(),
+        }
+    }
+
+    /// Whether all running animations are still waiting for their delay, so the value does not change yet
+    fn is_pending(&self, now: Instant) -> bool {
+        match self {
+            AnimationStatus::Static(_) | AnimationStatus::Snap(_) => false,
+            AnimationStatus::Running { animations, .. } => {
+                animations.iter().all(|animation| animation.is_pending(now))
             }
         }
     }
 }
 
-// This is used to filter signals with create_memo. Yes, a total hack.
-enum SignalUpdate {
-    Ignore,
-    Update,
-}
+#[cfg(not(feature = "disable-animations"))]
+impl<T, I: Clone + Sub<I, Output = I>> AnimationStatus<T, I> {
+    /// The combined value of all running animations at time `now`
+    fn value(
+        &self,
+        tween: &dyn Fn(&T, &T, f64) -> I,
+        composition: Composition<I>,
+        now: Instant,
+    ) -> I {
+        match self {
+            AnimationStatus::Static(state) | AnimationStatus::Snap(state) => {
+                tween(state, state, 1.0)
+            }
+            AnimationStatus::Running {
+                animations, to_i, ..
+            } => {
+                // Add all animation results to a single value
+                animations.iter().fold(to_i.clone(), |acc, animation| {
+                    let animation_value = animation.value(tween, now);
 
-impl PartialEq for SignalUpdate {
-    fn eq(&self, other: &Self) -> bool {
-        match other {
-            SignalUpdate::Ignore => true,
-            SignalUpdate::Update => false,
+                    match composition {
+                        Composition::Additive => acc - (animation.to_i.clone() - animation_value),
+                        Composition::Custom { compose, uncompose } => {
+                            compose(&acc, &uncompose(&animation_value, &animation.to_i))
+                        }
+                    }
+                })
+            }
         }
     }
 }
 
-/// Create a derived signal that animated the value of the input signals.
-/// Takes as input a reactive source callback function and a tween function.
-///
-/// The source callback function is run in a reactive context and is expected to take the value of one or more input
-/// signals and return an `AnimationTarget` value. An `AnimationTarget` specifies a target value to
-/// animate towards and details about the duration, easing and animation of how to animate towards it.
-/// There are shortcut methods to create an `AnimationTarget` with default values, see
-/// [`AnimationTarget`] for details.
-///
-/// The tween callback specifies how to interpolate between two input values. As input it takes three
-/// arguments: `from`, `to` and `progress`. Where `from` and `to` are the values from the input signal
-/// and the `progress` is a value between 0.0 - 1.0. The easing is already applied to the `progress`.
-/// The tween function is expected to do a linear interpolation between `from` & `to` and return the
-/// result.
-///
-/// If the input is in any way numeric or supports the `Add`, `Sub` and `Mul<f64>` traits it is recommended
-/// to use the [`tween_default`] function as input which performs a simple `(to - from) * progress + from`.
-///
-/// If you are dealing with structs that are composed of numbers (for example a `Position { x: f64, y: f64 }`)
-/// you can use the [derive_more](https://docs.rs/crate/derive_more/latest) crate to implement the necessary traits.
-/// This way you can still use the `tween_default` function.
-///
-/// This function is generic over two types: `T` and `I`.
-/// * `T` is the type of values that are animated between. Animations are always from a `T` towards another `T`
-/// * `I` is the type of the interpolated values between values of type `T`.
-///
-/// In simple cases `I` is the same as `T` such as animating between `f64`'s. But they can also be different
-/// if for example the `T` is an enum which cannot represent 'in-between' values by itself.
-///
-/// Updates to the derived signal only happen on browser animation frames and only when there are animations
-/// running. If you are dealing with a HTML Canvas it is recommended to use a `create_effect()` to draw on the
-/// canvas and subscribe directly to the animated signals.
-/// All animated signals update simultaneously on animation frames so even if you subscribe to multiple animated
-/// input signals the effect will never run more than 60fps.
-///
-/// # Additive animations
-///
-/// This library uses an additive animation system. This means that multiple animations with different
-/// targets and different durations can play simultaneously without them interrupting each other.
-///
-/// Internally all animations are towards 0. For example if we start an animation from 0 to 100, this is
-/// converted to an animation from -100 to 0 which gets added to the final 100 value.
-///
-/// If then a second animation is started from 100 to 1000 it gets converted to an animation from -900 to 0.
-/// Both the -100 to 0 and the -900 to 0 animation value get added to the final 1000 value until both settle on 1000 as they reach 0.
-///
-/// This allows for all animations to play to completion even if animations are started before the previous animation is finished.
-///
-/// # Examples
-/// ```
-/// # use std::time::Duration;
-/// # use leptos::*;
-/// # use leptos_animation::{AnimationContext, AnimationMode, AnimationTarget, create_animated_signal, easing, tween_default};
-/// # let runtime = create_runtime();
-/// # AnimationContext::provide();
-/// let (value, set_value) = create_signal(42.0);
-///
-/// // Simple default animation
-/// let animated_value = create_animated_signal(move || value.get().into(), tween_default);
-///
-/// // Custom duration
-/// let slow_value = create_animated_signal(move || (value.get(), Duration::from_secs_f64(5.0)).into(), tween_default::<f64, f64>);
-///
-/// // Custom duration, easing & mode
-/// let custom_value = create_animated_signal(
-///         move || AnimationTarget {
-///             target: value.get(),
-///             duration: Duration::from_secs_f64(1.5),
-///             easing: easing::ELASTIC_IN_OUT,
-///             mode: AnimationMode::ReplaceOrStart
-///         },
-///         tween_default);
-///
-/// // Custom tween function
-/// let tween_value = create_animated_signal(
-///         move || value.get().into(),
-///         |from, to, progress| {
-///             (to - from) * progress + from
-///         });
-///
-/// # runtime.dispose();
-/// ```
-#[deprecated(note = "please use `AnimatedSignal::new()` instead")]
-pub fn create_animated_signal<T, I>(
-    source: impl Fn() -> AnimationTarget<T> + 'static,
-    tween: fn(&T, &T, f64) -> I,
-) -> AnimatedSignal<T, I>
-where
-    T: 'static,
-    T: Clone,
-    I: Clone,
-    I: Sub<I, Output = I>,
-{
-    AnimatedSignal::new(source, tween)
+#[cfg(not(feature = "disable-animations"))]
+impl<T: Clone, I> AnimationStatus<T, I> {
+    /// The value that the signal settles on once all animations have finished
+    fn settled(&self) -> &T {
+        match self {
+            AnimationStatus::Static(value) | AnimationStatus::Snap(value) => value,
+            AnimationStatus::Running { to, .. } => to,
+        }
+    }
+
+    /// Removes the animations that have finished and returns their completion callbacks
+    fn remove_finished_animations(&mut self, now: Instant) -> Vec<OnComplete> {
+        let mut completed = Vec::new();
+        match self {
+            AnimationStatus::Static(_) => {}
+            AnimationStatus::Snap(value) => *self = AnimationStatus::Static(value.clone()),
+            AnimationStatus::Running { to, animations, .. } => {
+                animations.retain(|animation| {
+                    let finished = animation.is_finished(now);
+                    if finished {
+                        completed.extend(animation.on_complete.clone());
+                    }
+                    !finished
+                });
+                if animations.is_empty() {
+                    *self = AnimationStatus::Snap(to.clone());
+                }
+            }
+        }
+        completed
+    }
+
+    /// Ends all animations at their target and returns their completion callbacks
+    fn snap_to_target(&mut self) -> Vec<OnComplete> {
+        let AnimationStatus::Running { to, animations, .. } = self else {
+            return Vec::new();
+        };
+        let completed = animations
+            .drain(..)
+            .filter_map(|animation| animation.on_complete)
+            .collect();
+        *self = AnimationStatus::Snap(to.clone());
+        completed
+    }
 }
 
+/// A version of the output of an animated signal. The animation tick runs on every animation frame
+/// and advances the generation only in frames in which the output changes. As the tick is a memo,
+/// an unchanged generation compares equal and the subscribers of the signal are not notified, so
+/// static, paused and delayed signals cost nothing beyond the tick itself.
+#[cfg(not(feature = "disable-animations"))]
+type Generation = u64;
+
 /// Default linear tween between any type of number
 pub fn tween_default<T, I>(from: &T, to: &T, progress: f64) -> I
 where
@@ -452,11 +1151,47 @@ where
     (*to - *from) * progress + *from
 }
 
+type TweenFn<T, I> = Rc<dyn Fn(&T, &T, f64) -> I>;
+
+/// The interval over which [`AnimatedSignal::velocity()`] measures the change of the value
+#[cfg(not(feature = "disable-animations"))]
+const VELOCITY_INTERVAL: Duration = Duration::from_millis(1);
+
+#[cfg(not(feature = "disable-animations"))]
 #[derive(Copy, Clone)]
 pub struct AnimatedSignal<T: 'static, I: 'static> {
+    context: AnimationContext,
+    budget: Option<AnimationBudget>,
+    tween: StoredValue<TweenFn<T, I>, LocalStorage>,
     animation_status: StoredValue<AnimationStatus<T, I>, LocalStorage>,
+    composition: StoredValue<Composition<I>, LocalStorage>,
+    clock: StoredValue<Clock>,
+    update_policy: StoredValue<UpdatePolicy>,
+    frame_interval: StoredValue<FrameInterval>,
+    phase_offset: StoredValue<Option<Duration>>,
+    configure: StoredValue<Option<ConfigureFn<T>>, LocalStorage>,
+    pending_targets: StoredValue<VecDeque<AnimationTarget<T>>, LocalStorage>,
+    flushed_frame: StoredValue<u64>,
+    source_changes: Memo<u64>,
+    applied_changes: StoredValue<u64>,
+    finished_waiters: StoredValue<Vec<finished::SharedWaiter>>,
+    watch: StoredValue<Watch>,
+    playback_rate_effect: StoredValue<Option<Effect<LocalStorage>>, LocalStorage>,
+    #[cfg(feature = "inspector")]
+    profile: StoredValue<Option<usize>>,
+    /// Notified when the animations changed outside of a frame
+    changed: Trigger,
     update_animation_status_effect: Effect<LocalStorage>,
-    animation_tick: Memo<SignalUpdate>,
+    animation_tick: Memo<Generation>,
+    animated_signal: Signal<I, LocalStorage>,
+}
+
+// Without animations a signal is nothing but its source mapped to the targets, see
+// `AnimatedSignal::new_pass_through()`
+#[cfg(feature = "disable-animations")]
+#[derive(Copy, Clone)]
+pub struct AnimatedSignal<T: 'static, I: 'static> {
+    target: PhantomData<fn() -> T>,
     animated_signal: Signal<I, LocalStorage>,
 }
 
@@ -470,6 +1205,9 @@ impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal
     /// There are shortcut methods to create an `AnimationTarget` with default values, see
     /// [`AnimationTarget`] for details.
     ///
+    /// Instead of a callback the source can also be any signal of `AnimationTarget`s, such as a `Memo`
+    /// or `ReadSignal`, see [`AnimationSource`].
+    ///
     /// The tween callback specifies how to interpolate between two input values. As input it takes three
     /// arguments: `from`, `to` and `progress`. Where `from` and `to` are the values from the input signal
     /// and the `progress` is a value between 0.0 - 1.0. The easing is already applied to the `progress`.
@@ -478,6 +1216,8 @@ impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal
     ///
     /// If the input is in any way numeric or supports the `Add`, `Sub` and `Mul<f64>` traits it is recommended
     /// to use the [`tween_default`] function as input which performs a simple `(to - from) * progress + from`.
+    /// Types that implement [`Tween`](tween::Tween), such as numbers, tuples and durations, can also be
+    /// animated with [`new_tweenable()`](AnimatedSignal::new_tweenable) without any tween function.
     ///
     /// If you are dealing with structs that are composed of numbers (for example a `Position { x: f64, y: f64 }`)
     /// you can use the [derive_more](https://docs.rs/crate/derive_more/latest) crate to implement the necessary traits.
@@ -496,6 +1236,10 @@ impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal
     /// All animated signals update simultaneously on animation frames so even if you subscribe to multiple animated
     /// input signals the effect will never run more than 60fps.
     ///
+    /// The source can also read other animated signals, for example to animate a shadow that trails an animated
+    /// position. Within a frame the signals it reads are updated first, so such chained animations start and
+    /// snap in the same frame as the signals they depend on instead of one frame behind.
+    ///
     /// # Additive animations
     ///
     /// This library uses an additive animation system. This means that multiple animations with different
@@ -512,11 +1256,10 @@ impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal
     /// # Examples
     /// ```
     /// # use std::time::Duration;
-    /// # use leptos::*;
-    /// # use leptos_animation::{AnimationContext, AnimationMode, AnimationTarget, AnimatedSignal, easing, tween_default};
-    /// # let runtime = create_runtime();
-    /// # AnimationContext::provide();
-    /// let (value, set_value) = create_signal(42.0);
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimationMode, AnimationTarget, AnimatedSignal, easing, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (value, set_value) = signal(42.0);
     ///
     /// // Simple default animation
     /// let animated_value = AnimatedSignal::new(move || value.get().into(), tween_default);
@@ -530,7 +1273,8 @@ impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal
     ///             target: value.get(),
     ///             duration: Duration::from_secs_f64(1.5),
     ///             easing: easing::ELASTIC_IN_OUT,
-    ///             mode: AnimationMode::ReplaceOrStart
+    ///             mode: AnimationMode::ReplaceOrStart,
+    ///             ..Default::default()
     ///         },
     ///         tween_default);
     ///
@@ -541,36 +1285,116 @@ impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal
     ///             (to - from) * progress + from
     ///         });
     ///
-    /// # runtime.dispose();
+    /// // Any signal of targets, such as a memo that is already computed elsewhere
+    /// let doubled = Memo::new(move |_| AnimationTarget::from(value.get() * 2.0));
+    /// let doubled_value = AnimatedSignal::new(doubled, tween_default);
     /// ```
-    pub fn new(
-        source: impl Fn() -> AnimationTarget<T> + 'static,
+    #[track_caller]
+    pub fn new<Marker>(
+        source: impl AnimationSource<T, Marker>,
         tween: fn(&T, &T, f64) -> I,
+    ) -> AnimatedSignal<T, I> {
+        Self::new_with_tween(source.into_signal(), Rc::new(tween))
+    }
+
+    /// Creates an animated signal with a tween that is not a plain function pointer
+    #[track_caller]
+    pub(crate) fn new_with_tween(
+        source: Signal<AnimationTarget<T>, LocalStorage>,
+        tween: TweenFn<T, I>,
+    ) -> AnimatedSignal<T, I> {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            Self::new_animated(source, tween)
+        }
+        #[cfg(feature = "disable-animations")]
+        {
+            Self::new_pass_through(source, tween)
+        }
+    }
+
+    #[cfg(not(feature = "disable-animations"))]
+    #[track_caller]
+    fn new_animated(
+        source: Signal<AnimationTarget<T>, LocalStorage>,
+        tween: TweenFn<T, I>,
     ) -> AnimatedSignal<T, I> {
         let context: AnimationContext = use_context().expect(
             "No AnimationContext present, call AnimationContext::provide() in a parent scope",
         );
 
-        let source = Signal::derive_local(source);
+        let budget = use_context::<AnimationBudget>();
+        let watchdog = use_context::<AnimationWatchdog>();
+        let watch = StoredValue::new(Watch::new(Location::caller()));
+        let composition = StoredValue::new_local(Composition::Additive);
 
         let animation_status = StoredValue::new_local(AnimationStatus::<T, I>::Static(
             source.get_untracked().target,
         ));
 
-        // Effect that listens to changes in the source and updates the animation status
-        let update_animation_status_effect = Effect::new(move |prev: Option<()>| {
-            let animation_target = source.get();
+        // The time of the animations of this signal, which stands still while it is paused
+        let clock = StoredValue::new(Clock::new(context.now()));
+        let now = move || {
+            let context_now = context.now();
+            clock
+                .try_with_value(|clock| clock.now(context_now))
+                .unwrap_or(context_now)
+        };
+
+        // Loops with a phase offset start on the shared loop of the context
+        let phase_offset = StoredValue::new(None);
+        let start_of = move |animation_target: &AnimationTarget<T>| {
+            let start = now() + animation_target.delay;
+            match phase_offset.get_value() {
+                Some(offset) => phase::shared_loop_start(
+                    start,
+                    context.epoch,
+                    offset,
+                    animation_target.duration,
+                    animation_target.repeat,
+                ),
+                None => start,
+            }
+        };
 
-            // Don't start an animation the very first run
-            if prev.is_none() {
-                return;
+        // Applies a target of the source to the animation status
+        let tween = StoredValue::new_local(tween);
+        let configure: StoredValue<Option<ConfigureFn<T>>, LocalStorage> =
+            StoredValue::new_local(None);
+        let apply_target = move |mut animation_target: AnimationTarget<T>| {
+            if let Some(configure) = configure.get_value() {
+                configure(&mut animation_target);
             }
+            if let Some(resolve) = animation_target.relative.take() {
+                let settled = animation_status.with_value(|status| status.settled().clone());
+                animation_target.target = resolve(&settled, &animation_target.target);
+                for keyframe in &mut animation_target.keyframes {
+                    keyframe.value = resolve(&settled, &keyframe.value);
+                }
+            }
+            let tween = tween.get_value();
+            let running_animations = animation_status.with_value(AnimationStatus::animation_count);
+            let mut animation_target = context.adapt_target(animation_target, running_animations);
+            if let Some(budget) = budget {
+                animation_target = budget.adapt_target(animation_target, running_animations);
+            }
+            let (started, cancelled) = match animation_target.mode {
+                AnimationMode::Start | AnimationMode::ReplaceWithVelocity => (1, 0),
+                AnimationMode::ReplaceOrStart => (usize::from(running_animations == 0), 0),
+                AnimationMode::ReplaceOrSnap => (0, 0),
+                AnimationMode::Snap => (0, running_animations),
+            };
+            context.count_animations(started, 0, cancelled);
+
+            let start = start_of(&animation_target);
             animation_status.update_value(|animation_status| {
                 match animation_status {
                     // Starting an animation from a non-running state
                     AnimationStatus::Static(state) | AnimationStatus::Snap(state) => {
                         match animation_target.mode {
-                            AnimationMode::Start | AnimationMode::ReplaceOrStart => {
+                            AnimationMode::Start
+                            | AnimationMode::ReplaceOrStart
+                            | AnimationMode::ReplaceWithVelocity => {
                                 let to_i =
                                     tween(&animation_target.target, &animation_target.target, 1.0);
                                 *animation_status = AnimationStatus::Running {
@@ -580,9 +1404,14 @@ impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal
                                         from: state.clone(),
                                         to: animation_target.target,
                                         to_i,
-                                        start: Instant::now(),
+                                        start,
                                         duration: animation_target.duration,
                                         easing: animation_target.easing,
+                                        repeat: animation_target.repeat,
+                                        from_rest: animation_target.mode
+                                            == AnimationMode::ReplaceWithVelocity,
+                                        keyframes: animation_target.keyframes,
+                                        on_complete: animation_target.on_complete,
                                     }]),
                                 }
                             }
@@ -597,7 +1426,7 @@ impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal
                         to_i,
                         animations,
                     } => match animation_target.mode {
-                        AnimationMode::Start => {
+                        AnimationMode::Start | AnimationMode::ReplaceWithVelocity => {
                             let new_to_i =
                                 tween(&animation_target.target, &animation_target.target, 1.0);
 
@@ -605,9 +1434,14 @@ impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal
                                 from: to.clone(),
                                 to: animation_target.target.clone(),
                                 to_i: new_to_i.clone(),
-                                start: Instant::now(),
+                                start,
                                 duration: animation_target.duration,
                                 easing: animation_target.easing,
+                                repeat: animation_target.repeat,
+                                from_rest: animation_target.mode
+                                    == AnimationMode::ReplaceWithVelocity,
+                                keyframes: animation_target.keyframes,
+                                on_complete: animation_target.on_complete,
                             });
                             *to = animation_target.target;
                             *to_i = new_to_i;
@@ -619,6 +1453,12 @@ impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal
                             let last_animation = animations.front_mut().unwrap();
                             last_animation.to = animation_target.target;
                             last_animation.to_i = to_i.clone();
+                            last_animation.keyframes = animation_target.keyframes;
+                            // The repetitions of the new target start with the current one
+                            last_animation.restart_repetition(now());
+                            last_animation.repeat = animation_target.repeat;
+                            // The animation now finishes at the new target
+                            last_animation.on_complete = animation_target.on_complete;
                         }
                         AnimationMode::Snap => {
                             *animation_status = AnimationStatus::Snap(animation_target.target)
@@ -626,57 +1466,223 @@ impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal
                     },
                 }
             });
+            if let Some(budget) = budget {
+                budget.track(
+                    running_animations,
+                    animation_status.with_value(AnimationStatus::animation_count),
+                );
+            }
             context.request_animation_frame();
+        };
+
+        // Counts the changes of the source, so each change is applied once: either by the effect
+        // below or, when the source changed during an animation frame, by the animation tick
+        let source_changes = Memo::new(move |changes: Option<&u64>| {
+            source.track();
+            changes.map_or(0, |changes| changes + 1)
+        });
+        // The first value of the source is the initial state, not a change
+        let applied_changes = StoredValue::new(0);
+        let take_source_change = move || {
+            let changes = source_changes.get();
+            let applied = applied_changes.try_get_value().unwrap_or(changes);
+            (changes != applied).then(|| {
+                applied_changes.set_value(changes);
+                source.get_untracked()
+            })
+        };
+
+        // Effect that listens to changes in the source and updates the animation status, right
+        // away or on the next frame depending on the update policy
+        let update_policy = StoredValue::new(UpdatePolicy::ApplyAll);
+        let pending_targets = StoredValue::new_local(VecDeque::new());
+        let finished_waiters = StoredValue::new(Vec::new());
+        let receive_target = move |animation_target| match update_policy.get_value().capacity() {
+            None => apply_target(animation_target),
+            Some(capacity) => {
+                pending_targets.update_value(|pending| {
+                    pending.push_back(animation_target);
+                    while pending.len() > capacity {
+                        pending.pop_front();
+                    }
+                });
+                context.request_animation_frame();
+            }
+        };
+        let update_animation_status_effect = Effect::new(move |_| {
+            if let Some(animation_target) = take_source_change() {
+                receive_target(animation_target);
+            }
         });
 
         // Signal that derives from the global animation_frame signal but only
         // fires when 'this' animation has something to update.
-        let animation_tick = Memo::new(move |_| {
+        // Requests the frame in which the output updates next
+        let frame_interval = StoredValue::new(FrameInterval::default());
+        let request_next_frame = move || match frame_interval
+            .try_with_value(FrameInterval::interval)
+            .flatten()
+        {
+            Some(interval) => context.request_frame_after(interval),
+            None => context.request_animation_frame(),
+        };
+
+        let flushed_frame = StoredValue::new(context.frame.get_value());
+        let animation_tick = Memo::new(move |generation: Option<&Generation>| {
+            let generation = generation.copied().unwrap_or_default();
             context.animation_frame.track();
+            // A source that depends on other animated signals changes while they are updated in
+            // this frame. Taking the change right away reads them first, so chained animations
+            // update in the same frame instead of one frame behind.
+            if let Some(animation_target) = take_source_change() {
+                receive_target(animation_target);
+            }
+            // Apply the targets that were held back since the previous frame. The tick also runs
+            // when the source changes between frames, those targets wait for the next frame.
+            let frame = context.frame.get_value();
+            let first_tick_of_frame = flushed_frame.try_get_value() != Some(frame);
+            if first_tick_of_frame {
+                flushed_frame.set_value(frame);
+                let pending = pending_targets
+                    .try_update_value(std::mem::take)
+                    .unwrap_or_default();
+                for animation_target in pending {
+                    apply_target(animation_target);
+                }
+            }
             let was_snap = animation_status.with_value(|animation_status| {
                 matches!(animation_status, AnimationStatus::Snap(_))
             });
-
-            animation_status.update_value(|animation_status| {
-                animation_status.remove_finished_animations();
+            // Running animations beyond the evaluations that the budget allows per frame snap
+            let over_budget = budget.is_some_and(|budget| {
+                let evaluates = first_tick_of_frame
+                    && !clock.with_value(Clock::is_paused)
+                    && animation_status.with_value(|animation_status| {
+                        matches!(animation_status, AnimationStatus::Running { .. })
+                    });
+                evaluates && !budget.spend_evaluation(frame)
             });
 
-            if was_snap {
-                SignalUpdate::Update
-            } else {
-                animation_status.with_value(|animation_status| match animation_status {
-                    AnimationStatus::Static(_) => SignalUpdate::Ignore,
-                    _ => SignalUpdate::Update,
+            let (before, after, completed) = animation_status
+                .try_update_value(|animation_status| {
+                    let before = animation_status.animation_count();
+                    let mut completed = animation_status.remove_finished_animations(now());
+                    if over_budget {
+                        completed.extend(animation_status.snap_to_target());
+                    }
+                    (before, animation_status.animation_count(), completed)
                 })
+                .unwrap_or_default();
+            // Run the callbacks after the update, so they can use this signal
+            for on_complete in completed {
+                on_complete.call();
+            }
+            let settled = after == 0
+                && pending_targets
+                    .try_with_value(VecDeque::is_empty)
+                    .unwrap_or(true);
+            if settled {
+                if let Some(waiters) = finished_waiters.try_update_value(std::mem::take) {
+                    finished::resolve(waiters);
+                }
+            }
+            if let Some(budget) = budget {
+                budget.track(before, after);
+            }
+            if let Some(watchdog) = watchdog {
+                let animating = after > 0 && !clock.with_value(Clock::is_paused);
+                let now = context.real_now();
+                watch.update_value(|watch| watch.check(watchdog, animating, now));
+            }
+            context.count_animations(0, before.saturating_sub(after), 0);
+
+            let updated = was_snap
+                || animation_status.with_value(|animation_status| match animation_status {
+                    AnimationStatus::Static(_) => false,
+                    // Paused animations keep their value without requesting frames
+                    _ if clock.with_value(Clock::is_paused) => false,
+                    // Keep waiting for the delays to pass without updating the output
+                    _ if animation_status.is_pending(now()) => {
+                        request_next_frame();
+                        false
+                    }
+                    _ => match frame_interval
+                        .try_update_value(|frame_interval| frame_interval.wait(context.real_now()))
+                        .flatten()
+                    {
+                        // Hold the output until the interval has passed
+                        Some(wait) => {
+                            context.request_frame_after(wait);
+                            false
+                        }
+                        None => true,
+                    },
+                });
+            match updated {
+                true => generation.wrapping_add(1),
+                false => generation,
             }
         });
 
+        #[cfg(feature = "inspector")]
+        let profile = StoredValue::new(None);
+        let changed = Trigger::new();
         let animated_signal = Signal::derive_local(move || {
             animation_tick.read();
-            let i: I = animation_status.with_value(|animation_status| match animation_status {
-                AnimationStatus::Static(state) | AnimationStatus::Snap(state) => {
-                    tween(state, state, 1.0)
-                }
-                AnimationStatus::Running {
-                    animations, to_i, ..
-                } => {
+            changed.track();
+            #[cfg(feature = "inspector")]
+            let evaluation_start = profile.get_value().map(|slot| (slot, Instant::now()));
+
+            let value = animation_status.with_value(|animation_status| {
+                if let AnimationStatus::Running { .. } = animation_status {
                     // Keep this signal updated in the animation loop
-                    context.request_animation_frame();
+                    if !clock.with_value(Clock::is_paused) {
+                        request_next_frame();
+                    }
+                }
+                animation_status.value(&*tween.get_value(), composition.get_value(), now())
+            });
 
-                    // Add all animation results to a single value
-                    animations.iter().fold(to_i.clone(), |acc, animation| {
-                        let animation_value =
-                            tween(&animation.from, &animation.to, animation.progress());
+            #[cfg(feature = "inspector")]
+            if let Some((slot, start)) = evaluation_start {
+                context.inspector.record_cost(slot, start.elapsed());
+            }
+            value
+        });
 
-                        acc - (animation.to_i.clone() - animation_value)
-                    })
+        on_cleanup(move || {
+            if let Some(count) = animation_status.try_with_value(AnimationStatus::animation_count) {
+                if let Some(budget) = budget {
+                    budget.track(count, 0);
                 }
-            });
-            i
+                context.count_animations(0, 0, count);
+            }
+            if let Some(waiters) = finished_waiters.try_update_value(std::mem::take) {
+                finished::resolve(waiters);
+            }
         });
 
         AnimatedSignal {
+            context,
+            budget,
+            tween,
             animation_status,
+            composition,
+            clock,
+            update_policy,
+            frame_interval,
+            phase_offset,
+            configure,
+            pending_targets,
+            flushed_frame,
+            source_changes,
+            applied_changes,
+            finished_waiters,
+            watch,
+            playback_rate_effect: StoredValue::new_local(None),
+            #[cfg(feature = "inspector")]
+            profile,
+            changed,
             update_animation_status_effect,
             animation_tick,
             animated_signal,
@@ -684,6 +1690,526 @@ impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal
     }
 }
 
+impl<T, I: Clone + Sub<I, Output = I>> AnimatedSignal<T, I> {
+    /// Calculates the animated value at the exact time of the call, without waiting for the next
+    /// animation frame and without subscribing to the signal. This is useful in event handlers that
+    /// need the precise on-screen value, like the position of a moving element at the time of a click.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (x, set_x) = signal(10.0);
+    /// let animated_x = AnimatedSignal::new(move || x.get().into(), tween_default);
+    ///
+    /// assert_eq!(animated_x.current_value(), 10.0);
+    /// ```
+    pub fn current_value(&self) -> I {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let now = self.now();
+            let composition = self.composition();
+            self.animation_status.with_value(|animation_status| {
+                self.tween
+                    .with_value(|tween| animation_status.value(&**tween, composition, now))
+            })
+        }
+        // Without animations the signal is always at its target
+        #[cfg(feature = "disable-animations")]
+        self.animated_signal.get_untracked()
+    }
+}
+
+impl<T: Clone + Sub<T, Output = T>> AnimatedSignal<T, T> {
+    /// Cancels all animations and keeps the signal at the value it has right now, instead of
+    /// snapping to the target. This lets a drag take over an element that is still moving, from
+    /// exactly where the user grabbed it. The completion callbacks of the cancelled animations
+    /// don't run and targets held back by the [update policy](AnimatedSignal::with_update_policy)
+    /// are dropped. The next change of the source animates from the stopped value.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (x, set_x) = signal(0.0);
+    /// let animated_x: AnimatedSignal<f64, f64> = AnimatedSignal::new(move || x.get().into(), tween_default);
+    ///
+    /// // The user grabs the element
+    /// animated_x.stop();
+    /// assert!(!animated_x.is_animating().get_untracked());
+    /// ```
+    pub fn stop(&self) {
+        // Without animations there is nothing to stop
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let value = self.current_value();
+            self.pending_targets.update_value(VecDeque::clear);
+            self.settle(value);
+        }
+    }
+}
+
+impl<T, I: Clone + Sub<I, Output = I> + Mul<f64, Output = I>> AnimatedSignal<T, I> {
+    /// The rate of change of the animated value at the time of the call, in units of `I` per
+    /// second. It is zero while nothing is animating or the signal is paused. This is what a
+    /// fling or drag gesture needs to take over from an animation without a jolt.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (x, set_x) = signal(0.0);
+    /// let animated_x = AnimatedSignal::new(move || x.get().into(), tween_default);
+    ///
+    /// // The user grabs the element, continue the drag at the speed it was moving
+    /// let pixels_per_second = animated_x.velocity();
+    /// assert_eq!(pixels_per_second, 0.0);
+    /// ```
+    pub fn velocity(&self) -> I {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let now = self.now();
+            let composition = self.composition();
+            let paused = self.is_paused();
+            self.animation_status.with_value(|animation_status| {
+                self.tween.with_value(|tween| {
+                    let value = animation_status.value(&**tween, composition.clone(), now);
+                    let zero = value.clone() - value.clone();
+                    if paused {
+                        return zero;
+                    }
+                    match (&composition, animation_status) {
+                        // Additive animations add up, and so do their velocities
+                        (Composition::Additive, AnimationStatus::Running { animations, .. }) => {
+                            animations
+                                .iter()
+                                .filter_map(|animation| animation.velocity(&**tween, now))
+                                .fold(zero, |velocity, animation_velocity| {
+                                    velocity - animation_velocity * -1.0
+                                })
+                        }
+                        (Composition::Additive, _) => zero,
+                        (Composition::Custom { .. }, _) => {
+                            let before = now.checked_sub(VELOCITY_INTERVAL).unwrap_or(now);
+                            let previous = animation_status.value(&**tween, composition, before);
+                            (value - previous) * (1.0 / VELOCITY_INTERVAL.as_secs_f64())
+                        }
+                    }
+                })
+            })
+        }
+        // Without animations nothing is moving
+        #[cfg(feature = "disable-animations")]
+        {
+            let value = self.current_value();
+            value.clone() - value
+        }
+    }
+}
+
+impl<T, I> AnimatedSignal<T, I> {
+    /// How the running animations of this signal are combined
+    #[cfg(not(feature = "disable-animations"))]
+    fn composition(&self) -> Composition<I>
+    where
+        I: Clone,
+    {
+        self.composition.get_value()
+    }
+
+    /// The time of the animations of this signal, which stands still while it is paused
+    #[cfg(not(feature = "disable-animations"))]
+    fn now(&self) -> Instant {
+        let context_now = self.context.now();
+        self.clock.with_value(|clock| clock.now(context_now))
+    }
+
+    /// Freezes all animations of this signal at their current value, including animations that
+    /// are started while it is paused, until [`resume()`](AnimatedSignal::resume) is called.
+    /// Other animated signals keep running.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (progress, set_progress) = signal(0.0);
+    /// let animated_progress = AnimatedSignal::new(move || progress.get().into(), tween_default);
+    ///
+    /// // Hold the progress bar while the upload is stalled
+    /// animated_progress.pause();
+    /// assert!(animated_progress.is_paused());
+    /// animated_progress.resume();
+    /// ```
+    pub fn pause(&self) {
+        // Without animations there is nothing to pause
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let context_now = self.context.now();
+            self.clock.update_value(|clock| clock.pause(context_now));
+        }
+    }
+
+    /// Continues the animations of this signal where [`pause()`](AnimatedSignal::pause) froze them
+    pub fn resume(&self) {
+        // Without animations there is nothing to resume
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let context_now = self.context.now();
+            if self
+                .clock
+                .try_update_value(|clock| clock.resume(context_now))
+                == Some(true)
+            {
+                self.context.request_animation_frame();
+            }
+        }
+    }
+
+    /// Whether this signal is paused
+    pub fn is_paused(&self) -> bool {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            self.clock.with_value(Clock::is_paused)
+        }
+        // Without animations there is nothing to pause
+        #[cfg(feature = "disable-animations")]
+        false
+    }
+
+    /// Makes the animations of this signal run `rate` times as fast as normal, on top of the
+    /// [time scale](AnimationContext::set_time_scale) of the context. Running animations keep their
+    /// current value and continue at the new rate. The rate can be a signal to change it reactively,
+    /// replacing the rate set by any previous call. Negative rates are treated as 0.0.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (x, set_x) = signal(0.0);
+    /// let animated_x = AnimatedSignal::new(move || x.get().into(), tween_default);
+    ///
+    /// // Run this component at half speed
+    /// animated_x.set_playback_rate(0.5);
+    /// assert_eq!(animated_x.playback_rate(), 0.5);
+    ///
+    /// // Or follow a reactive slider
+    /// let (rate, set_rate) = signal(1.0);
+    /// animated_x.set_playback_rate(rate);
+    /// ```
+    pub fn set_playback_rate(&self, rate: impl Into<Signal<f64>>) {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let rate = rate.into();
+            let clock = self.clock;
+            let context = self.context;
+            let apply = move |rate: f64| {
+                let context_now = context.now();
+                clock.try_update_value(|clock| clock.set_scale(context_now, rate));
+            };
+            apply(rate.get_untracked());
+
+            let effect = Effect::new(move |_| apply(rate.get()));
+            if let Some(Some(previous)) = self
+                .playback_rate_effect
+                .try_update_value(|previous| previous.replace(effect))
+            {
+                previous.dispose();
+            }
+        }
+        // Without animations there is nothing to speed up
+        #[cfg(feature = "disable-animations")]
+        let _ = rate;
+    }
+
+    /// How fast the animations of this signal run, see
+    /// [`set_playback_rate()`](AnimatedSignal::set_playback_rate)
+    pub fn playback_rate(&self) -> f64 {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            self.clock.with_value(Clock::scale)
+        }
+        // Without animations there is nothing to speed up
+        #[cfg(feature = "disable-animations")]
+        1.0
+    }
+
+    /// Rescales the remaining time of all running animations by `factor` while preserving the current
+    /// value. A factor below 1.0 speeds the animations up, for example to hurry an exit animation along
+    /// when the user is impatient, and a factor above 1.0 slows them down.
+    /// Animations that are started afterwards are not affected. Negative factors count as 0.0, which
+    /// finishes the animations right away, and factors that are NaN or infinite are ignored.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (open, set_open) = signal(1.0);
+    /// let opacity = AnimatedSignal::new(move || open.get().into(), tween_default);
+    ///
+    /// // The user hovers the close button, finish the fade out twice as fast
+    /// opacity.retime(0.5);
+    /// ```
+    pub fn retime(&self, factor: f64) {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            if !factor.is_finite() {
+                return;
+            }
+            let factor = factor.max(0.0);
+            let now = self.now();
+            self.animation_status.update_value(|animation_status| {
+                if let AnimationStatus::Running { animations, .. } = animation_status {
+                    for animation in animations {
+                        animation.retime(now, factor);
+                    }
+                }
+            });
+        }
+        // Without animations there is nothing to retime
+        #[cfg(feature = "disable-animations")]
+        let _ = factor;
+    }
+
+    /// Rescales all running animations so that they finish after `remaining`, while preserving the
+    /// current value. The animations keep their timing relative to each other: the animation that
+    /// would finish last finishes after exactly `remaining`. Infinitely repeating animations are
+    /// rescaled along with the others, but don't count as the animation that finishes last.
+    pub fn set_remaining(&self, remaining: Duration) {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let now = self.now();
+            let longest =
+                self.animation_status
+                    .with_value(|animation_status| match animation_status {
+                        AnimationStatus::Running { animations, .. } => animations
+                            .iter()
+                            .filter_map(|animation| animation.remaining(now))
+                            .max(),
+                        _ => None,
+                    });
+            match longest {
+                Some(longest) if !longest.is_zero() => {
+                    self.retime(remaining.as_secs_f64() / longest.as_secs_f64())
+                }
+                _ => {}
+            }
+        }
+        // Without animations there is nothing to retime
+        #[cfg(feature = "disable-animations")]
+        let _ = remaining;
+    }
+
+    /// Whether any animations of this signal are running, including animations that are waiting
+    /// for their delay or are paused. The signal updates on animation frames, like the value.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (open, set_open) = signal(false);
+    /// let height = AnimatedSignal::new(move || if open.get() { 200.0 } else { 0.0 }.into(), tween_default);
+    /// let is_animating = height.is_animating();
+    /// assert!(!is_animating.get_untracked());
+    /// # let _html =
+    /// view! {
+    ///     <button disabled=is_animating on:click=move |_| set_open.update(|open| *open = !*open)>
+    ///         "Toggle"
+    ///     </button>
+    /// }
+    /// # .to_html();
+    /// ```
+    pub fn is_animating(&self) -> Signal<bool> {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let context = self.context;
+            let animated_signal = self.animated_signal;
+            let animation_status = self.animation_status;
+            Memo::new(move |_| {
+                // Delayed animations start without changing the value, so check on every frame
+                context.animation_frame.track();
+                // Keep the animations updating while only this signal is observed
+                animated_signal.track();
+                animation_status
+                    .try_with_value(AnimationStatus::animation_count)
+                    .unwrap_or_default()
+                    > 0
+            })
+            .into()
+        }
+        // Without animations nothing is moving
+        #[cfg(feature = "disable-animations")]
+        Signal::stored(false)
+    }
+
+    /// The eased progress of the most recent animation, from 0.0 at its start to 1.0 at its end,
+    /// so secondary effects can follow the motion of this signal, such as a shadow that fades in
+    /// while a card is lifted. It is 1.0 while no animation is running. Repeating animations
+    /// report the progress of their current repetition.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (lifted, set_lifted) = signal(false);
+    /// let offset = AnimatedSignal::new(move || if lifted.get() { -8.0 } else { 0.0 }.into(), tween_default);
+    /// let progress = offset.progress();
+    /// let shadow_opacity = move || if lifted.get() { progress.get() } else { 1.0 - progress.get() };
+    /// assert_eq!(progress.get_untracked(), 1.0);
+    /// ```
+    pub fn progress(&self) -> Signal<f64> {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let context = self.context;
+            let clock = self.clock;
+            let animated_signal = self.animated_signal;
+            let animation_status = self.animation_status;
+            Memo::new(move |_| {
+                animated_signal.track();
+                let context_now = context.now();
+                let now = clock
+                    .try_with_value(|clock| clock.now(context_now))
+                    .unwrap_or(context_now);
+                animation_status
+                    .try_with_value(|animation_status| match animation_status {
+                        AnimationStatus::Running { animations, .. } => {
+                            animations.front().map(|animation| animation.progress(now))
+                        }
+                        AnimationStatus::Static(_) | AnimationStatus::Snap(_) => None,
+                    })
+                    .flatten()
+                    .unwrap_or(1.0)
+            })
+            .into()
+        }
+        // Without animations the signal is always at its target
+        #[cfg(feature = "disable-animations")]
+        Signal::stored(1.0)
+    }
+
+    /// Jumps all running animations to `position`, measured from the end of their delay, so they
+    /// can be scrubbed to any point. The value updates right away, even while the signal is
+    /// [paused](AnimatedSignal::pause), which makes it possible to drive an animation from a
+    /// slider. Animations that are moved past their end finish on the next animation frame.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (x, set_x) = signal(0.0);
+    /// let animated_x: AnimatedSignal<f64, f64> =
+    ///     AnimatedSignal::new(move || (x.get(), Duration::from_secs(2)).into(), tween_default);
+    ///
+    /// // Hold the animation and look at its state halfway through
+    /// animated_x.pause();
+    /// animated_x.seek(Duration::from_secs(1));
+    /// ```
+    pub fn seek(&self, position: Duration) {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let now = self.now();
+            self.seek_with(|animation| animation.seek(now, position));
+        }
+        // Without animations there is nothing to seek
+        #[cfg(feature = "disable-animations")]
+        let _ = position;
+    }
+
+    /// Jumps all running animations to `progress` between 0.0 (the start) and 1.0 (the end),
+    /// see [`seek()`](AnimatedSignal::seek). The progress covers all repetitions, except for
+    /// infinitely repeating animations where it covers a single one.
+    pub fn set_progress(&self, progress: f64) {
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let progress = progress.clamp(0.0, 1.0);
+            let now = self.now();
+            self.seek_with(|animation| {
+                let total_duration = animation.total_duration().unwrap_or(animation.duration);
+                animation.seek(now, total_duration.mul_f64(progress));
+            });
+        }
+        // Without animations there is nothing to seek
+        #[cfg(feature = "disable-animations")]
+        let _ = progress;
+    }
+
+    /// Completes all animations right away and snaps the signal to its final target, including
+    /// targets held back by the [update policy](AnimatedSignal::with_update_policy). The completion
+    /// callbacks of the animations run, as if they had played to the end. Useful for a button that
+    /// skips an intro.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (shown, set_shown) = signal(1.0);
+    /// let logo: AnimatedSignal<f64, f64> = AnimatedSignal::new(move || shown.get().into(), tween_default);
+    ///
+    /// // Skip intro
+    /// logo.finish();
+    /// assert_eq!(logo.get_untracked(), 1.0);
+    /// ```
+    pub fn finish(&self)
+    where
+        T: Clone,
+    {
+        // Without animations every target is shown and completed as soon as it is read
+        #[cfg(not(feature = "disable-animations"))]
+        {
+            let pending = self
+                .pending_targets
+                .try_update_value(std::mem::take)
+                .unwrap_or_default();
+            let Some((target, completed)) =
+                self.animation_status.try_with_value(|animation_status| {
+                    let mut target = animation_status.settled().clone();
+                    let mut completed = Vec::new();
+                    if let AnimationStatus::Running { animations, .. } = animation_status {
+                        completed.extend(
+                            animations
+                                .iter()
+                                .filter_map(|animation| animation.on_complete.clone()),
+                        );
+                    }
+                    for animation_target in pending {
+                        target = match animation_target.relative {
+                            Some(resolve) => resolve(&target, &animation_target.target),
+                            None => animation_target.target,
+                        };
+                        completed.extend(animation_target.on_complete);
+                    }
+                    (target, completed)
+                })
+            else {
+                return;
+            };
+            self.settle(target);
+            for on_complete in completed {
+                on_complete.call();
+            }
+        }
+    }
+
+    /// Ends all animations with the signal at `value`, without running their completion callbacks
+    #[cfg(not(feature = "disable-animations"))]
+    fn settle(&self, value: T) {
+        let Some(count) = self.animation_status.try_update_value(|animation_status| {
+            let count = animation_status.animation_count();
+            *animation_status = AnimationStatus::Snap(value);
+            count
+        }) else {
+            return;
+        };
+        if let Some(budget) = self.budget {
+            budget.track(count, 0);
+        }
+        self.context.count_animations(0, 0, count);
+        self.changed.notify();
+        self.context.request_animation_frame();
+    }
+
+    #[cfg(not(feature = "disable-animations"))]
+    fn seek_with(&self, mut seek: impl FnMut(&mut Animation<T, I>)) {
+        self.animation_status.update_value(|animation_status| {
+            if let AnimationStatus::Running { animations, .. } = animation_status {
+                animations.iter_mut().for_each(&mut seek);
+            }
+        });
+        self.changed.notify();
+        self.context.request_animation_frame();
+    }
+}
+
 impl<T, I> Deref for AnimatedSignal<T, I> {
     type Target = Signal<I, LocalStorage>;
 
@@ -693,10 +2219,308 @@ impl<T, I> Deref for AnimatedSignal<T, I> {
 }
 
 impl<T, I> Dispose for AnimatedSignal<T, I> {
+    #[cfg(not(feature = "disable-animations"))]
     fn dispose(self) {
         self.animation_status.dispose();
+        self.tween.dispose();
+        self.clock.dispose();
+        self.configure.dispose();
+        self.composition.dispose();
+        self.update_policy.dispose();
+        self.frame_interval.dispose();
+        self.phase_offset.dispose();
+        self.pending_targets.dispose();
+        self.flushed_frame.dispose();
+        self.applied_changes.dispose();
+        self.source_changes.dispose();
+        if let Some(waiters) = self.finished_waiters.try_update_value(std::mem::take) {
+            finished::resolve(waiters);
+        }
+        self.finished_waiters.dispose();
+        self.watch.dispose();
         self.animation_tick.dispose();
+        if let Some(Some(effect)) = self.playback_rate_effect.try_update_value(Option::take) {
+            effect.dispose();
+        }
+        self.playback_rate_effect.dispose();
+        #[cfg(feature = "inspector")]
+        self.profile.dispose();
+        self.changed.dispose();
         self.update_animation_status_effect.dispose();
         self.animated_signal.dispose();
     }
+
+    #[cfg(feature = "disable-animations")]
+    fn dispose(self) {
+        self.animated_signal.dispose();
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32"), not(feature = "disable-animations")))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::testing::TestAnimationHarness;
+
+    fn mode() -> impl Strategy<Value = AnimationMode> {
+        prop_oneof![
+            Just(AnimationMode::Start),
+            Just(AnimationMode::ReplaceOrStart),
+            Just(AnimationMode::ReplaceOrSnap),
+            Just(AnimationMode::Snap),
+            Just(AnimationMode::ReplaceWithVelocity),
+        ]
+    }
+
+    /// A target, how long its animation takes and how long to wait for the next target
+    fn change() -> impl Strategy<Value = (AnimationTarget<f64>, Duration)> {
+        (-1000.0..1000.0f64, mode(), 0..500u64, 0..300u64).prop_map(
+            |(target, mode, duration, wait)| {
+                let animation_target = AnimationTarget {
+                    duration: Duration::from_millis(duration),
+                    mode,
+                    ..AnimationTarget::from(target)
+                };
+                (animation_target, Duration::from_millis(wait))
+            },
+        )
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn animations_settle_on_the_last_target(changes in prop::collection::vec(change(), 1..12)) {
+            let harness = TestAnimationHarness::new();
+            let (source, set_source) = signal(AnimationTarget::from(0.0));
+            let animated: AnimatedSignal<f64, f64> = AnimatedSignal::new(source, tween_default);
+            harness.observe(*animated);
+
+            for (animation_target, wait) in &changes {
+                let mode = animation_target.mode;
+                set_source.set(animation_target.clone());
+                harness.step_by(*wait);
+                animated.animation_status.with_value(|animation_status| {
+                    if let AnimationStatus::Running { animations, .. } = animation_status {
+                        prop_assert!(!animations.is_empty());
+                        // The most recent animation is in front
+                        let mut starts = animations.iter().map(|animation| animation.start);
+                        let newest_first = starts
+                            .clone()
+                            .zip(starts.by_ref().skip(1))
+                            .all(|(newer, older)| newer >= older);
+                        prop_assert!(newest_first);
+                    }
+                    if mode == AnimationMode::Snap {
+                        prop_assert_eq!(animation_status.animation_count(), 0);
+                    }
+                    Ok(())
+                })?;
+            }
+
+            harness.run_until_settled();
+            let last = changes.last().map(|(animation_target, _)| animation_target.target);
+            let animation_count = animated.animation_status.with_value(AnimationStatus::animation_count);
+            prop_assert_eq!(animation_count, 0);
+            harness.assert_value_near(&animated, last.unwrap_or_default(), 1e-6);
+        }
+    }
+
+    /// Counts how often the subscribers of `signal` are notified, starting with the first run
+    fn count_notifications(
+        harness: &TestAnimationHarness,
+        signal: AnimatedSignal<f64, f64>,
+    ) -> Rc<std::cell::Cell<usize>> {
+        let notifications = Rc::new(std::cell::Cell::new(0));
+        let counter = Rc::clone(&notifications);
+        Effect::new(move |_| {
+            signal.track();
+            counter.set(counter.get() + 1);
+        });
+        harness.run_effects();
+        notifications
+    }
+
+    fn animated_height(
+        harness: &TestAnimationHarness,
+    ) -> (WriteSignal<AnimationTarget<f64>>, AnimatedSignal<f64, f64>) {
+        let (source, set_source) = signal(AnimationTarget::from(0.0));
+        let animated = AnimatedSignal::new(source, tween_default);
+        harness.observe(*animated);
+        (set_source, animated)
+    }
+
+    #[test]
+    fn chained_signals_update_in_the_same_frame() {
+        let harness = TestAnimationHarness::new();
+        let (set_source, leader) = animated_height(&harness);
+        let follower: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+            move || AnimationTarget {
+                mode: AnimationMode::Snap,
+                ..leader.get().into()
+            },
+            tween_default,
+        );
+        harness.observe(*follower);
+        set_source.set((100.0, Duration::from_secs(1), easing::LINEAR).into());
+        harness.step();
+
+        // A frame without running the effects afterwards, as a view reads the values in the frame
+        harness.advance(Duration::from_millis(100));
+        harness.context().flush_now();
+        let leader_value = leader.get_untracked();
+        assert!(leader_value > 0.0);
+        assert_eq!(follower.get_untracked(), leader_value);
+    }
+
+    #[test]
+    fn idle_callbacks_run_while_paused() {
+        let harness = TestAnimationHarness::new();
+        let (set_source, animated) = animated_height(&harness);
+        set_source.set((100.0, Duration::from_secs(1), easing::LINEAR).into());
+        harness.step_by(Duration::from_millis(100));
+        let idle = StoredValue::new_local(false);
+        harness.context().on_idle(move || idle.set_value(true));
+        harness.step_by(Duration::from_millis(100));
+        assert!(!idle.get_value());
+
+        // The animation has not finished, but nothing competes for the frames while it stands still
+        harness.context().pause_all();
+        harness.step_by(Duration::from_millis(100));
+        assert!(idle.get_value());
+        assert!(animated.get_untracked() < 100.0);
+    }
+
+    #[test]
+    fn static_signals_ignore_frames() {
+        let harness = TestAnimationHarness::new();
+        let (_, animated) = animated_height(&harness);
+        let notifications = count_notifications(&harness, animated);
+        for _ in 0..5 {
+            harness.context().flush_now();
+            harness.step();
+        }
+        assert_eq!(notifications.get(), 1);
+    }
+
+    #[test]
+    fn running_signals_update_every_frame() {
+        let harness = TestAnimationHarness::new();
+        let (set_source, animated) = animated_height(&harness);
+        let notifications = count_notifications(&harness, animated);
+        set_source.set((100.0, Duration::from_secs(1)).into());
+        harness.step();
+        let started = notifications.get();
+        for frame in 1..=5 {
+            harness.step();
+            assert_eq!(notifications.get(), started + frame);
+        }
+    }
+
+    #[test]
+    fn paused_signals_ignore_frames() {
+        let harness = TestAnimationHarness::new();
+        let (set_source, animated) = animated_height(&harness);
+        let notifications = count_notifications(&harness, animated);
+        set_source.set((100.0, Duration::from_secs(1)).into());
+        harness.step();
+        animated.pause();
+        harness.step();
+        let paused = notifications.get();
+        for _ in 0..5 {
+            harness.context().flush_now();
+            harness.step();
+        }
+        assert_eq!(notifications.get(), paused);
+
+        animated.resume();
+        harness.step();
+        assert!(notifications.get() > paused);
+    }
+
+    #[test]
+    fn delayed_signals_update_once_the_delay_passed() {
+        let harness = TestAnimationHarness::new();
+        let (set_source, animated) = animated_height(&harness);
+        let notifications = count_notifications(&harness, animated);
+        set_source.set(AnimationTarget {
+            duration: Duration::from_millis(500),
+            delay: Duration::from_millis(100),
+            ..AnimationTarget::from(100.0)
+        });
+        harness.step();
+        let waiting = notifications.get();
+        harness.step_by(Duration::from_millis(50));
+        assert_eq!(notifications.get(), waiting);
+        // Frames keep being requested while the delay passes
+        assert!(harness.is_animating());
+
+        harness.step_by(Duration::from_millis(60));
+        assert!(notifications.get() > waiting);
+    }
+
+    #[test]
+    fn even_alternate_counts_come_to_rest_at_the_target() {
+        let harness = TestAnimationHarness::new();
+        let (set_source, animated) = animated_height(&harness);
+        set_source.set(
+            AnimationTarget::from((100.0, Duration::from_secs(1), easing::LINEAR))
+                .repeat(RepeatMode::Alternate(2)),
+        );
+        let step_until = |elapsed: Duration| {
+            while harness.elapsed() < elapsed {
+                harness.step();
+            }
+        };
+        harness.step();
+        let start = harness.elapsed();
+        step_until(start + Duration::from_millis(1950));
+        harness.assert_value_near(&animated, 5.0, 2.0);
+        // The third repetition plays forwards again
+        step_until(start + Duration::from_millis(2950));
+        harness.assert_value_near(&animated, 95.0, 2.0);
+        harness.run_until_settled();
+        assert_eq!(animated.get_untracked(), 100.0);
+    }
+
+    #[test]
+    fn retiming_with_extreme_factors_does_not_panic() {
+        let harness = TestAnimationHarness::new();
+        let (set_source, animated) = animated_height(&harness);
+        set_source.set((100.0, Duration::from_secs(1), easing::LINEAR).into());
+        harness.step();
+        harness.step_by(Duration::from_millis(200));
+        let value = animated.get_untracked();
+        for factor in [f64::NAN, f64::INFINITY] {
+            animated.retime(factor);
+            harness.assert_value_near(&animated, value, 1e-6);
+        }
+        // Durations beyond what fits saturate instead of panicking
+        animated.retime(1e300);
+        harness.step();
+        assert!(animated.is_animating().get_untracked());
+
+        animated.retime(-1.0);
+        harness.step();
+        assert_eq!(animated.get_untracked(), 100.0);
+    }
+
+    #[test]
+    fn snapped_signals_update_once() {
+        let harness = TestAnimationHarness::new();
+        let (set_source, animated) = animated_height(&harness);
+        let notifications = count_notifications(&harness, animated);
+        set_source.set((100.0, AnimationMode::Snap).into());
+        harness.run_until_settled();
+        let snapped = notifications.get();
+        assert!(snapped > 1);
+        assert_eq!(animated.get_untracked(), 100.0);
+
+        for _ in 0..5 {
+            harness.context().flush_now();
+            harness.step();
+        }
+        assert_eq!(notifications.get(), snapped);
+    }
 }
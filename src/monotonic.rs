@@ -0,0 +1,85 @@
+//! Values that never move backwards, such as the fill of a progress bar.
+
+use leptos::prelude::*;
+
+use crate::AnimatedSignal;
+#[cfg(not(feature = "disable-animations"))]
+use crate::AnimationStatus;
+
+/// What a [`monotonic()`](AnimatedSignal::monotonic) value does when its target becomes smaller
+/// than the value it already shows
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Decrease {
+    /// Jump straight to the smaller target and rise monotonically from there, like a progress bar
+    /// that starts over for the next task. This is the default
+    #[default]
+    Snap,
+
+    /// Keep showing the highest value reached until a later target exceeds it, like the progress
+    /// of a download whose size estimate grew
+    Hold,
+}
+
+impl<T, I: Clone + PartialOrd + 'static> AnimatedSignal<T, I> {
+    /// The value of this signal, constrained to never decrease while it animates. Easings that
+    /// overshoot or start by moving backwards (such as [`BACK_OUT`](crate::easing::BACK_OUT) and
+    /// [`BACK_IN`](crate::easing::BACK_IN)) are clamped between the value shown last and the
+    /// target, so the value rises steadily and comes to rest on the target. Retargeting to a value
+    /// below the one shown is handled as `decrease` says.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # use leptos_animation::monotonic::Decrease;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (loaded, set_loaded) = signal(0.0);
+    /// let bar: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+    ///     move || (loaded.get(), Duration::from_millis(400), easing::BACK_OUT).into(),
+    ///     tween_default,
+    /// );
+    /// // A springy bar that never bounces back below what was already loaded
+    /// let width = bar.monotonic(Decrease::Hold);
+    /// assert_eq!(width.get_untracked(), 0.0);
+    /// # let _html =
+    /// view! { <div class="progress" style:width=move || format!("{}%", width.get())></div> }
+    /// # .to_html();
+    /// ```
+    pub fn monotonic(&self, decrease: Decrease) -> Signal<I, LocalStorage> {
+        let signal = self.animated_signal;
+        #[cfg(not(feature = "disable-animations"))]
+        let status = self.animation_status;
+        #[cfg(not(feature = "disable-animations"))]
+        let tween = self.tween;
+        let shown: StoredValue<Option<I>, LocalStorage> = StoredValue::new_local(None);
+        Signal::derive_local(move || {
+            let value = signal.get();
+            #[cfg(not(feature = "disable-animations"))]
+            let target = status
+                .try_with_value(|status| match status {
+                    AnimationStatus::Running { to_i, .. } => Some(to_i.clone()),
+                    AnimationStatus::Static(to) | AnimationStatus::Snap(to) => {
+                        tween.try_with_value(|tween| tween(to, to, 1.0))
+                    }
+                })
+                .flatten()
+                .unwrap_or_else(|| value.clone());
+            // Without animations the signal is always at its target
+            #[cfg(feature = "disable-animations")]
+            let target = value.clone();
+
+            let mut floor = shown.get_value().unwrap_or_else(|| value.clone());
+            if target < floor && decrease == Decrease::Snap {
+                floor = target.clone();
+            }
+            let constrained = if value < floor {
+                floor
+            } else if value > target && target >= floor {
+                target
+            } else {
+                value
+            };
+            shown.set_value(Some(constrained.clone()));
+            constrained
+        })
+    }
+}
@@ -0,0 +1,149 @@
+//! Animated numbers written out for people to read, such as the counting up statistics of a
+//! dashboard.
+
+use leptos::prelude::*;
+
+use crate::AnimatedSignal;
+
+/// How [`AnimatedSignal::formatted()`] writes a number: with a fixed number of decimals, the
+/// digits of the integer part grouped by thousands, and an optional prefix and suffix for units.
+///
+/// The separators default to those of English, [`locale()`](NumberFormat::locale) picks those of
+/// other languages.
+/// ```
+/// # use leptos_animation::number::NumberFormat;
+/// assert_eq!(NumberFormat::new().format(1234567.8), "1,234,568");
+/// assert_eq!(NumberFormat::new().decimals(2).prefix("$").format(-1234.5), "-$1,234.50");
+/// assert_eq!(NumberFormat::locale("de-DE").decimals(1).format(1234.56), "1.234,6");
+/// assert_eq!(NumberFormat::new().separators("", ".").suffix(" ms").format(1234.0), "1234 ms");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    decimals: usize,
+    group_separator: String,
+    decimal_separator: String,
+    prefix: String,
+    suffix: String,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat::new()
+    }
+}
+
+impl NumberFormat {
+    /// Whole numbers with the separators of English, such as `1,234,568`
+    pub fn new() -> NumberFormat {
+        NumberFormat {
+            decimals: 0,
+            group_separator: ",".to_string(),
+            decimal_separator: ".".to_string(),
+            prefix: String::new(),
+            suffix: String::new(),
+        }
+    }
+
+    /// Whole numbers with the separators of the language of a BCP 47 language tag such as `"fr"`
+    /// or `"de-CH"`, as `navigator.language` returns it. The separators of the most common
+    /// languages are built in, other languages get those of English.
+    pub fn locale(tag: &str) -> NumberFormat {
+        let tag = tag.to_ascii_lowercase().replace('_', "-");
+        let language = tag.split('-').next().unwrap_or_default();
+        let (group, decimal) = match (language, tag.as_str()) {
+            (_, "de-ch" | "it-ch" | "fr-ch") => ("\u{2019}", "."),
+            (_, "es-mx") => (",", "."),
+            (_, "pt-br") => (".", ","),
+            ("fr", _) => ("\u{202f}", ","),
+            ("cs" | "fi" | "nb" | "no" | "pl" | "pt" | "ru" | "sk" | "sv" | "uk", _) => {
+                ("\u{a0}", ",")
+            }
+            ("da" | "de" | "el" | "es" | "id" | "it" | "nl" | "ro" | "sl" | "tr", _) => (".", ","),
+            _ => (",", "."),
+        };
+        NumberFormat::new().separators(group, decimal)
+    }
+
+    /// Writes `decimals` digits after the decimal separator, rounded
+    pub fn decimals(mut self, decimals: usize) -> NumberFormat {
+        self.decimals = decimals;
+        self
+    }
+
+    /// Separates the groups of thousands with `group`, which may be empty to not group them, and
+    /// the decimals with `decimal`
+    pub fn separators(mut self, group: &str, decimal: &str) -> NumberFormat {
+        self.group_separator = group.to_string();
+        self.decimal_separator = decimal.to_string();
+        self
+    }
+
+    /// Writes `prefix` in front of the number, after the minus sign, such as a currency symbol
+    pub fn prefix(mut self, prefix: &str) -> NumberFormat {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Writes `suffix` after the number, such as a unit
+    pub fn suffix(mut self, suffix: &str) -> NumberFormat {
+        self.suffix = suffix.to_string();
+        self
+    }
+
+    /// Writes `value` in this format. Values that round to zero are written without a minus sign.
+    pub fn format(&self, value: f64) -> String {
+        let rounded = format!("{:.*}", self.decimals, value.abs());
+        let (integer, fraction) = rounded.split_once('.').unwrap_or((&rounded, ""));
+        let negative = value < 0.0 && rounded.bytes().any(|digit| matches!(digit, b'1'..=b'9'));
+
+        let mut formatted = String::with_capacity(rounded.len() * 2 + 8);
+        if negative {
+            formatted.push('-');
+        }
+        formatted.push_str(&self.prefix);
+        if !integer.bytes().all(|digit| digit.is_ascii_digit()) {
+            // Infinite and NaN values
+            formatted.push_str(integer);
+        } else {
+            for (index, digit) in integer.chars().enumerate() {
+                if index > 0 && (integer.len() - index) % 3 == 0 {
+                    formatted.push_str(&self.group_separator);
+                }
+                formatted.push(digit);
+            }
+        }
+        if !fraction.is_empty() {
+            formatted.push_str(&self.decimal_separator);
+            formatted.push_str(fraction);
+        }
+        formatted.push_str(&self.suffix);
+        formatted
+    }
+}
+
+impl<T, I: Clone + Into<f64> + 'static> AnimatedSignal<T, I> {
+    /// The animated value written as `format` says, for counters that count up to a new value.
+    /// The text only changes when the written number changes, so the DOM is not touched on frames
+    /// that round to the same text.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # use leptos_animation::number::NumberFormat;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (revenue, set_revenue) = signal(1234.5);
+    /// let animated_revenue: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+    ///     move || (revenue.get(), Duration::from_secs(2), easing::CUBIC_OUT).into(),
+    ///     tween_default,
+    /// );
+    /// let text = animated_revenue.formatted(NumberFormat::new().decimals(2).prefix("$"));
+    /// assert_eq!(text.get_untracked(), "$1,234.50");
+    /// # let _html =
+    /// view! { <span class="stat">{text}</span> }
+    /// # .to_html();
+    /// ```
+    pub fn formatted(&self, format: NumberFormat) -> Signal<String> {
+        let animated_signal = self.animated_signal;
+        Memo::new(move |_| format.format(animated_signal.get().into())).into()
+    }
+}
@@ -0,0 +1,61 @@
+//! Animated signals without animations, for builds with the `disable-animations` feature such as
+//! kiosks, tests and low-end devices.
+
+use std::marker::PhantomData;
+use std::ops::Sub;
+
+use leptos::prelude::*;
+
+use crate::{AnimatedSignal, AnimationTarget, TweenFn};
+
+impl<T: 'static + Clone, I: 'static + Clone + Sub<I, Output = I>> AnimatedSignal<T, I> {
+    /// Creates an animated signal that shows every target of the source right away, as if it was
+    /// started with [`AnimationMode::Snap`](crate::AnimationMode::Snap). The signal derives its
+    /// value straight from the source, it creates no effects or memos, requests no animation
+    /// frames and needs no [`AnimationContext`](crate::AnimationContext).
+    ///
+    /// As nothing is kept between targets, relative targets are shown as their offset and the
+    /// settings of [`with_config()`](AnimatedSignal::with_config) are not applied. The completion
+    /// callbacks of the targets are dropped without running: a source that is a closure builds its
+    /// target anew on every read, so there is no single point at which a target completes. Code
+    /// that waits for the signal can use [`finished()`](AnimatedSignal::finished), which resolves
+    /// right away.
+    pub(crate) fn new_pass_through(
+        source: Signal<AnimationTarget<T>, LocalStorage>,
+        tween: TweenFn<T, I>,
+    ) -> AnimatedSignal<T, I> {
+        AnimatedSignal {
+            target: PhantomData,
+            animated_signal: Signal::derive_local(move || {
+                source.with(|animation_target| {
+                    let target = &animation_target.target;
+                    tween(target, target, 1.0)
+                })
+            }),
+        }
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::tween_default;
+
+    #[test]
+    fn targets_are_shown_right_away() {
+        let owner = Owner::new();
+        owner.set();
+        let (target, set_target) = signal(0.0);
+        let animated: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+            move || (target.get(), Duration::from_secs(1)).into(),
+            tween_default,
+        );
+
+        set_target.set(100.0);
+        assert_eq!(animated.get_untracked(), 100.0);
+        assert_eq!(animated.velocity(), 0.0);
+        assert!(!animated.is_animating().get_untracked());
+    }
+}
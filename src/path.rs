@@ -0,0 +1,793 @@
+//! Shape morphing between SVG paths, by animating the `d` attribute of a `<path>`.
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+use crate::css::format_number;
+use crate::tween::Tween;
+
+type Point = (f64, f64);
+
+/// A cubic Bézier curve from the end of the curve before it
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Cubic {
+    c1: Point,
+    c2: Point,
+    to: Point,
+}
+
+/// A run of curves that starts with a `M` command
+#[derive(Clone, Debug, PartialEq)]
+struct Subpath {
+    start: Point,
+    curves: Vec<Cubic>,
+    closed: bool,
+}
+
+/// A parsed SVG path, to morph into other paths with [`tween_path`].
+///
+/// Paths are parsed from the path data of a `d` attribute with [`Path::parse()`] or `str::parse()`,
+/// and written back out with [`to_d()`](Path::to_d) or `format!("{path}")`. All commands are
+/// supported, relative and absolute. They are converted to cubic Bézier curves, so the path data
+/// that is written out only uses `M`, `C` and `Z`.
+/// ```
+/// # use leptos_animation::path::Path;
+/// let triangle = Path::parse("M 0 0 h 10 v 10 z").unwrap();
+/// assert_eq!(
+///     triangle.to_d(),
+///     "M0 0C3.3333 0 6.6667 0 10 0C10 3.3333 10 6.6667 10 10C6.6667 6.6667 3.3333 3.3333 0 0Z"
+/// );
+///
+/// let error = Path::parse("M 0 0 L 10").unwrap_err();
+/// assert_eq!(error.to_string(), "invalid SVG path data at byte 10");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Path {
+    subpaths: Vec<Subpath>,
+}
+
+/// The error of parsing invalid path data
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PathError {
+    /// The byte of the path data at which parsing failed
+    pub offset: usize,
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid SVG path data at byte {}", self.offset)
+    }
+}
+
+impl std::error::Error for PathError {}
+
+impl Path {
+    /// Parses the path data of a `d` attribute, see <https://www.w3.org/TR/SVG2/paths.html#PathData>
+    pub fn parse(d: &str) -> Result<Path, PathError> {
+        PathParser::new(d).parse()
+    }
+
+    /// Whether the path draws nothing
+    pub fn is_empty(&self) -> bool {
+        self.subpaths.is_empty()
+    }
+
+    /// The path data for the `d` attribute of a `<path>`
+    pub fn to_d(&self) -> String {
+        self.to_string()
+    }
+
+    /// Both paths with the same number of subpaths and curves, see [`tween_path`]
+    fn align(&self, other: &Path) -> (Vec<Subpath>, Vec<Subpath>) {
+        let count = self.subpaths.len().max(other.subpaths.len());
+        let subpath_or_point = |subpaths: &[Subpath], others: &[Subpath], index: usize| {
+            subpaths.get(index).cloned().unwrap_or_else(|| Subpath {
+                start: others[index].center(),
+                curves: Vec::new(),
+                closed: others[index].closed,
+            })
+        };
+        (0..count)
+            .map(|index| {
+                let from = subpath_or_point(&self.subpaths, &other.subpaths, index);
+                let to = subpath_or_point(&other.subpaths, &self.subpaths, index);
+                let curves = from.curves.len().max(to.curves.len());
+                let from = from.split_into(curves);
+                let to = to.split_into(curves);
+                (from.rotated_towards(&to), to)
+            })
+            .unzip()
+    }
+
+    /// Combines both paths point by point, after giving them the same structure
+    fn zip(&self, rhs: &Path, f: impl Fn(f64, f64) -> f64) -> Path {
+        let point = |a: Point, b: Point| (f(a.0, b.0), f(a.1, b.1));
+        let (from, to) = self.align(rhs);
+        let subpaths = from
+            .iter()
+            .zip(&to)
+            .map(|(a, b)| Subpath {
+                start: point(a.start, b.start),
+                curves: a
+                    .curves
+                    .iter()
+                    .zip(&b.curves)
+                    .map(|(a, b)| Cubic {
+                        c1: point(a.c1, b.c1),
+                        c2: point(a.c2, b.c2),
+                        to: point(a.to, b.to),
+                    })
+                    .collect(),
+                closed: a.closed,
+            })
+            .collect();
+        Path { subpaths }
+    }
+}
+
+impl FromStr for Path {
+    type Err = PathError;
+
+    fn from_str(d: &str) -> Result<Path, PathError> {
+        Path::parse(d)
+    }
+}
+
+impl Subpath {
+    /// The center of the bounding box of the points of the subpath, which subpaths that only exist
+    /// on one side of a morph grow from or shrink into
+    fn center(&self) -> Point {
+        let points = self
+            .curves
+            .iter()
+            .flat_map(|curve| [curve.c1, curve.c2, curve.to]);
+        let (mut min, mut max) = (self.start, self.start);
+        for (x, y) in points {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+        ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0)
+    }
+
+    /// The same shape in `count` curves, at least as many as there are. The longer curves are split
+    /// into more pieces, so the points of the morph spread out evenly.
+    fn split_into(self, count: usize) -> Subpath {
+        if self.curves.len() >= count {
+            return self;
+        }
+        if self.curves.is_empty() {
+            let point = Cubic {
+                c1: self.start,
+                c2: self.start,
+                to: self.start,
+            };
+            return Subpath {
+                curves: vec![point; count],
+                ..self
+            };
+        }
+
+        let mut from = self.start;
+        let lengths: Vec<f64> = self
+            .curves
+            .iter()
+            .map(|curve| {
+                let length = curve.length_estimate(from);
+                from = curve.to;
+                length
+            })
+            .collect();
+        let mut pieces = vec![1; self.curves.len()];
+        for _ in self.curves.len()..count {
+            let longest = (0..pieces.len())
+                .max_by(|&a, &b| {
+                    let a = lengths[a] / pieces[a] as f64;
+                    let b = lengths[b] / pieces[b] as f64;
+                    a.total_cmp(&b)
+                })
+                .unwrap_or_default();
+            pieces[longest] += 1;
+        }
+
+        let mut curves = Vec::with_capacity(count);
+        let mut from = self.start;
+        for (curve, pieces) in self.curves.iter().zip(pieces) {
+            curve.split_into(from, pieces, &mut curves);
+            from = curve.to;
+        }
+        Subpath { curves, ..self }
+    }
+
+    /// The same closed shape starting at whichever of its points lets the points of a morph into
+    /// `other` travel the least
+    fn rotated_towards(self, other: &Subpath) -> Subpath {
+        let count = self.curves.len();
+        if !self.closed || !other.closed || count < 2 || count != other.curves.len() {
+            return self;
+        }
+        let distance = |a: Point, b: Point| (a.0 - b.0).powi(2) + (a.1 - b.1).powi(2);
+        let travel = |offset: usize| {
+            (0..count)
+                .map(|index| {
+                    let curve = &self.curves[(index + offset) % count];
+                    let target = &other.curves[index];
+                    distance(curve.to, target.to)
+                        + distance(curve.c1, target.c1)
+                        + distance(curve.c2, target.c2)
+                })
+                .sum::<f64>()
+        };
+        let offset = (0..count)
+            .min_by(|&a, &b| travel(a).total_cmp(&travel(b)))
+            .unwrap_or_default();
+        if offset == 0 {
+            return self;
+        }
+        let mut curves = self.curves;
+        curves.rotate_left(offset);
+        Subpath {
+            start: curves[count - 1].to,
+            curves,
+            closed: true,
+        }
+    }
+}
+
+impl Cubic {
+    /// A straight line to `to`
+    fn line(from: Point, to: Point) -> Cubic {
+        Cubic {
+            c1: lerp(from, to, 1.0 / 3.0),
+            c2: lerp(from, to, 2.0 / 3.0),
+            to,
+        }
+    }
+
+    /// The mean of the length of the chord and of the control polygon, which the curve lies
+    /// between
+    fn length_estimate(&self, from: Point) -> f64 {
+        let distance = |a: Point, b: Point| (a.0 - b.0).hypot(a.1 - b.1);
+        let polygon =
+            distance(from, self.c1) + distance(self.c1, self.c2) + distance(self.c2, self.to);
+        (distance(from, self.to) + polygon) / 2.0
+    }
+
+    /// Splits the curve from `from` into `pieces` curves of equal parameter ranges
+    fn split_into(&self, from: Point, pieces: usize, curves: &mut Vec<Cubic>) {
+        let mut from = from;
+        let mut rest = *self;
+        for remaining in (2..=pieces).rev() {
+            let (first, second) = rest.split_at(from, 1.0 / remaining as f64);
+            curves.push(first);
+            from = first.to;
+            rest = second;
+        }
+        curves.push(rest);
+    }
+
+    /// The curve from `from` split at `t` with de Casteljau's algorithm
+    fn split_at(&self, from: Point, t: f64) -> (Cubic, Cubic) {
+        let a = lerp(from, self.c1, t);
+        let b = lerp(self.c1, self.c2, t);
+        let c = lerp(self.c2, self.to, t);
+        let ab = lerp(a, b, t);
+        let bc = lerp(b, c, t);
+        let middle = lerp(ab, bc, t);
+        (
+            Cubic {
+                c1: a,
+                c2: ab,
+                to: middle,
+            },
+            Cubic {
+                c1: bc,
+                c2: c,
+                to: self.to,
+            },
+        )
+    }
+}
+
+fn lerp(from: Point, to: Point, t: f64) -> Point {
+    (from.0.tween(&to.0, t), from.1.tween(&to.1, t))
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let point = |(x, y): Point| format!("{} {}", format_number(x), format_number(y));
+        for subpath in &self.subpaths {
+            write!(f, "M{}", point(subpath.start))?;
+            for curve in &subpath.curves {
+                let (c1, c2, to) = (point(curve.c1), point(curve.c2), point(curve.to));
+                write!(f, "C{c1} {c2} {to}")?;
+            }
+            if subpath.closed {
+                write!(f, "Z")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads path data into cubic curves
+struct PathParser<'a> {
+    d: &'a [u8],
+    offset: usize,
+    subpaths: Vec<Subpath>,
+    /// The subpath that is being drawn
+    current: Option<Subpath>,
+    /// The point that the next command draws from
+    point: Point,
+    /// Where the next subpath starts if it doesn't start with a `M`, after a `Z`
+    start: Point,
+    /// The second control point of the last `C` or `S` command, for the next `S`
+    cubic_control: Option<Point>,
+    /// The control point of the last `Q` or `T` command, for the next `T`
+    quadratic_control: Option<Point>,
+}
+
+impl<'a> PathParser<'a> {
+    fn new(d: &'a str) -> PathParser<'a> {
+        PathParser {
+            d: d.as_bytes(),
+            offset: 0,
+            subpaths: Vec::new(),
+            current: None,
+            point: (0.0, 0.0),
+            start: (0.0, 0.0),
+            cubic_control: None,
+            quadratic_control: None,
+        }
+    }
+
+    fn parse(mut self) -> Result<Path, PathError> {
+        let mut command = None;
+        loop {
+            self.skip_separators();
+            let Some(&next) = self.d.get(self.offset) else {
+                break;
+            };
+            if next.is_ascii_alphabetic() {
+                self.offset += 1;
+                command = Some(next);
+            } else {
+                // Numbers after the arguments of a command repeat it, a `M` continues as a `L`
+                command = match command {
+                    Some(b'M') => Some(b'L'),
+                    Some(b'm') => Some(b'l'),
+                    Some(b'Z' | b'z') | None => return Err(self.error()),
+                    command => command,
+                };
+            }
+            match command {
+                Some(command) => self.command(command)?,
+                None => return Err(self.error()),
+            }
+        }
+        self.finish_subpath();
+        Ok(Path {
+            subpaths: self.subpaths,
+        })
+    }
+
+    fn command(&mut self, command: u8) -> Result<(), PathError> {
+        let relative = command.is_ascii_lowercase();
+        let point = |parser: &mut Self| -> Result<Point, PathError> {
+            let (x, y) = (parser.number()?, parser.number()?);
+            Ok(match relative {
+                true => (parser.point.0 + x, parser.point.1 + y),
+                false => (x, y),
+            })
+        };
+        if self.current.is_none() && !matches!(command, b'M' | b'm') {
+            // Only a `Z` may be followed by another command than `M`
+            if self.subpaths.last().is_none_or(|subpath| !subpath.closed) {
+                return Err(PathError {
+                    offset: self.offset - 1,
+                });
+            }
+            self.begin_subpath(self.start);
+        }
+
+        let (mut cubic_control, mut quadratic_control) = (None, None);
+        match command.to_ascii_uppercase() {
+            b'M' => {
+                let to = point(self)?;
+                self.finish_subpath();
+                self.begin_subpath(to);
+            }
+            b'L' => {
+                let to = point(self)?;
+                self.push(Cubic::line(self.point, to));
+            }
+            b'H' => {
+                let x = self.number()?;
+                let x = if relative { self.point.0 + x } else { x };
+                self.push(Cubic::line(self.point, (x, self.point.1)));
+            }
+            b'V' => {
+                let y = self.number()?;
+                let y = if relative { self.point.1 + y } else { y };
+                self.push(Cubic::line(self.point, (self.point.0, y)));
+            }
+            b'C' => {
+                let (c1, c2, to) = (point(self)?, point(self)?, point(self)?);
+                self.push(Cubic { c1, c2, to });
+                cubic_control = Some(c2);
+            }
+            b'S' => {
+                let (c2, to) = (point(self)?, point(self)?);
+                let c1 = reflect(self.cubic_control, self.point);
+                self.push(Cubic { c1, c2, to });
+                cubic_control = Some(c2);
+            }
+            b'Q' => {
+                let (control, to) = (point(self)?, point(self)?);
+                self.push(quadratic(self.point, control, to));
+                quadratic_control = Some(control);
+            }
+            b'T' => {
+                let to = point(self)?;
+                let control = reflect(self.quadratic_control, self.point);
+                self.push(quadratic(self.point, control, to));
+                quadratic_control = Some(control);
+            }
+            b'A' => {
+                let (rx, ry, rotation) = (self.number()?, self.number()?, self.number()?);
+                let (large_arc, sweep) = (self.flag()?, self.flag()?);
+                let to = point(self)?;
+                for curve in arc(self.point, (rx, ry), rotation, large_arc, sweep, to) {
+                    self.push(curve);
+                }
+            }
+            b'Z' => {
+                if let Some(mut subpath) = self.current.take() {
+                    if self.point != subpath.start {
+                        subpath.curves.push(Cubic::line(self.point, subpath.start));
+                    }
+                    subpath.closed = true;
+                    self.point = subpath.start;
+                    self.start = subpath.start;
+                    self.subpaths.push(subpath);
+                }
+            }
+            _ => {
+                return Err(PathError {
+                    offset: self.offset - 1,
+                })
+            }
+        }
+        self.cubic_control = cubic_control;
+        self.quadratic_control = quadratic_control;
+        Ok(())
+    }
+
+    fn begin_subpath(&mut self, start: Point) {
+        self.current = Some(Subpath {
+            start,
+            curves: Vec::new(),
+            closed: false,
+        });
+        self.point = start;
+        self.start = start;
+    }
+
+    fn finish_subpath(&mut self) {
+        self.subpaths.extend(self.current.take());
+    }
+
+    fn push(&mut self, curve: Cubic) {
+        self.point = curve.to;
+        if let Some(subpath) = &mut self.current {
+            subpath.curves.push(curve);
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while self
+            .d
+            .get(self.offset)
+            .is_some_and(|&byte| byte.is_ascii_whitespace() || byte == b',')
+        {
+            self.offset += 1;
+        }
+    }
+
+    fn error(&self) -> PathError {
+        PathError {
+            offset: self.offset,
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, PathError> {
+        self.skip_separators();
+        let start = self.offset;
+        let digits = |parser: &mut Self| {
+            let from = parser.offset;
+            while parser.d.get(parser.offset).is_some_and(u8::is_ascii_digit) {
+                parser.offset += 1;
+            }
+            parser.offset > from
+        };
+        if matches!(self.d.get(self.offset), Some(b'+' | b'-')) {
+            self.offset += 1;
+        }
+        let mut any = digits(self);
+        if self.d.get(self.offset) == Some(&b'.') {
+            self.offset += 1;
+            any |= digits(self);
+        }
+        if !any {
+            self.offset = start;
+            return Err(self.error());
+        }
+        if matches!(self.d.get(self.offset), Some(b'e' | b'E')) {
+            let mantissa = self.offset;
+            self.offset += 1;
+            if matches!(self.d.get(self.offset), Some(b'+' | b'-')) {
+                self.offset += 1;
+            }
+            if !digits(self) {
+                // Not an exponent but the next command, such as the `e` of an arc written as `a`
+                self.offset = mantissa;
+            }
+        }
+        std::str::from_utf8(&self.d[start..self.offset])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or(PathError { offset: start })
+    }
+
+    /// The flags of arcs are single digits that don't need to be separated
+    fn flag(&mut self) -> Result<bool, PathError> {
+        self.skip_separators();
+        let flag = match self.d.get(self.offset) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(self.error()),
+        };
+        self.offset += 1;
+        Ok(flag)
+    }
+}
+
+/// The control point of a smooth curve, the previous control point mirrored around `point`
+fn reflect(control: Option<Point>, point: Point) -> Point {
+    match control {
+        Some(control) => (2.0 * point.0 - control.0, 2.0 * point.1 - control.1),
+        None => point,
+    }
+}
+
+/// The quadratic curve from `from` as the cubic curve that draws it exactly
+fn quadratic(from: Point, control: Point, to: Point) -> Cubic {
+    Cubic {
+        c1: lerp(from, control, 2.0 / 3.0),
+        c2: lerp(to, control, 2.0 / 3.0),
+        to,
+    }
+}
+
+/// An elliptical arc as cubic curves of at most a quarter turn each, converted as in
+/// <https://www.w3.org/TR/SVG2/implnote.html#ArcConversionEndpointToCenter>
+fn arc(
+    from: Point,
+    (rx, ry): Point,
+    rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+    to: Point,
+) -> Vec<Cubic> {
+    if from == to {
+        return Vec::new();
+    }
+    let (mut rx, mut ry) = (rx.abs(), ry.abs());
+    if rx == 0.0 || ry == 0.0 {
+        return vec![Cubic::line(from, to)];
+    }
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let (dx, dy) = ((from.0 - to.0) / 2.0, (from.1 - to.1) / 2.0);
+    let (x1, y1) = (cos * dx + sin * dy, -sin * dx + cos * dy);
+
+    // Radii too small to reach the end are scaled up
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let coefficient = sign * (numerator / denominator).max(0.0).sqrt();
+    let (cx1, cy1) = (coefficient * rx * y1 / ry, -coefficient * ry * x1 / rx);
+    let center = (
+        cos * cx1 - sin * cy1 + (from.0 + to.0) / 2.0,
+        sin * cx1 + cos * cy1 + (from.1 + to.1) / 2.0,
+    );
+
+    let angle = |(ux, uy): Point, (vx, vy): Point| (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+    let start = ((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let end = ((-x1 - cx1) / rx, (-y1 - cy1) / ry);
+    let start_angle = angle((1.0, 0.0), start);
+    let mut sweep_angle = angle(start, end);
+    if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= TAU;
+    } else if sweep && sweep_angle < 0.0 {
+        sweep_angle += TAU;
+    }
+
+    // A point on the unit circle mapped onto the ellipse
+    let on_ellipse = |(x, y): Point| {
+        (
+            center.0 + rx * x * cos - ry * y * sin,
+            center.1 + rx * x * sin + ry * y * cos,
+        )
+    };
+    let segments = (sweep_angle.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = sweep_angle / segments as f64;
+    let handle = 4.0 / 3.0 * (step / 4.0).tan();
+    (0..segments)
+        .map(|segment| {
+            let (sin1, cos1) = (start_angle + step * segment as f64).sin_cos();
+            let (sin2, cos2) = (start_angle + step * (segment + 1) as f64).sin_cos();
+            Cubic {
+                c1: on_ellipse((cos1 - handle * sin1, sin1 + handle * cos1)),
+                c2: on_ellipse((cos2 + handle * sin2, sin2 - handle * cos2)),
+                to: match segment + 1 == segments {
+                    true => to,
+                    false => on_ellipse((cos2, sin2)),
+                },
+            }
+        })
+        .collect()
+}
+
+/// Morphs one path into another. Both paths are first brought to the same structure without
+/// changing their shape: subpaths that only one of them has grow from the center of the subpath
+/// they become, the longest curves are split until both sides have as many, and closed shapes
+/// start at the points that lie closest together. Then the points of the curves are interpolated.
+/// An open and a closed subpath switch halfway.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::path::{tween_path, Path};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let square: Path = "M 0 0 H 10 V 10 H 0 Z".parse().unwrap();
+/// let diamond: Path = "M 5 0 L 10 5 L 5 10 L 0 5 Z".parse().unwrap();
+/// let halfway = tween_path(&square, &diamond, 0.5);
+/// let expected: Path = "M 2.5 0 L 10 2.5 L 7.5 10 L 0 7.5 Z".parse().unwrap();
+/// assert_eq!(halfway.to_d(), expected.to_d());
+///
+/// let (expanded, set_expanded) = signal(false);
+/// let icon = AnimatedSignal::new(
+///     move || if expanded.get() { diamond.clone() } else { square.clone() }.into(),
+///     tween_path,
+/// );
+/// # let _html =
+/// view! {
+///     <svg viewBox="0 0 10 10">
+///         <path d=move || icon.get().to_d() />
+///     </svg>
+/// }
+/// # .to_html();
+/// ```
+pub fn tween_path(from: &Path, to: &Path, progress: f64) -> Path {
+    let (from, to) = from.align(to);
+    let subpaths = from
+        .iter()
+        .zip(&to)
+        .map(|(from, to)| Subpath {
+            start: lerp(from.start, to.start, progress),
+            curves: from
+                .curves
+                .iter()
+                .zip(&to.curves)
+                .map(|(from, to)| Cubic {
+                    c1: lerp(from.c1, to.c1, progress),
+                    c2: lerp(from.c2, to.c2, progress),
+                    to: lerp(from.to, to.to, progress),
+                })
+                .collect(),
+            closed: match progress < 0.5 {
+                true => from.closed,
+                false => to.closed,
+            },
+        })
+        .collect();
+    Path { subpaths }
+}
+
+impl Tween for Path {
+    type Interpolated = Path;
+
+    fn tween(&self, other: &Self, progress: f64) -> Path {
+        tween_path(self, other, progress)
+    }
+}
+
+impl Add for Path {
+    type Output = Path;
+
+    fn add(self, rhs: Path) -> Path {
+        self.zip(&rhs, |a, b| a + b)
+    }
+}
+
+impl Sub for Path {
+    type Output = Path;
+
+    fn sub(self, rhs: Path) -> Path {
+        self.zip(&rhs, |a, b| a - b)
+    }
+}
+
+impl Mul<f64> for Path {
+    type Output = Path;
+
+    fn mul(self, rhs: f64) -> Path {
+        self.zip(&self, |a, _| a * rhs)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// A command of path data with whole coordinates, which are written without rounding
+    fn command() -> impl Strategy<Value = String> {
+        let coordinate = || -1000..1000i32;
+        prop_oneof![
+            (coordinate(), coordinate()).prop_map(|(x, y)| format!("L{x} {y}")),
+            (coordinate(), coordinate()).prop_map(|(x, y)| format!("l{x},{y}")),
+            coordinate().prop_map(|x| format!("H{x}")),
+            coordinate().prop_map(|y| format!("v{y}")),
+            (
+                coordinate(),
+                coordinate(),
+                coordinate(),
+                coordinate(),
+                coordinate(),
+                coordinate()
+            )
+                .prop_map(|(a, b, c, d, x, y)| format!("C{a} {b} {c} {d} {x} {y}")),
+            Just("Z".to_string()),
+        ]
+    }
+
+    fn path_data() -> impl Strategy<Value = String> {
+        (
+            -1000..1000i32,
+            -1000..1000i32,
+            prop::collection::vec(command(), 0..8),
+        )
+            .prop_map(|(x, y, commands)| format!("M{x} {y}{}", commands.concat()))
+    }
+
+    proptest! {
+        #[test]
+        fn written_paths_read_back(d in path_data()) {
+            // Lines turn into curves whose control points are rounded when they are written
+            let written = Path::parse(&d).unwrap().to_d();
+            prop_assert_eq!(Path::parse(&written).map(|path| path.to_d()), Ok(written));
+        }
+
+        #[test]
+        fn any_text_parses_without_panicking(d in "[MmLlHhVvCcSsQqTtAaZz0-9.,e +-]{0,40}") {
+            let _ = Path::parse(&d);
+        }
+
+        #[test]
+        fn tweens_end_on_the_target(from in path_data(), to in path_data()) {
+            let from = Path::parse(&from).unwrap();
+            let to = Path::parse(&to).unwrap();
+            let end = tween_path(&from, &to, 1.0);
+            // The target may have been split into more curves to match the other path
+            prop_assert_eq!(tween_path(&end, &to, 0.0).to_d(), end.to_d());
+            prop_assert_eq!(end.subpaths.len(), from.subpaths.len().max(to.subpaths.len()));
+        }
+    }
+}
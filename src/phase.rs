@@ -0,0 +1,84 @@
+//! Phase offsets for loops that many elements share, such as the dots of a loading indicator that
+//! bounce one after the other.
+
+use std::time::Duration;
+
+#[cfg(not(feature = "disable-animations"))]
+use instant::Instant;
+#[cfg(not(feature = "disable-animations"))]
+use leptos::prelude::*;
+
+use crate::AnimatedSignal;
+#[cfg(not(feature = "disable-animations"))]
+use crate::RepeatMode;
+
+impl<T, I> AnimatedSignal<T, I> {
+    /// Plays the infinitely repeating animations of this signal `offset` ahead on a loop that all
+    /// signals of the context share. Their repetitions are counted from the start of the context
+    /// rather than from when they started, so signals that loop with the same duration stay in
+    /// step no matter when they were created or retargeted, and the offset shifts them in phase by
+    /// a fixed amount, like a negative `animation-delay` in CSS. A loop joins the shared one at
+    /// the point it has reached, after the delay of its target has passed.
+    ///
+    /// Animations that play a counted number of times are not affected.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (loading, set_loading) = signal(false);
+    /// // One bounce for all dots
+    /// let bounce = move || match loading.get() {
+    ///     true => AnimationTarget::from((-8.0, Duration::from_millis(300), easing::SINE_IN_OUT))
+    ///         .repeat(RepeatMode::AlternateInfinite),
+    ///     false => 0.0.into(),
+    /// };
+    /// // Every dot a sixth of the loop behind the one before it, so a wave runs across them
+    /// let dots: Vec<AnimatedSignal<f64, f64>> = (0..3)
+    ///     .map(|dot| {
+    ///         AnimatedSignal::new(bounce, tween_default)
+    ///             .with_phase_offset(Duration::from_millis(100 * (2 - dot)))
+    ///     })
+    ///     .collect();
+    /// set_loading.set(true);
+    /// # let _html =
+    /// view! {
+    ///     {dots
+    ///         .into_iter()
+    ///         .map(|y| view! { <span class="dot" style:translate=move || format!("0 {}px", y.get())></span> })
+    ///         .collect_view()}
+    /// }
+    /// # .to_html();
+    /// ```
+    pub fn with_phase_offset(self, offset: Duration) -> Self {
+        // Signals without animations have nothing to apply the setting to
+        #[cfg(not(feature = "disable-animations"))]
+        self.phase_offset.set_value(Some(offset));
+        #[cfg(feature = "disable-animations")]
+        let _ = offset;
+        self
+    }
+}
+
+/// The start of an animation that would otherwise start at `start`, moved to the start of the
+/// current repetition of the shared loop that runs `offset` ahead of `epoch`
+#[cfg(not(feature = "disable-animations"))]
+pub(crate) fn shared_loop_start(
+    start: Instant,
+    epoch: Instant,
+    offset: Duration,
+    duration: Duration,
+    repeat: RepeatMode,
+) -> Instant {
+    if repeat.count().is_some() || duration.is_zero() {
+        return start;
+    }
+    // Alternating loops go back and forth in step too
+    let period = match repeat.alternates() {
+        true => duration * 2,
+        false => duration,
+    };
+    let position = start.saturating_duration_since(epoch) + offset;
+    let into_repetition = Duration::from_nanos((position.as_nanos() % period.as_nanos()) as u64);
+    start.checked_sub(into_repetition).unwrap_or(start)
+}
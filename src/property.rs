@@ -0,0 +1,133 @@
+//! Arbitration between several sources that want to animate the same value.
+
+use leptos::prelude::*;
+
+use crate::animation_target::{AnimationSource, LocalSignalSource};
+use crate::AnimationTarget;
+
+type InputSource<T> = Signal<Option<AnimationTarget<T>>, LocalStorage>;
+
+/// A value that is written by several prioritized inputs, such as a card that is scaled up on hover,
+/// follows the pointer while being dragged and can be reset programmatically.
+///
+/// Every input either claims the value with a target or is released (`None`). The input with the
+/// highest priority that claims the value owns it, and when it is released ownership goes back to the
+/// next one, which makes the value animate back to that input's target. If no input claims the value
+/// the base source is used. Inputs with the same priority are resolved in favour of the one added last.
+///
+/// An `AnimatedProperty` can be used anywhere an [`AnimationSource`] is expected.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::property::AnimatedProperty;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (hovered, set_hovered) = signal(false);
+///
+/// let scale = AnimatedProperty::new(move || 1.0.into())
+///     .with_input(1, move || hovered.get().then(|| 1.1.into()));
+/// let pressed = scale.input(2);
+///
+/// let animated_scale = AnimatedSignal::new(scale, tween_default);
+///
+/// set_hovered.set(true);
+/// pressed.set(0.9);
+/// assert_eq!(scale.target().get_untracked().target, 0.9);
+///
+/// // Releasing the pressed input gives control back to the hover input
+/// pressed.release();
+/// assert_eq!(scale.target().get_untracked().target, 1.1);
+/// ```
+pub struct AnimatedProperty<T: 'static> {
+    base: Signal<AnimationTarget<T>, LocalStorage>,
+    inputs: RwSignal<Vec<(i32, InputSource<T>)>, LocalStorage>,
+}
+
+impl<T> Clone for AnimatedProperty<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for AnimatedProperty<T> {}
+
+impl<T: Clone + 'static> AnimatedProperty<T> {
+    /// Creates a property that uses `base` whenever no input claims the value
+    pub fn new<Marker>(base: impl AnimationSource<T, Marker>) -> AnimatedProperty<T> {
+        AnimatedProperty {
+            base: base.into_signal(),
+            inputs: RwSignal::new_local(Vec::new()),
+        }
+    }
+
+    /// Adds a reactive input that claims the value whenever `source` returns a target
+    pub fn with_input(
+        self,
+        priority: i32,
+        source: impl Fn() -> Option<AnimationTarget<T>> + 'static,
+    ) -> Self {
+        self.add_input(priority, Signal::derive_local(source));
+        self
+    }
+
+    /// Adds an input that is set and released imperatively, for example from event handlers
+    pub fn input(&self, priority: i32) -> PropertyInput<T> {
+        let target = RwSignal::new_local(None);
+        self.add_input(priority, target.into());
+        PropertyInput { target }
+    }
+
+    fn add_input(&self, priority: i32, source: InputSource<T>) {
+        self.inputs.update(|inputs| {
+            // Keep the inputs sorted by priority, with later inputs before earlier ones of the same priority
+            let index = inputs.partition_point(|(other, _)| *other > priority);
+            inputs.insert(index, (priority, source));
+        });
+    }
+
+    /// The target of the input that currently owns the value
+    pub fn target(&self) -> Signal<AnimationTarget<T>, LocalStorage> {
+        let this = *self;
+        Signal::derive_local(move || {
+            this.inputs
+                .with(|inputs| inputs.iter().find_map(|(_, source)| source.get()))
+                .unwrap_or_else(|| this.base.get())
+        })
+    }
+}
+
+impl<T: Clone + 'static> AnimationSource<T, LocalSignalSource> for AnimatedProperty<T> {
+    fn into_signal(self) -> Signal<AnimationTarget<T>, LocalStorage> {
+        self.target()
+    }
+}
+
+/// An input of an [`AnimatedProperty`] that is set and released imperatively
+pub struct PropertyInput<T: 'static> {
+    target: RwSignal<Option<AnimationTarget<T>>, LocalStorage>,
+}
+
+impl<T> Clone for PropertyInput<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PropertyInput<T> {}
+
+impl<T: 'static> PropertyInput<T> {
+    /// Claims the value with a new target
+    pub fn set(&self, target: impl Into<AnimationTarget<T>>) {
+        self.target.set(Some(target.into()));
+    }
+
+    /// Gives up the value so lower priority inputs own it again
+    pub fn release(&self) {
+        self.target.set(None);
+    }
+
+    /// Whether this input currently claims the value. It may still be overruled by an input
+    /// with a higher priority.
+    pub fn is_set(&self) -> bool {
+        self.target.with(Option::is_some)
+    }
+}
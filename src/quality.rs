@@ -0,0 +1,160 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use instant::Instant;
+use leptos::prelude::*;
+
+use crate::{easing, AnimationContext, Easing};
+#[cfg(not(feature = "disable-animations"))]
+use crate::{AnimationMode, AnimationTarget};
+
+/// The quality tier the animation system is currently running at, see [`AdaptiveQuality`]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum QualityTier {
+    /// Animations play exactly as requested
+    #[default]
+    High,
+
+    /// Recent frames exceeded the frame budget. Easings are replaced by a cheaper easing and the
+    /// number of overlapping animations per signal is capped
+    Reduced,
+
+    /// Recent frames exceeded twice the frame budget. New animations snap directly to their target
+    Minimal,
+}
+
+/// An optional policy that degrades animations when recent frames took longer than the frame budget.
+/// Enable it with [`AnimationContext::set_adaptive_quality()`] and read the current tier with
+/// [`AnimationContext::quality_tier()`] to adapt other parts of the application as well.
+///
+/// The tier is based on the average duration of the most recent consecutive animation frames and
+/// only affects animations that are started after the tier changed.
+#[derive(Clone, Debug)]
+pub struct AdaptiveQuality {
+    /// The time a single frame is allowed to take. Defaults to 20ms, a little more than a 60fps frame
+    pub frame_budget: Duration,
+
+    /// The number of recent frames that are averaged. Defaults to 30
+    pub sample_frames: usize,
+
+    /// The easing that replaces the requested easing in the [`Reduced`](QualityTier::Reduced) tier. Defaults to [`LINEAR`](easing::LINEAR)
+    pub reduced_easing: Easing,
+
+    /// The maximum number of overlapping animations per signal in the [`Reduced`](QualityTier::Reduced) tier.
+    /// Animations started beyond this limit replace the target of the latest animation instead. Defaults to 2
+    pub reduced_max_concurrent: usize,
+}
+
+impl Default for AdaptiveQuality {
+    fn default() -> Self {
+        AdaptiveQuality {
+            frame_budget: Duration::from_millis(20),
+            sample_frames: 30,
+            reduced_easing: easing::LINEAR,
+            reduced_max_concurrent: 2,
+        }
+    }
+}
+
+impl AdaptiveQuality {
+    fn tier(&self, frame_times: &VecDeque<Duration>) -> QualityTier {
+        if frame_times.is_empty() {
+            return QualityTier::High;
+        }
+        let average = frame_times.iter().sum::<Duration>() / frame_times.len() as u32;
+        if average > self.frame_budget * 2 {
+            QualityTier::Minimal
+        } else if average > self.frame_budget {
+            QualityTier::Reduced
+        } else {
+            QualityTier::High
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct QualityState {
+    policy: Option<AdaptiveQuality>,
+    frame_times: VecDeque<Duration>,
+    last_frame: Option<Instant>,
+}
+
+impl AnimationContext {
+    /// Enables or disables (with `None`) the [`AdaptiveQuality`] policy for this context.
+    /// Disabling the policy resets the tier to [`High`](QualityTier::High).
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimationContext, quality::{AdaptiveQuality, QualityTier}};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = AnimationContext::provide();
+    /// context.set_adaptive_quality(Some(AdaptiveQuality::default()));
+    ///
+    /// // Skip the decorative background animation on slow devices
+    /// let show_background = Signal::derive(move || context.quality_tier().get() == QualityTier::High);
+    /// ```
+    pub fn set_adaptive_quality(&self, policy: Option<AdaptiveQuality>) {
+        let disabled = policy.is_none();
+        self.quality.update_value(|quality| {
+            quality.policy = policy;
+            quality.frame_times.clear();
+        });
+        if disabled {
+            self.quality_tier.set(QualityTier::High);
+        }
+    }
+
+    /// The current [`QualityTier`]. Always [`High`](QualityTier::High) unless an [`AdaptiveQuality`] policy is set.
+    pub fn quality_tier(&self) -> Signal<QualityTier> {
+        self.quality_tier.into()
+    }
+
+    /// Records the time between consecutive animation frames and updates the quality tier
+    pub(crate) fn record_frame(&self, consecutive: bool) {
+        let now = self.real_now();
+        let tier = self.quality.try_update_value(|quality| {
+            let last_frame = quality.last_frame.replace(now);
+            let policy = quality.policy.as_ref()?;
+
+            if let (true, Some(last_frame)) = (consecutive, last_frame) {
+                quality.frame_times.push_back(now - last_frame);
+                while quality.frame_times.len() > policy.sample_frames {
+                    quality.frame_times.pop_front();
+                }
+            }
+            Some(policy.tier(&quality.frame_times))
+        });
+
+        if let Some(Some(tier)) = tier {
+            if tier != self.quality_tier.get_untracked() {
+                self.quality_tier.set(tier);
+            }
+        }
+    }
+
+    /// Applies the current quality tier to a newly started animation
+    #[cfg(not(feature = "disable-animations"))]
+    pub(crate) fn adapt_target<T>(
+        &self,
+        mut target: AnimationTarget<T>,
+        running_animations: usize,
+    ) -> AnimationTarget<T> {
+        let Some(policy) = self.quality.with_value(|quality| quality.policy.clone()) else {
+            return target;
+        };
+        match self.quality_tier.get_untracked() {
+            QualityTier::High => {}
+            QualityTier::Reduced => {
+                target.easing = policy.reduced_easing;
+                if matches!(
+                    target.mode,
+                    AnimationMode::Start | AnimationMode::ReplaceWithVelocity
+                ) && running_animations >= policy.reduced_max_concurrent
+                {
+                    target.mode = AnimationMode::ReplaceOrStart;
+                }
+            }
+            QualityTier::Minimal => target.mode = AnimationMode::Snap,
+        }
+        target
+    }
+}
@@ -0,0 +1,252 @@
+//! Rotations in 3D for canvas and WebGL scenes, as quaternions that are animated along the arc
+//! between two orientations.
+
+use std::ops::{Add, Mul, Sub};
+
+use crate::css::format_number;
+use crate::style::Deg;
+use crate::tween::Tween;
+
+/// The rotations closer together than this are mixed linearly, where the arc is a straight line
+const SLERP_THRESHOLD: f64 = 0.9995;
+
+/// A rotation in 3D as a unit quaternion `w + xi + yj + zk`.
+///
+/// Mixing the components of two rotations, or the angles of Euler rotations, takes a wrong path
+/// in between: the object wobbles, speeds up and slows down on the way, and can shrink when the
+/// quaternion is applied as a matrix. Animate rotations with [`tween_quat`] instead, which turns
+/// at a constant speed around a single axis along the shorter arc.
+/// ```
+/// # use leptos_animation::quat::Quat;
+/// # use leptos_animation::style::Deg;
+/// let quarter = Quat::from_axis_angle([0.0, 0.0, 1.0], Deg(90.0));
+/// let [x, y, z] = quarter.rotate([1.0, 0.0, 0.0]);
+/// assert!(x.abs() < 1e-9 && (y - 1.0).abs() < 1e-9 && z.abs() < 1e-9);
+/// assert_eq!(quarter.to_css(), "rotate3d(0, 0, 1, 90deg)");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quat {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Quat::IDENTITY
+    }
+}
+
+impl Quat {
+    /// The rotation that leaves everything in place
+    pub const IDENTITY: Quat = Quat::new(0.0, 0.0, 0.0, 1.0);
+
+    /// The quaternion with the given components, which should have a length of 1 to be a rotation,
+    /// see [`normalize()`](Quat::normalize)
+    pub const fn new(x: f64, y: f64, z: f64, w: f64) -> Quat {
+        Quat { x, y, z, w }
+    }
+
+    /// The rotation by `angle` around `axis`, counterclockwise when looking against the axis. The
+    /// axis doesn't need to have a length of 1, an axis of zero length is no rotation.
+    pub fn from_axis_angle(axis: [f64; 3], Deg(angle): Deg) -> Quat {
+        let [x, y, z] = axis;
+        let length = (x * x + y * y + z * z).sqrt();
+        if length == 0.0 || !length.is_finite() {
+            return Quat::IDENTITY;
+        }
+        let (sin, cos) = (angle.to_radians() / 2.0).sin_cos();
+        let scale = sin / length;
+        Quat::new(x * scale, y * scale, z * scale, cos)
+    }
+
+    /// The rotation by `x` around the X axis, then `y` around the Y axis, then `z` around the
+    /// Z axis
+    pub fn from_euler(x: Deg, y: Deg, z: Deg) -> Quat {
+        Quat::from_axis_angle([0.0, 0.0, 1.0], z)
+            * Quat::from_axis_angle([0.0, 1.0, 0.0], y)
+            * Quat::from_axis_angle([1.0, 0.0, 0.0], x)
+    }
+
+    /// The axis with a length of 1 and the angle of the rotation, the angle lies in 0° - 360°.
+    /// The identity rotation turns around the X axis by 0°.
+    pub fn axis_angle(&self) -> ([f64; 3], Deg) {
+        let q = self.normalize();
+        let sin = (q.x * q.x + q.y * q.y + q.z * q.z).sqrt();
+        if sin < 1e-12 {
+            return ([1.0, 0.0, 0.0], Deg(0.0));
+        }
+        let angle = 2.0 * sin.atan2(q.w);
+        ([q.x / sin, q.y / sin, q.z / sin], Deg(angle.to_degrees()))
+    }
+
+    /// The dot product of the two quaternions, the cosine of half the angle between two rotations
+    pub fn dot(&self, other: &Quat) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    /// The length of the quaternion, 1 for rotations
+    pub fn length(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// The quaternion scaled to a length of 1, the identity for a quaternion of zero length
+    pub fn normalize(&self) -> Quat {
+        let length = self.length();
+        if length == 0.0 || !length.is_finite() {
+            return Quat::IDENTITY;
+        }
+        *self * (1.0 / length)
+    }
+
+    /// The rotation that undoes this one
+    pub fn inverse(&self) -> Quat {
+        let q = self.normalize();
+        Quat::new(-q.x, -q.y, -q.z, q.w)
+    }
+
+    /// Rotates the vector `[x, y, z]`
+    pub fn rotate(&self, vector: [f64; 3]) -> [f64; 3] {
+        let q = self.normalize();
+        let rotated = q * Quat::new(vector[0], vector[1], vector[2], 0.0) * q.inverse();
+        [rotated.x, rotated.y, rotated.z]
+    }
+
+    /// The rotation as a column major 4x4 matrix, the layout that WebGL's `uniformMatrix4fv`
+    /// expects
+    pub fn to_matrix(&self) -> [f32; 16] {
+        let Quat { x, y, z, w } = self.normalize();
+        [
+            1.0 - 2.0 * (y * y + z * z),
+            2.0 * (x * y + z * w),
+            2.0 * (x * z - y * w),
+            0.0,
+            2.0 * (x * y - z * w),
+            1.0 - 2.0 * (x * x + z * z),
+            2.0 * (y * z + x * w),
+            0.0,
+            2.0 * (x * z + y * w),
+            2.0 * (y * z - x * w),
+            1.0 - 2.0 * (x * x + y * y),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        ]
+        .map(|value| value as f32)
+    }
+
+    /// The rotation as a CSS `rotate3d()` function, for the `transform` property
+    pub fn to_css(&self) -> String {
+        let ([x, y, z], Deg(angle)) = self.axis_angle();
+        let [x, y, z, angle] = [x, y, z, angle].map(format_number);
+        format!("rotate3d({x}, {y}, {z}, {angle}deg)")
+    }
+}
+
+/// Applies `rhs` before `self`, like multiplying their rotation matrices
+impl Mul for Quat {
+    type Output = Quat;
+
+    fn mul(self, rhs: Quat) -> Quat {
+        Quat::new(
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+        )
+    }
+}
+
+/// Spherical linear interpolation between two rotations: the rotation turns at a constant speed
+/// around a single axis, the shorter way around. Rotations that are almost the same are mixed
+/// linearly and normalized, which is indistinguishable and avoids dividing by almost zero.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::quat::{tween_quat, Quat};
+/// # use leptos_animation::style::Deg;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let resting = Quat::IDENTITY;
+/// let flipped = Quat::from_axis_angle([0.0, 1.0, 0.0], Deg(180.0));
+/// // Halfway the card has turned by 90° around the same axis
+/// let halfway = tween_quat(&resting, &flipped, 0.5);
+/// assert_eq!(halfway.to_css(), "rotate3d(0, 1, 0, 90deg)");
+///
+/// let (flip, set_flip) = signal(false);
+/// let card = AnimatedSignal::new(
+///     move || if flip.get() { flipped } else { resting }.into(),
+///     tween_quat,
+/// );
+/// // Upload the rotation to a shader on every frame
+/// Effect::new(move || {
+///     let matrix: [f32; 16] = card.get().to_matrix();
+/// #   let _ = matrix;
+/// });
+/// ```
+pub fn tween_quat(from: &Quat, to: &Quat, progress: f64) -> Quat {
+    let from = from.normalize();
+    let mut to = to.normalize();
+
+    // A quaternion and its negation are the same rotation, take the one on the shorter arc
+    let mut cos = from.dot(&to);
+    if cos < 0.0 {
+        to = to * -1.0;
+        cos = -cos;
+    }
+    if cos > SLERP_THRESHOLD {
+        return (from + (to - from) * progress).normalize();
+    }
+
+    let angle = cos.acos();
+    let sin = angle.sin();
+    let from_weight = ((1.0 - progress) * angle).sin() / sin;
+    let to_weight = (progress * angle).sin() / sin;
+    from * from_weight + to * to_weight
+}
+
+impl Tween for Quat {
+    type Interpolated = Quat;
+
+    fn tween(&self, other: &Self, progress: f64) -> Quat {
+        tween_quat(self, other, progress)
+    }
+}
+
+// The sums of additive animations are no unit quaternions, the rotations made of them normalize
+// them again
+impl Add for Quat {
+    type Output = Quat;
+
+    fn add(self, rhs: Quat) -> Quat {
+        Quat::new(
+            self.x + rhs.x,
+            self.y + rhs.y,
+            self.z + rhs.z,
+            self.w + rhs.w,
+        )
+    }
+}
+
+impl Sub for Quat {
+    type Output = Quat;
+
+    fn sub(self, rhs: Quat) -> Quat {
+        Quat::new(
+            self.x - rhs.x,
+            self.y - rhs.y,
+            self.z - rhs.z,
+            self.w - rhs.w,
+        )
+    }
+}
+
+impl Mul<f64> for Quat {
+    type Output = Quat;
+
+    fn mul(self, rhs: f64) -> Quat {
+        Quat::new(self.x * rhs, self.y * rhs, self.z * rhs, self.w * rhs)
+    }
+}
@@ -0,0 +1,248 @@
+use std::ops::Range;
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use crate::style::Deg;
+use crate::AnimationContext;
+
+/// Spreads the streams of [`Randomness::sample()`] apart, so neighbouring streams and indices
+/// don't share samples
+const STREAM_SPREAD: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// The `Randomness` configuration determines the random numbers used by randomized effects such
+/// as [wiggles](AnimationContext::wiggle), the jitter of staggered text with
+/// [`GlyphText::with_jitter()`](crate::text::GlyphText::with_jitter) and
+/// [`LineReveal::with_jitter()`](crate::text::LineReveal::with_jitter), and
+/// [confetti](AnimationContext::confetti). By default every `AnimationContext` is seeded differently,
+/// set an explicit seed with [`AnimationContext::set_randomness()`] to make visual tests and replays
+/// reproducible.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Randomness {
+    seed: u64,
+}
+
+impl Randomness {
+    /// A configuration that always produces the same random numbers
+    pub const fn seeded(seed: u64) -> Randomness {
+        Randomness { seed }
+    }
+
+    /// A configuration with a seed derived from the current time
+    pub fn from_time() -> Randomness {
+        Randomness::seeded(instant::now().to_bits())
+    }
+
+    /// The seed of this configuration
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Creates an independent generator for the given `stream`. Generators with the same seed and
+    /// stream always produce the same numbers, regardless of the order in which they are created.
+    /// This makes it possible to give for example every item in a list its own stable jitter by using
+    /// the index of the item as the stream.
+    pub fn generator(&self, stream: u64) -> Rng {
+        let mut rng = Rng {
+            state: self.seed ^ stream.wrapping_mul(0xD6E8_FEB8_6659_FD93),
+        };
+        rng.next_u64();
+        rng
+    }
+
+    /// The random value in `0.0..1.0` at `index` of `stream`, the same one no matter how often or
+    /// in which order it is asked for
+    pub(crate) fn sample(&self, stream: u64, index: u64) -> f64 {
+        self.generator(stream.wrapping_mul(STREAM_SPREAD).wrapping_add(index))
+            .next_f64()
+    }
+}
+
+impl Default for Randomness {
+    fn default() -> Self {
+        Randomness::from_time()
+    }
+}
+
+/// A small and fast pseudo random number generator (SplitMix64). Not suitable for cryptography.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Returns the next random `u64`
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random value in `0.0..1.0`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a random value within `range`
+    pub fn range(&mut self, range: Range<f64>) -> f64 {
+        range.start + self.next_f64() * (range.end - range.start)
+    }
+}
+
+impl AnimationContext {
+    /// Sets the [`Randomness`] of this context and restarts its random number generator
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimationContext, random::Randomness};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = AnimationContext::provide();
+    /// context.set_randomness(Randomness::seeded(42));
+    /// let first = context.random();
+    ///
+    /// context.set_randomness(Randomness::seeded(42));
+    /// assert_eq!(context.random(), first);
+    /// ```
+    pub fn set_randomness(&self, randomness: Randomness) {
+        self.randomness
+            .set_value((randomness, randomness.generator(0)));
+    }
+
+    /// The current [`Randomness`] configuration of this context
+    pub fn randomness(&self) -> Randomness {
+        self.randomness.with_value(|(randomness, _)| *randomness)
+    }
+
+    /// Returns the next random value in `0.0..1.0` from the generator of this context
+    pub fn random(&self) -> f64 {
+        self.randomness
+            .try_update_value(|(_, rng)| rng.next_f64())
+            .unwrap_or_default()
+    }
+
+    /// Returns a wiggle, a value that drifts smoothly between -1.0 and 1.0 in random steps,
+    /// `frequency` times per second, for idle motion such as a floating badge or a nervous
+    /// button. Wiggles of different `stream`s move independently, and the same seed and stream
+    /// always wiggle the same way.
+    ///
+    /// Animation frames keep being requested for as long as the returned signal is being read.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimationContext, random::Randomness};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = expect_context::<AnimationContext>();
+    /// context.set_randomness(Randomness::seeded(7));
+    ///
+    /// let wiggle = context.wiggle(2.0, 0);
+    /// # let _html =
+    /// view! {
+    ///     <span style:transform=move || format!("rotate({}deg)", wiggle.get() * 3.0)>"New"</span>
+    /// }
+    /// # .to_html();
+    /// ```
+    pub fn wiggle(&self, frequency: f64, stream: u64) -> Signal<f64> {
+        let context = *self;
+        Signal::derive(move || {
+            context.animation_frame.track();
+            context.request_animation_frame();
+
+            let randomness = context.randomness();
+            let steps = (context.now() - context.epoch).as_secs_f64() * frequency.max(0.0);
+            let step = steps.floor();
+            let [from, to] = [step, step + 1.0].map(|step| randomness.sample(stream, step as u64));
+            // Ease between the random steps, so the wiggle never changes direction abruptly
+            let progress = (1.0 - (steps.fract() * std::f64::consts::PI).cos()) / 2.0;
+            (from + (to - from) * progress) * 2.0 - 1.0
+        })
+    }
+
+    /// Returns `count` pieces of confetti with random launch directions, speeds, spins and hues,
+    /// for a burst that is animated with the time since it was fired, see [`ConfettiPiece`]. The
+    /// same seed and stream always give the same burst.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimationContext, random::Randomness};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = expect_context::<AnimationContext>();
+    /// context.set_randomness(Randomness::seeded(7));
+    ///
+    /// let burst = context.confetti(40, 0);
+    /// assert_eq!(burst, context.confetti(40, 0));
+    /// assert_ne!(burst, context.confetti(40, 1));
+    /// ```
+    pub fn confetti(&self, count: usize, stream: u64) -> Vec<ConfettiPiece> {
+        let mut rng = self.randomness().generator(stream);
+        (0..count)
+            .map(|_| ConfettiPiece {
+                // Mostly upwards, fanning out to both sides
+                direction: Deg(rng.range(-60.0..60.0)),
+                speed: rng.range(300.0..900.0),
+                spin: rng.range(-720.0..720.0),
+                hue: rng.range(0.0..360.0),
+            })
+            .collect()
+    }
+}
+
+/// A piece of confetti of a burst from [`AnimationContext::confetti()`], launched from the origin
+/// of the burst and pulled down by gravity
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConfettiPiece {
+    /// The direction in which the piece is launched, clockwise from straight up
+    pub direction: Deg,
+    /// The launch speed in pixels per second
+    pub speed: f64,
+    /// The rotation of the piece in degrees per second
+    pub spin: f64,
+    /// The hue of the piece in degrees, for an `hsl()` color
+    pub hue: f64,
+}
+
+impl ConfettiPiece {
+    /// The offset of the piece from the origin of the burst in pixels `elapsed` after it was
+    /// fired, with screen coordinates where y points down, under `gravity` in pixels per second
+    /// squared
+    pub fn position(&self, elapsed: Duration, gravity: f64) -> (f64, f64) {
+        let t = elapsed.as_secs_f64();
+        let (sin, cos) = self.direction.0.to_radians().sin_cos();
+        (
+            sin * self.speed * t,
+            -cos * self.speed * t + gravity * t * t / 2.0,
+        )
+    }
+
+    /// The rotation of the piece `elapsed` after it was fired
+    pub fn rotation(&self, elapsed: Duration) -> Deg {
+        Deg(self.spin * elapsed.as_secs_f64())
+    }
+}
+
+#[cfg(all(test, not(feature = "disable-animations")))]
+mod tests {
+    use super::*;
+    use crate::testing::TestAnimationHarness;
+
+    #[test]
+    fn seeded_wiggles_repeat_and_stay_in_range() {
+        let harness = TestAnimationHarness::new();
+        let context = harness.context();
+        context.set_randomness(Randomness::seeded(3));
+        let wiggle = context.wiggle(4.0, 1);
+
+        let mut values = Vec::new();
+        for _ in 0..60 {
+            harness.step();
+            values.push(wiggle.get_untracked());
+        }
+        assert!(values.iter().all(|value| (-1.0..=1.0).contains(value)));
+        assert!(values.windows(2).any(|pair| pair[0] != pair[1]));
+
+        let other = context.wiggle(4.0, 2);
+        assert_ne!(wiggle.get_untracked(), other.get_untracked());
+        assert_eq!(
+            wiggle.get_untracked(),
+            context.wiggle(4.0, 1).get_untracked()
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::AnimatedSignal;
+
+/// An axis-aligned rectangle that can be animated with [`tween_default`](crate::tween_default)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub const fn new(x: f64, y: f64, width: f64, height: f64) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns true if the point lies within the rectangle. Rectangles with a negative width or
+    /// height are treated as if they extend in the opposite direction.
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        let (left, right) = min_max(self.x, self.x + self.width);
+        let (top, bottom) = min_max(self.y, self.y + self.height);
+        (left..=right).contains(&x) && (top..=bottom).contains(&y)
+    }
+}
+
+fn min_max(a: f64, b: f64) -> (f64, f64) {
+    (a.min(b), a.max(b))
+}
+
+impl Add for Rect {
+    type Output = Rect;
+
+    fn add(self, rhs: Rect) -> Rect {
+        Rect::new(
+            self.x + rhs.x,
+            self.y + rhs.y,
+            self.width + rhs.width,
+            self.height + rhs.height,
+        )
+    }
+}
+
+impl Sub for Rect {
+    type Output = Rect;
+
+    fn sub(self, rhs: Rect) -> Rect {
+        Rect::new(
+            self.x - rhs.x,
+            self.y - rhs.y,
+            self.width - rhs.width,
+            self.height - rhs.height,
+        )
+    }
+}
+
+impl Mul<f64> for Rect {
+    type Output = Rect;
+
+    fn mul(self, rhs: f64) -> Rect {
+        Rect::new(
+            self.x * rhs,
+            self.y * rhs,
+            self.width * rhs,
+            self.height * rhs,
+        )
+    }
+}
+
+/// An animated [`Rect`], with the position and size animated together
+pub type AnimatedRect = AnimatedSignal<Rect, Rect>;
+
+impl AnimatedRect {
+    /// Hit-tests a point against the rectangle at the exact time of the call, see
+    /// [`current_value()`](AnimatedSignal::current_value). Use this for pointer events on canvas UIs
+    /// where the rectangle may have moved since the last animation frame.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, rect::{AnimatedRect, Rect}, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (target, set_target) = signal(Rect::new(10.0, 10.0, 100.0, 50.0));
+    /// let button: AnimatedRect = AnimatedSignal::new(move || target.get().into(), tween_default);
+    ///
+    /// assert!(button.contains(60.0, 30.0));
+    /// assert!(!button.contains(5.0, 30.0));
+    /// ```
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        self.current_value().contains(x, y)
+    }
+}
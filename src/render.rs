@@ -0,0 +1,41 @@
+//! Binding animated signals directly in views, without wrapping them in closures.
+
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+
+use leptos::prelude::*;
+use leptos::tachys::reactive_graph::ReactiveFunction;
+
+use crate::AnimatedSignal;
+
+/// An animated signal can be used in a view wherever a closure can, such as a `style:` directive,
+/// an attribute or a text node. The value is written with its `Display` implementation, which the
+/// CSS values of this crate such as [`CssLength`](crate::length::CssLength) and
+/// [`Rgba`](crate::color::Rgba) write as CSS. Like with a closure, the DOM is only touched when the
+/// written value changes.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::length::{tween_length, CssLength};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (open, set_open) = signal(false);
+/// let opacity = AnimatedSignal::new(move || if open.get() { 1.0 } else { 0.0 }.into(), tween_default);
+/// let height = AnimatedSignal::new(
+///     move || if open.get() { CssLength::Rem(12.0) } else { CssLength::Px(0.0) }.into(),
+///     tween_length,
+/// );
+/// # let _html =
+/// view! { <div class="drawer" style:opacity=opacity style:height=height></div> }
+/// # .to_html();
+/// ```
+impl<T: 'static, I: Display + 'static> ReactiveFunction for AnimatedSignal<T, I> {
+    type Output = String;
+
+    fn invoke(&mut self) -> String {
+        self.animated_signal.with(ToString::to_string)
+    }
+
+    fn into_shared(mut self) -> Arc<Mutex<dyn FnMut() -> String + Send>> {
+        Arc::new(Mutex::new(move || self.invoke()))
+    }
+}
@@ -0,0 +1,268 @@
+//! Named animation scenarios defined as data, for showcasing motion in storybooks and demo pages.
+
+use std::rc::Rc;
+use std::time::Duration;
+
+use instant::Instant;
+use leptos::prelude::*;
+
+use crate::{AnimationContext, AnimationMode, AnimationTarget};
+
+/// A target of a [`Scenario`] and when it is set
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScenarioStep<T> {
+    /// The time since the start of the scenario at which the target is set
+    pub at: Duration,
+
+    /// The target that is set
+    pub target: AnimationTarget<T>,
+}
+
+/// A named sequence of targets for an animated signal, starting from an initial state.
+///
+/// Unlike a [`Timeline`](crate::timeline::Timeline), which plays animations itself, a scenario only
+/// feeds targets to an [`AnimatedSignal`](crate::AnimatedSignal) at fixed times, the way user
+/// interactions would. The signal animates them with its own tween and modes, so stories show the
+/// component exactly as it behaves in the application.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos_animation::*;
+/// # use leptos_animation::scenario::Scenario;
+/// let second = Duration::from_secs(1);
+/// let open_and_close = Scenario::new("open and close", 0.0)
+///     .at(Duration::ZERO, (100.0, second, easing::CUBIC_OUT))
+///     .at(second * 2, (0.0, second, easing::CUBIC_IN));
+/// assert_eq!(open_and_close.duration(), second * 2);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scenario<T> {
+    /// The name by which the scenario is played
+    pub name: String,
+
+    /// The value that the signal snaps to when the scenario starts
+    pub initial: T,
+
+    /// The targets that are set while the scenario plays, sorted by their time
+    pub steps: Vec<ScenarioStep<T>>,
+}
+
+impl<T> Scenario<T> {
+    /// A scenario without steps
+    pub fn new(name: impl Into<String>, initial: T) -> Scenario<T> {
+        Scenario {
+            name: name.into(),
+            initial,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Adds a step that sets `target` at time `at` after the start of the scenario
+    pub fn at(mut self, at: Duration, target: impl Into<AnimationTarget<T>>) -> Self {
+        let index = self.steps.partition_point(|step| step.at <= at);
+        self.steps.insert(
+            index,
+            ScenarioStep {
+                at,
+                target: target.into(),
+            },
+        );
+        self
+    }
+
+    /// The time of the last step, after which no more targets are set
+    pub fn duration(&self) -> Duration {
+        self.steps.last().map(|step| step.at).unwrap_or_default()
+    }
+}
+
+struct PlayerState {
+    /// The index of the scenario that is shown
+    scenario: usize,
+    /// When the scenario started, `None` while it shows its initial state
+    started: Option<Instant>,
+    /// Counts the starts, so restarting a scenario sets its targets again
+    run: u64,
+}
+
+/// Plays [`Scenario`]s by name, as the source of an [`AnimatedSignal`](crate::AnimatedSignal).
+///
+/// The player starts out showing the initial state of the first scenario. Playing a scenario snaps
+/// the signal to its initial state and then sets the targets of its steps at their times. A
+/// scenario can be restarted at any time, for example from a "replay" button of a story.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::scenario::{Scenario, ScenarioPlayer};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let second = Duration::from_secs(1);
+/// let player = ScenarioPlayer::new(vec![
+///     Scenario::new("expand", 0.0).at(Duration::ZERO, (200.0, second)),
+///     Scenario::new("collapse", 200.0).at(Duration::ZERO, (0.0, second)),
+/// ]);
+/// let height: AnimatedSignal<f64, f64> = AnimatedSignal::new(player.target(), tween_default);
+///
+/// assert_eq!(player.names(), ["expand", "collapse"]);
+/// assert!(player.play("collapse"));
+/// assert_eq!(player.current(), "collapse");
+/// # let _html =
+/// view! {
+///     <button on:click=move |_| player.restart()>"Replay"</button>
+///     <div style:height=move || format!("{}px", height.get())></div>
+/// }
+/// # .to_html();
+/// ```
+pub struct ScenarioPlayer<T: 'static> {
+    context: AnimationContext,
+    scenarios: StoredValue<Rc<[Scenario<T>]>, LocalStorage>,
+    state: StoredValue<PlayerState>,
+    changed: Trigger,
+    /// The run, the scenario and the latest step that has been reached
+    position: Memo<(u64, usize, Option<usize>)>,
+}
+
+impl<T> Clone for ScenarioPlayer<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ScenarioPlayer<T> {}
+
+impl<T: Clone + 'static> ScenarioPlayer<T> {
+    /// Creates a player for `scenarios`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scenarios` is empty.
+    pub fn new(scenarios: Vec<Scenario<T>>) -> ScenarioPlayer<T> {
+        assert!(
+            !scenarios.is_empty(),
+            "A ScenarioPlayer needs at least one scenario"
+        );
+        let context: AnimationContext = use_context().expect(
+            "No AnimationContext present, call AnimationContext::provide() in a parent scope",
+        );
+        let scenarios: StoredValue<Rc<[Scenario<T>]>, LocalStorage> =
+            StoredValue::new_local(scenarios.into());
+        let state = StoredValue::new(PlayerState {
+            scenario: 0,
+            started: None,
+            run: 0,
+        });
+        let changed = Trigger::new();
+
+        let position = Memo::new(move |previous: Option<&(u64, usize, Option<usize>)>| {
+            changed.track();
+            context.animation_frame.track();
+            let (scenario, started, run) = state
+                .try_with_value(|state| (state.scenario, state.started, state.run))
+                .unwrap_or((0, None, 0));
+            let Some(started) = started else {
+                return (run, scenario, None);
+            };
+            // Show the initial state for a frame before the first step, even if it is set right away
+            if previous.is_none_or(|(previous_run, ..)| *previous_run != run) {
+                context.request_animation_frame();
+                return (run, scenario, None);
+            }
+            let elapsed = context.now().saturating_duration_since(started);
+            let (reached, remaining) = scenarios
+                .try_with_value(|scenarios| {
+                    let steps = &scenarios[scenario].steps;
+                    let reached = steps.partition_point(|step| step.at <= elapsed);
+                    (reached, reached < steps.len())
+                })
+                .unwrap_or_default();
+            if remaining {
+                context.request_animation_frame();
+            }
+            (run, scenario, reached.checked_sub(1))
+        });
+
+        ScenarioPlayer {
+            context,
+            scenarios,
+            state,
+            changed,
+            position,
+        }
+    }
+
+    /// The target for the animated signal, to be used as its source
+    pub fn target(&self) -> Signal<AnimationTarget<T>, LocalStorage> {
+        let scenarios = self.scenarios;
+        let position = self.position;
+        Signal::derive_local(move || {
+            let (_, scenario, step) = position.get();
+            scenarios.with_value(|scenarios| {
+                let scenario = &scenarios[scenario];
+                match step {
+                    Some(step) => scenario.steps[step].target.clone(),
+                    None => (scenario.initial.clone(), AnimationMode::Snap).into(),
+                }
+            })
+        })
+    }
+
+    /// The names of all scenarios, in the order they were given
+    pub fn names(&self) -> Vec<String> {
+        self.scenarios.with_value(|scenarios| {
+            scenarios
+                .iter()
+                .map(|scenario| scenario.name.clone())
+                .collect()
+        })
+    }
+
+    /// The name of the scenario that is shown
+    pub fn current(&self) -> String {
+        let scenario = self.state.with_value(|state| state.scenario);
+        self.scenarios
+            .with_value(|scenarios| scenarios[scenario].name.clone())
+    }
+
+    /// Plays the scenario called `name` from its start, returns `false` if there is none
+    pub fn play(&self, name: &str) -> bool {
+        let Some(scenario) = self
+            .scenarios
+            .with_value(|scenarios| scenarios.iter().position(|scenario| scenario.name == name))
+        else {
+            return false;
+        };
+        self.start(scenario, Some(self.context.now()));
+        true
+    }
+
+    /// Plays the current scenario again from its start
+    pub fn restart(&self) {
+        let scenario = self.state.with_value(|state| state.scenario);
+        self.start(scenario, Some(self.context.now()));
+    }
+
+    /// Stops the current scenario and snaps back to its initial state
+    pub fn reset(&self) {
+        let scenario = self.state.with_value(|state| state.scenario);
+        self.start(scenario, None);
+    }
+
+    /// Whether all steps of the current scenario have been set
+    pub fn is_finished(&self) -> bool {
+        let (_, scenario, step) = self.position.get_untracked();
+        let started = self.state.with_value(|state| state.started.is_some());
+        let steps = self
+            .scenarios
+            .with_value(|scenarios| scenarios[scenario].steps.len());
+        started && step.map_or(0, |step| step + 1) == steps
+    }
+
+    fn start(&self, scenario: usize, started: Option<Instant>) {
+        self.state.update_value(|state| {
+            state.scenario = scenario;
+            state.started = started;
+            state.run += 1;
+        });
+        self.changed.notify();
+        self.context.request_animation_frame();
+    }
+}
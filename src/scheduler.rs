@@ -0,0 +1,387 @@
+//! Frame scheduling for an [`AnimationContext`](crate::AnimationContext).
+//!
+//! A context asks its [`Scheduler`] for a frame whenever animations are running, and the scheduler
+//! calls back once that frame has arrived. The scheduler is chosen when the context is created:
+//!
+//! - [`WebScheduler`] uses `window.request_animation_frame()` and is used by
+//!   [`AnimationContext::provide()`](crate::AnimationContext::provide)
+//! - [`CallbackScheduler`] hands frame requests to an external event loop and is used by
+//!   [`AnimationContext::provide_with_custom_request_animation_frame()`](crate::AnimationContext::provide_with_custom_request_animation_frame)
+//! - [`TimerScheduler`] uses `setTimeout()` at a fixed frame rate, for environments without
+//!   `requestAnimationFrame()` such as some webviews and worker contexts.
+//!   [`AnimationContext::provide()`](crate::AnimationContext::provide) falls back to it automatically.
+//! - [`WorkerScheduler`] uses `requestAnimationFrame()` of a dedicated worker, see the
+//!   [`worker`](crate::worker) module. [`AnimationContext::provide()`](crate::AnimationContext::provide)
+//!   picks it automatically inside workers.
+//! - [`ManualScheduler`] only runs frames when told to, for tests
+//!
+//! Other hosts (a Tauri webview, a worker rendering into an `OffscreenCanvas`) can implement the
+//! trait themselves and pass it to
+//! [`AnimationContext::provide_with_scheduler()`](crate::AnimationContext::provide_with_scheduler).
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use instant::Instant;
+use js_sys::{Function, Reflect};
+use leptos::wasm_bindgen::closure::Closure;
+use leptos::wasm_bindgen::{JsCast, JsValue};
+
+use crate::coordinator;
+
+/// The callback a [`Scheduler`] calls when a requested frame has arrived
+pub type FrameCallback = Box<dyn FnOnce()>;
+
+/// Decides when animation frames happen
+pub trait Scheduler: 'static {
+    /// Requests a frame and calls `frame` when it arrives. A context only has a single
+    /// frame request pending at any time, so this is not called again until `frame` was called
+    /// or the request was cancelled.
+    fn request_frame(&self, frame: FrameCallback);
+
+    /// Requests a frame that is not needed before `delay` has passed, for signals that animate
+    /// over minutes or hours and only update every few seconds. Like
+    /// [`request_frame()`](Scheduler::request_frame) it calls `frame` once and is cancelled with
+    /// [`cancel_frame()`](Scheduler::cancel_frame). Schedulers with timers wait without waking up
+    /// in between, the default requests a frame right away.
+    fn request_frame_after(&self, delay: Duration, frame: FrameCallback) {
+        let _ = delay;
+        self.request_frame(frame);
+    }
+
+    /// Cancels the pending frame request, `frame` must not be called anymore.
+    /// This is called when the context is cleaned up.
+    fn cancel_frame(&self);
+}
+
+/// Schedules frames with `window.request_animation_frame()`. All web schedulers of the same
+/// window share a single frame loop, so every island of a page can have its own context.
+pub struct WebScheduler {
+    id: u64,
+    window: Option<web_sys::Window>,
+    /// The timeout before a delayed frame request
+    timeout: Rc<RefCell<Option<JsValue>>>,
+}
+
+impl WebScheduler {
+    /// Schedules frames on the window of the application
+    pub fn new() -> WebScheduler {
+        WebScheduler {
+            id: coordinator::next_id(),
+            window: None,
+            timeout: Rc::default(),
+        }
+    }
+
+    /// Schedules frames on another window, such as that of an iframe or a popup
+    pub fn for_window(window: web_sys::Window) -> WebScheduler {
+        WebScheduler {
+            id: coordinator::next_id(),
+            window: Some(window),
+            timeout: Rc::default(),
+        }
+    }
+}
+
+impl Default for WebScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scheduler for WebScheduler {
+    fn request_frame(&self, frame: FrameCallback) {
+        coordinator::request(self.id, self.window.as_ref(), frame);
+    }
+
+    fn request_frame_after(&self, delay: Duration, frame: FrameCallback) {
+        let id = self.id;
+        let window = self.window.clone();
+        let timeout = self.timeout.clone();
+        // Wait with a timer and then request a frame, so the frame stays in sync with the display
+        let callback = Closure::once_into_js(move || {
+            timeout.borrow_mut().take();
+            coordinator::request(id, window.as_ref(), frame);
+        });
+        *self.timeout.borrow_mut() = Some(set_timeout(callback, delay));
+    }
+
+    fn cancel_frame(&self) {
+        if let Some(timeout) = self.timeout.borrow_mut().take() {
+            call_global("clearTimeout", &[timeout]);
+        }
+        coordinator::cancel(self.id);
+    }
+}
+
+/// Schedules frames with an external event loop, see
+/// [`AnimationContext::provide_with_custom_request_animation_frame()`](crate::AnimationContext::provide_with_custom_request_animation_frame)
+pub struct CallbackScheduler {
+    request: Box<dyn Fn()>,
+    pending: Rc<RefCell<Option<FrameCallback>>>,
+}
+
+impl CallbackScheduler {
+    /// Calls `request` whenever a frame is needed. The event loop has to call the function
+    /// returned by [`CallbackScheduler::on_frame()`] once that frame arrives.
+    pub fn new(request: impl Fn() + 'static) -> CallbackScheduler {
+        CallbackScheduler {
+            request: Box::new(request),
+            pending: Rc::default(),
+        }
+    }
+
+    /// The function to call when a requested frame has arrived. Calls without a pending
+    /// request are ignored.
+    pub fn on_frame(&self) -> impl Fn() {
+        let pending = self.pending.clone();
+        move || {
+            run_pending(&pending);
+        }
+    }
+}
+
+impl Scheduler for CallbackScheduler {
+    fn request_frame(&self, frame: FrameCallback) {
+        *self.pending.borrow_mut() = Some(frame);
+        (self.request)();
+    }
+
+    fn cancel_frame(&self) {
+        self.pending.borrow_mut().take();
+    }
+}
+
+/// Schedules frames with `setTimeout()` at a fixed frame rate, for environments in which
+/// `requestAnimationFrame()` is not available. Unlike animation frames, timers are not synchronized
+/// with the display, so prefer the [`WebScheduler`] whenever possible.
+pub struct TimerScheduler {
+    interval: Duration,
+    last_frame: Rc<Cell<Option<Instant>>>,
+    handle: Rc<RefCell<Option<JsValue>>>,
+}
+
+impl TimerScheduler {
+    /// Runs frames at most `fps` times per second. Frame rates below 1.0, including NaN, run a
+    /// frame every second.
+    pub fn new(fps: f64) -> TimerScheduler {
+        TimerScheduler {
+            interval: Duration::from_secs_f64(1.0 / fps.max(1.0)),
+            last_frame: Rc::default(),
+            handle: Rc::default(),
+        }
+    }
+}
+
+impl Scheduler for TimerScheduler {
+    fn request_frame(&self, frame: FrameCallback) {
+        // Space the frames evenly instead of waiting a full interval after every request
+        let now = Instant::now();
+        let delay = self
+            .last_frame
+            .get()
+            .map(|last_frame| (last_frame + self.interval).saturating_duration_since(now))
+            .unwrap_or_default();
+
+        let last_frame = self.last_frame.clone();
+        let handle = self.handle.clone();
+        let callback = Closure::once_into_js(move || {
+            handle.borrow_mut().take();
+            last_frame.set(Some(Instant::now()));
+            frame();
+        });
+        *self.handle.borrow_mut() = Some(set_timeout(callback, delay));
+    }
+
+    fn request_frame_after(&self, delay: Duration, frame: FrameCallback) {
+        let handle = self.handle.clone();
+        let last_frame = self.last_frame.clone();
+        let callback = Closure::once_into_js(move || {
+            handle.borrow_mut().take();
+            last_frame.set(Some(Instant::now()));
+            frame();
+        });
+        *self.handle.borrow_mut() = Some(set_timeout(callback, delay.max(self.interval)));
+    }
+
+    fn cancel_frame(&self) {
+        if let Some(timeout) = self.handle.borrow_mut().take() {
+            call_global("clearTimeout", &[timeout]);
+        }
+    }
+}
+
+/// Schedules frames with `requestAnimationFrame()` of the global scope of a dedicated worker,
+/// which is synchronized with the display of the `OffscreenCanvas` the worker renders into.
+#[derive(Default)]
+pub struct WorkerScheduler {
+    handle: Rc<RefCell<Option<JsValue>>>,
+    /// The timeout before a delayed frame request
+    timeout: Rc<RefCell<Option<JsValue>>>,
+}
+
+impl WorkerScheduler {
+    /// A scheduler for the worker this is called in
+    pub fn new() -> WorkerScheduler {
+        WorkerScheduler::default()
+    }
+}
+
+impl Scheduler for WorkerScheduler {
+    fn request_frame(&self, frame: FrameCallback) {
+        request_worker_frame(&self.handle, frame);
+    }
+
+    fn request_frame_after(&self, delay: Duration, frame: FrameCallback) {
+        let handle = self.handle.clone();
+        let timeout = self.timeout.clone();
+        let callback = Closure::once_into_js(move || {
+            timeout.borrow_mut().take();
+            request_worker_frame(&handle, frame);
+        });
+        *self.timeout.borrow_mut() = Some(set_timeout(callback, delay));
+    }
+
+    fn cancel_frame(&self) {
+        if let Some(timeout) = self.timeout.borrow_mut().take() {
+            call_global("clearTimeout", &[timeout]);
+        }
+        if let Some(request) = self.handle.borrow_mut().take() {
+            call_global("cancelAnimationFrame", &[request]);
+        }
+    }
+}
+
+/// Requests an animation frame of the worker and keeps its handle in `handle` until it arrives
+fn request_worker_frame(handle: &Rc<RefCell<Option<JsValue>>>, frame: FrameCallback) {
+    let pending = handle.clone();
+    let callback = Closure::once_into_js(move || {
+        pending.borrow_mut().take();
+        frame();
+    });
+    *handle.borrow_mut() = Some(call_global("requestAnimationFrame", &[callback]));
+}
+
+/// Calls a function such as `setTimeout()` of the global scope, which is either a window or a worker
+fn call_global(name: &str, args: &[JsValue]) -> JsValue {
+    let global = js_sys::global();
+    let function: Function = Reflect::get(&global, &JsValue::from_str(name))
+        .ok()
+        .and_then(|function| function.dyn_into().ok())
+        .unwrap_or_else(|| panic!("The global scope has no {name}() function"));
+    function
+        .apply(&global, &args.iter().collect())
+        .unwrap_or_else(|_| panic!("{name}() failed"))
+}
+
+/// Calls `callback` after `delay` with `setTimeout()` and returns the handle of the timeout
+fn set_timeout(callback: JsValue, delay: Duration) -> JsValue {
+    call_global(
+        "setTimeout",
+        &[callback, JsValue::from_f64(delay.as_secs_f64() * 1000.0)],
+    )
+}
+
+/// The scheduler used by [`AnimationContext::provide()`](crate::AnimationContext::provide):
+/// animation frames of the window or worker when they are available and a 60 fps timer otherwise
+pub(crate) fn default_scheduler() -> Box<dyn Scheduler> {
+    #[cfg(target_arch = "wasm32")]
+    if web_sys::window().is_none()
+        && Reflect::has(
+            &js_sys::global(),
+            &JsValue::from_str("requestAnimationFrame"),
+        )
+        .unwrap_or(false)
+    {
+        return Box::new(WorkerScheduler::new());
+    }
+    #[cfg(target_arch = "wasm32")]
+    if !Reflect::has(
+        &js_sys::global(),
+        &JsValue::from_str("requestAnimationFrame"),
+    )
+    .unwrap_or(false)
+    {
+        leptos::logging::warn!("requestAnimationFrame() is not available, falling back to a 60 fps timer for animations");
+        return Box::new(TimerScheduler::new(60.0));
+    }
+    Box::new(WebScheduler::new())
+}
+
+/// Only runs frames when [`ManualScheduler::run_frame()`] is called, for stepping through
+/// animations in tests. Clones share the same pending frame, so keep a clone around after
+/// giving one to the context.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::AnimationContext;
+/// # use leptos_animation::scheduler::ManualScheduler;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let scheduler = ManualScheduler::new();
+/// let context = AnimationContext::provide_with_scheduler(scheduler.clone());
+///
+/// context.request_animation_frame();
+/// assert!(scheduler.is_frame_requested());
+/// assert!(scheduler.run_frame());
+/// assert!(!scheduler.run_frame());
+/// ```
+#[derive(Clone, Default)]
+pub struct ManualScheduler {
+    pending: Rc<RefCell<Option<FrameCallback>>>,
+}
+
+impl ManualScheduler {
+    /// A scheduler without a pending frame
+    pub fn new() -> ManualScheduler {
+        ManualScheduler::default()
+    }
+
+    /// Whether a frame has been requested that has not been run yet
+    pub fn is_frame_requested(&self) -> bool {
+        self.pending.borrow().is_some()
+    }
+
+    /// Runs the requested frame, returns `false` if no frame was requested
+    pub fn run_frame(&self) -> bool {
+        run_pending(&self.pending)
+    }
+}
+
+impl Scheduler for ManualScheduler {
+    fn request_frame(&self, frame: FrameCallback) {
+        *self.pending.borrow_mut() = Some(frame);
+    }
+
+    fn cancel_frame(&self) {
+        self.pending.borrow_mut().take();
+    }
+}
+
+/// Runs a pending frame callback, releasing the borrow first so the frame can request the next one
+fn run_pending(pending: &RefCell<Option<FrameCallback>>) -> bool {
+    let frame = pending.borrow_mut().take();
+    match frame {
+        Some(frame) => {
+            frame();
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timer_frame_rates_below_one_run_every_second() {
+        for fps in [0.0, -30.0, f64::NAN] {
+            assert_eq!(TimerScheduler::new(fps).interval, Duration::from_secs(1));
+        }
+        assert_eq!(TimerScheduler::new(f64::INFINITY).interval, Duration::ZERO);
+        assert_eq!(
+            TimerScheduler::new(50.0).interval,
+            Duration::from_millis(20)
+        );
+    }
+}
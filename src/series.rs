@@ -0,0 +1,175 @@
+//! Lists of values that are animated element by element, such as the bars and points of a chart
+//! whose series grow and shrink.
+
+use std::ops::{Add, Deref, Mul, Sub};
+
+use crate::tween::Tween;
+
+/// What happens to the elements that only one side of an animation between two lists has, when a
+/// series gains or loses elements.
+///
+/// The values in between two lists have as many elements as the longer list, except with
+/// [`Truncate`](LengthMismatch::Truncate), and settle on the length of the target list once the
+/// animation is finished. [`Tween`] for `Vec` uses [`Fade`](LengthMismatch::Fade), other
+/// strategies are picked with a tween function:
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::series::LengthMismatch;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let from = vec![10.0, 20.0];
+/// let to = vec![30.0, 40.0, 50.0];
+///
+/// // The new point grows out of the last one
+/// assert_eq!(*LengthMismatch::Pad.tween(&from, &to, 0.5), [20.0, 30.0, 35.0]);
+/// // The new point grows from zero
+/// assert_eq!(*LengthMismatch::Fade.tween(&from, &to, 0.5), [20.0, 30.0, 25.0]);
+/// // The new point is there right away
+/// assert_eq!(*LengthMismatch::Truncate.tween(&from, &to, 0.5), [20.0, 30.0, 50.0]);
+///
+/// let (points, set_points) = signal(from);
+/// let line = AnimatedSignal::new(
+///     move || points.get().into(),
+///     |from, to, progress| LengthMismatch::Pad.tween(from, to, progress),
+/// );
+/// set_points.set(to);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LengthMismatch {
+    /// The shorter list is padded with its last element, so new elements grow out of the last one
+    /// and removed elements merge into the new last one, like a line chart that extends to the
+    /// right. An empty list is padded with the default value.
+    Pad,
+    /// Elements fade in from and out to their default value, such as bars that grow from zero
+    #[default]
+    Fade,
+    /// New elements appear at their target right away and removed elements disappear right away
+    Truncate,
+}
+
+impl LengthMismatch {
+    /// Interpolates between two lists element by element, with the elements that only one of them
+    /// has handled by this strategy
+    pub fn tween<T: Tween + Default>(
+        self,
+        from: &[T],
+        to: &[T],
+        progress: f64,
+    ) -> Series<T::Interpolated> {
+        let neutral = T::default();
+        // The values that the elements of the other list come from or go to
+        let (from_fill, to_fill) = match self {
+            LengthMismatch::Pad => (
+                from.last().unwrap_or(&neutral),
+                to.last().unwrap_or(&neutral),
+            ),
+            LengthMismatch::Fade | LengthMismatch::Truncate => (&neutral, &neutral),
+        };
+        let len = match self {
+            LengthMismatch::Truncate => to.len(),
+            LengthMismatch::Pad | LengthMismatch::Fade => from.len().max(to.len()),
+        };
+        let values = (0..len)
+            .map(|index| match (from.get(index), to.get(index)) {
+                (Some(from), Some(to)) => from.tween(to, progress),
+                (None, Some(to)) if self == LengthMismatch::Truncate => to.tween(to, progress),
+                (None, Some(to)) => from_fill.tween(to, progress),
+                (Some(from), None) => from.tween(to_fill, progress),
+                (None, None) => unreachable!("index beyond both lists"),
+            })
+            .collect();
+        Series(values)
+    }
+}
+
+/// The values of an animated list, as interpolated by [`LengthMismatch::tween()`].
+///
+/// It dereferences to a slice of the values. It also supports the `Add`, `Sub` and `Mul<f64>`
+/// that additive animations need, where the elements that one side does not have count as their
+/// default value.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Series<I>(pub Vec<I>);
+
+impl<I> Deref for Series<I> {
+    type Target = [I];
+
+    fn deref(&self) -> &[I] {
+        &self.0
+    }
+}
+
+impl<I> From<Series<I>> for Vec<I> {
+    fn from(series: Series<I>) -> Self {
+        series.0
+    }
+}
+
+impl<I> IntoIterator for Series<I> {
+    type Item = I;
+    type IntoIter = std::vec::IntoIter<I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, I> IntoIterator for &'a Series<I> {
+    type Item = &'a I;
+    type IntoIter = std::slice::Iter<'a, I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<I: Default> Series<I> {
+    /// Combines the elements of both series, with the elements that only one of them has combined
+    /// with the default value
+    fn zip(self, rhs: Series<I>, f: impl Fn(I, I) -> I) -> Series<I> {
+        let len = self.0.len().max(rhs.0.len());
+        let mut lhs = self.0.into_iter();
+        let mut rhs = rhs.0.into_iter();
+        Series(
+            (0..len)
+                .map(|_| {
+                    f(
+                        lhs.next().unwrap_or_default(),
+                        rhs.next().unwrap_or_default(),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<I: Default + Add<Output = I>> Add for Series<I> {
+    type Output = Series<I>;
+
+    fn add(self, rhs: Series<I>) -> Series<I> {
+        self.zip(rhs, |a, b| a + b)
+    }
+}
+
+impl<I: Default + Sub<Output = I>> Sub for Series<I> {
+    type Output = Series<I>;
+
+    fn sub(self, rhs: Series<I>) -> Series<I> {
+        self.zip(rhs, |a, b| a - b)
+    }
+}
+
+impl<I: Mul<f64, Output = I>> Mul<f64> for Series<I> {
+    type Output = Series<I>;
+
+    fn mul(self, rhs: f64) -> Series<I> {
+        Series(self.0.into_iter().map(|value| value * rhs).collect())
+    }
+}
+
+impl<T: Tween + Default> Tween for Vec<T> {
+    type Interpolated = Series<T::Interpolated>;
+
+    fn tween(&self, other: &Self, progress: f64) -> Self::Interpolated {
+        LengthMismatch::Fade.tween(self, other, progress)
+    }
+}
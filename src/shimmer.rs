@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use crate::AnimationContext;
+
+impl AnimationContext {
+    /// Returns the shared shimmer phase, a value that runs from 0.0 to 1.0 once every `period`
+    /// and then wraps around. Every phase signal of a context is derived from the same clock and
+    /// is updated on the same animation frame, so all skeleton loaders on the page shimmer in sync
+    /// instead of each one running its own CSS animation with a random phase.
+    ///
+    /// The `offset` shifts the phase by a fraction of the period. This can be used to stagger
+    /// loaders (for example `index as f64 * 0.1`) while they are still driven by a single loop.
+    ///
+    /// The period can also be a [`DurationToken`](crate::tokens::DurationToken).
+    ///
+    /// Animation frames keep being requested for as long as the returned signal is being read.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::AnimationContext;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = AnimationContext::provide();
+    ///
+    /// // Three skeleton rows with a shimmer that travels from top to bottom
+    /// let phases: Vec<Signal<f64>> = (0..3)
+    ///     .map(|row| context.shimmer_phase(Duration::from_secs_f64(1.5), row as f64 * -0.1))
+    ///     .collect();
+    /// ```
+    pub fn shimmer_phase(&self, period: impl Into<Duration>, offset: f64) -> Signal<f64> {
+        let context = *self;
+        let period = period.into();
+        Signal::derive(move || {
+            context.animation_frame.track();
+            context.request_animation_frame();
+
+            if period.is_zero() {
+                return offset.rem_euclid(1.0);
+            }
+            let elapsed = (context.now() - context.epoch).as_secs_f64();
+            (elapsed / period.as_secs_f64() + offset).rem_euclid(1.0)
+        })
+    }
+}
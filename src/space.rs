@@ -0,0 +1,132 @@
+//! Interpolating numbers in another space than the one they are shown in, so values such as
+//! volume or frequency change at a pace that is perceived as even.
+
+use std::rc::Rc;
+
+use crate::animation_target::AnimationSource;
+use crate::AnimatedSignal;
+
+/// The quietest gain of [`InterpolationSpace::Decibels`], below which the gain is silent
+const SILENCE_DB: f64 = -100.0;
+
+/// A space in which a tween interpolates values. The values are transformed into the space, the
+/// tween runs there and its result is transformed back.
+///
+/// Our senses perceive many quantities logarithmically: a sweep from 100Hz to 10kHz sounds even
+/// when every octave takes the same time, not every hertz. Animating such values in the matching
+/// space makes them feel linear.
+/// ```
+/// # use leptos_animation::space::InterpolationSpace;
+/// // Halfway between 100Hz and 10kHz in pitch is 1kHz
+/// let frequency = InterpolationSpace::Logarithmic.lerp(100.0, 10_000.0, 0.5);
+/// assert!((frequency - 1_000.0).abs() < 1e-9);
+///
+/// // Halfway between full gain and -40dB is -20dB
+/// let gain = InterpolationSpace::Decibels.lerp(1.0, 0.01, 0.5);
+/// assert!((gain - 0.1).abs() < 1e-9);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub enum InterpolationSpace {
+    /// The values themselves
+    #[default]
+    Linear,
+
+    /// The natural logarithm of the values, for frequencies, zoom levels and other ratios. Values
+    /// have to be positive, smaller values are treated as the smallest positive number.
+    Logarithmic,
+
+    /// The loudness of a linear gain in decibels, for audio volumes. Gains of 0.0 are silent and
+    /// interpolate as -100dB.
+    Decibels,
+
+    /// A user defined space, such as a perceptual lightness for luminance values. `to_space`
+    /// transforms a value into the space and `from_space` is its inverse.
+    Custom {
+        /// Transforms a value into the space
+        to_space: fn(f64) -> f64,
+        /// Transforms a value from the space back
+        from_space: fn(f64) -> f64,
+    },
+}
+
+impl InterpolationSpace {
+    /// Transforms `value` into this space
+    pub fn to_space(self, value: f64) -> f64 {
+        match self {
+            InterpolationSpace::Linear => value,
+            InterpolationSpace::Logarithmic => value.max(f64::MIN_POSITIVE).ln(),
+            InterpolationSpace::Decibels => match value > 0.0 {
+                true => (20.0 * value.log10()).max(SILENCE_DB),
+                false => SILENCE_DB,
+            },
+            InterpolationSpace::Custom { to_space, .. } => to_space(value),
+        }
+    }
+
+    /// Transforms `value` from this space back
+    pub fn from_space(self, value: f64) -> f64 {
+        match self {
+            InterpolationSpace::Linear => value,
+            InterpolationSpace::Logarithmic => value.exp(),
+            InterpolationSpace::Decibels => match value <= SILENCE_DB {
+                true => 0.0,
+                false => 10f64.powf(value / 20.0),
+            },
+            InterpolationSpace::Custom { from_space, .. } => from_space(value),
+        }
+    }
+
+    /// Runs `tween` in this space
+    pub fn tween(
+        self,
+        tween: impl Fn(&f64, &f64, f64) -> f64,
+        from: &f64,
+        to: &f64,
+        progress: f64,
+    ) -> f64 {
+        let value = tween(&self.to_space(*from), &self.to_space(*to), progress);
+        self.from_space(value)
+    }
+
+    /// Interpolates linearly in this space
+    pub fn lerp(self, from: f64, to: f64, progress: f64) -> f64 {
+        self.tween(
+            |from, to, progress| (to - from) * progress + from,
+            &from,
+            &to,
+            progress,
+        )
+    }
+}
+
+impl AnimatedSignal<f64, f64> {
+    /// Like [`AnimatedSignal::new()`], but the tween interpolates in `space`: it is called with the
+    /// values transformed into the space and its progress is applied there. The tween returns the
+    /// value in the space, which is transformed back afterwards.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # use leptos_animation::space::InterpolationSpace;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (muted, set_muted) = signal(false);
+    ///
+    /// // Fade the music out evenly instead of it seeming to drop off at the end
+    /// let gain: AnimatedSignal<f64, f64> = AnimatedSignal::new_in_space(
+    ///     move || if muted.get() { 0.0 } else { 1.0 }.into(),
+    ///     tween_default,
+    ///     InterpolationSpace::Decibels,
+    /// );
+    ///
+    /// assert_eq!(gain.get_untracked(), 1.0);
+    /// ```
+    pub fn new_in_space<Marker>(
+        source: impl AnimationSource<f64, Marker>,
+        tween: fn(&f64, &f64, f64) -> f64,
+        space: InterpolationSpace,
+    ) -> AnimatedSignal<f64, f64> {
+        AnimatedSignal::new_with_tween(
+            source.into_signal(),
+            Rc::new(move |from, to, progress| space.tween(tween, from, to, progress)),
+        )
+    }
+}
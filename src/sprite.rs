@@ -0,0 +1,123 @@
+//! Sprite sheet animations that step through whole frames at their own frame rate.
+
+use std::ops::Deref;
+use std::time::Duration;
+
+use instant::Instant;
+use leptos::prelude::*;
+
+use crate::{AnimationContext, RepeatMode};
+
+/// The index of the current frame of a sprite animation that shows `frames` frames at `fps`
+/// frames per second, independent of the frame rate of the display.
+///
+/// The index is a memo, so views and canvas effects that read it only update when the shown frame
+/// actually changes, not on every animation frame. With
+/// [`Alternate`](RepeatMode::Alternate) repeats the animation plays back and forth, without
+/// showing the first and last frame twice in a row. Once a counted number of repetitions has
+/// played, the index stays on the frame that was shown last.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::sprite::AnimatedFrameIndex;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// // A walk cycle of 8 frames of 64px each, drawn at 12 fps
+/// let walk = AnimatedFrameIndex::new(8, 12.0, RepeatMode::Infinite);
+/// assert_eq!(walk.get_untracked(), 0);
+/// # let _html =
+/// view! {
+///     <div style:background-position=move || format!("-{}px 0", walk.get() * 64)></div>
+/// }
+/// # .to_html();
+///
+/// // A flag that waves back and forth: 0, 1, 2, 3, 2, 1, 0, 1, ...
+/// let flag = AnimatedFrameIndex::new(4, 8.0, RepeatMode::AlternateInfinite);
+/// ```
+#[derive(Clone, Copy)]
+pub struct AnimatedFrameIndex {
+    context: AnimationContext,
+    started: StoredValue<Instant>,
+    restarted: Trigger,
+    index: Memo<usize>,
+}
+
+impl AnimatedFrameIndex {
+    /// Starts playing `frames` frames at `fps` frames per second, repeated as `repeat` says. With
+    /// no frames or a frame rate that is not positive the index stays at 0.
+    pub fn new(frames: usize, fps: f64, repeat: RepeatMode) -> AnimatedFrameIndex {
+        let context: AnimationContext = use_context().expect(
+            "No AnimationContext present, call AnimationContext::provide() in a parent scope",
+        );
+        let started = StoredValue::new(context.now());
+        let restarted = Trigger::new();
+
+        let index = Memo::new(move |_| {
+            restarted.track();
+            context.animation_frame.track();
+            if frames <= 1 || !fps.is_finite() || fps <= 0.0 {
+                return 0;
+            }
+            let elapsed = started
+                .try_get_value()
+                .map(|started| context.now().saturating_duration_since(started))
+                .unwrap_or_default();
+            let step = (elapsed.as_secs_f64() * fps).floor() as u64;
+            let (index, playing) = frame_at(step, frames as u64, repeat);
+            if playing {
+                // Wake up for the next frame of the sprite, not for every frame of the display
+                let next = Duration::from_secs_f64((step + 1) as f64 / fps);
+                context.request_frame_after(next.saturating_sub(elapsed));
+            }
+            index as usize
+        });
+
+        AnimatedFrameIndex {
+            context,
+            started,
+            restarted,
+            index,
+        }
+    }
+
+    /// Plays the animation again from its first frame
+    pub fn restart(&self) {
+        self.started.set_value(self.context.now());
+        self.restarted.notify();
+    }
+}
+
+/// The frame shown at `step` frames since the start, and whether later steps show other frames
+fn frame_at(step: u64, frames: u64, repeat: RepeatMode) -> (u64, bool) {
+    match repeat {
+        RepeatMode::Count(_) | RepeatMode::Infinite => {
+            let last = repeat.count().map(|count| count as u64 * frames - 1);
+            match last {
+                Some(last) if step >= last => (frames - 1, false),
+                _ => (step % frames, true),
+            }
+        }
+        RepeatMode::Alternate(_) | RepeatMode::AlternateInfinite => {
+            // Every pass after the first starts on the frame the previous one ended on
+            let last = repeat.count().map(|count| count as u64 * (frames - 1));
+            let step = match last {
+                Some(last) if step >= last => last,
+                _ => step,
+            };
+            let period = 2 * (frames - 1);
+            let phase = step % period;
+            let index = match phase < frames {
+                true => phase,
+                false => period - phase,
+            };
+            (index, last.is_none_or(|last| step < last))
+        }
+    }
+}
+
+impl Deref for AnimatedFrameIndex {
+    type Target = Memo<usize>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.index
+    }
+}
@@ -0,0 +1,361 @@
+//! Typed CSS properties that are animated together and written into a single `style` attribute.
+
+use std::fmt::Write;
+use std::ops::{Add, Mul, Sub};
+
+use leptos::prelude::*;
+
+use crate::AnimatedSignal;
+#[cfg(not(feature = "disable-animations"))]
+use crate::AnimationStatus;
+
+/// A length in CSS pixels
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Px(pub f64);
+
+/// An angle in degrees
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Deg(pub f64);
+
+const OPACITY: u16 = 1 << 0;
+const TRANSLATE_X: u16 = 1 << 1;
+const TRANSLATE_Y: u16 = 1 << 2;
+const ROTATE: u16 = 1 << 3;
+const SCALE: u16 = 1 << 4;
+const WIDTH: u16 = 1 << 5;
+const HEIGHT: u16 = 1 << 6;
+const TOP: u16 = 1 << 7;
+const LEFT: u16 = 1 << 8;
+const TRANSFORM: u16 = TRANSLATE_X | TRANSLATE_Y | ROTATE | SCALE;
+/// Properties that make the browser recalculate the layout of the page on every frame
+#[cfg(debug_assertions)]
+const LAYOUT: u16 = WIDTH | HEIGHT | TOP | LEFT;
+
+/// A set of CSS properties with typed values, animated as a single value with
+/// [`tween_default`](crate::tween_default) and written into a `style` attribute.
+///
+/// Only the properties that were set are written. A property that is set on one side of an
+/// animation but not the other animates from or to its neutral value: an opacity and scale of 1,
+/// and 0 for everything else. The transform functions are always written in the order translate,
+/// rotate, scale.
+///
+/// [`AnimatedSignal::style()`] binds animated style targets to the `style` attribute and hints
+/// the browser about the animated properties with `will-change`.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::style::{Deg, Px, StyleTargets};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (open, set_open) = signal(false);
+/// let panel = AnimatedSignal::new(
+///     move || match open.get() {
+///         true => StyleTargets::new().opacity(1.0).translate_x(Px(0.0)).rotate(Deg(0.0)),
+///         false => StyleTargets::new().opacity(0.0).translate_x(Px(-20.0)).rotate(Deg(-5.0)),
+///     }
+///     .into(),
+///     tween_default,
+/// );
+///
+/// assert_eq!(panel.get_untracked().to_style(), "opacity: 0; transform: translate(-20px, 0px) rotate(-5deg);");
+/// # let _html =
+/// view! { <div style=panel.style()>"Menu"</div> }
+/// # .to_html();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StyleTargets {
+    /// The properties that were set, as a bit set
+    set: u16,
+    opacity: f64,
+    translate_x: f64,
+    translate_y: f64,
+    rotate: f64,
+    scale: f64,
+    width: f64,
+    height: f64,
+    top: f64,
+    left: f64,
+}
+
+impl Default for StyleTargets {
+    fn default() -> Self {
+        StyleTargets {
+            set: 0,
+            opacity: 1.0,
+            translate_x: 0.0,
+            translate_y: 0.0,
+            rotate: 0.0,
+            scale: 1.0,
+            width: 0.0,
+            height: 0.0,
+            top: 0.0,
+            left: 0.0,
+        }
+    }
+}
+
+impl StyleTargets {
+    /// No properties
+    pub fn new() -> StyleTargets {
+        StyleTargets::default()
+    }
+
+    /// Sets `opacity`
+    pub fn opacity(mut self, opacity: f64) -> Self {
+        self.set |= OPACITY;
+        self.opacity = opacity;
+        self
+    }
+
+    /// Sets the horizontal translation of `transform`
+    pub fn translate_x(mut self, Px(x): Px) -> Self {
+        self.set |= TRANSLATE_X;
+        self.translate_x = x;
+        self
+    }
+
+    /// Sets the vertical translation of `transform`
+    pub fn translate_y(mut self, Px(y): Px) -> Self {
+        self.set |= TRANSLATE_Y;
+        self.translate_y = y;
+        self
+    }
+
+    /// Sets the rotation of `transform`
+    pub fn rotate(mut self, Deg(angle): Deg) -> Self {
+        self.set |= ROTATE;
+        self.rotate = angle;
+        self
+    }
+
+    /// Sets the uniform scale of `transform`
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.set |= SCALE;
+        self.scale = scale;
+        self
+    }
+
+    /// Sets `width`
+    pub fn width(mut self, Px(width): Px) -> Self {
+        self.set |= WIDTH;
+        self.width = width;
+        self
+    }
+
+    /// Sets `height`
+    pub fn height(mut self, Px(height): Px) -> Self {
+        self.set |= HEIGHT;
+        self.height = height;
+        self
+    }
+
+    /// Sets `top`
+    pub fn top(mut self, Px(top): Px) -> Self {
+        self.set |= TOP;
+        self.top = top;
+        self
+    }
+
+    /// Sets `left`
+    pub fn left(mut self, Px(left): Px) -> Self {
+        self.set |= LEFT;
+        self.left = left;
+        self
+    }
+
+    /// Replaces the contents of `style` with the declarations of all set properties. Reusing the
+    /// same string every frame avoids allocating a new one.
+    pub fn write_style(&self, style: &mut String) {
+        style.clear();
+        let is_set = |property: u16| self.set & property != 0;
+        let separate = |style: &mut String| {
+            if !style.is_empty() {
+                style.push(' ');
+            }
+        };
+
+        if is_set(OPACITY) {
+            separate(style);
+            let _ = write!(style, "opacity: {};", self.opacity);
+        }
+        if self.set & TRANSFORM != 0 {
+            separate(style);
+            style.push_str("transform:");
+            if is_set(TRANSLATE_X | TRANSLATE_Y) {
+                let _ = write!(
+                    style,
+                    " translate({}px, {}px)",
+                    self.translate_x, self.translate_y
+                );
+            }
+            if is_set(ROTATE) {
+                let _ = write!(style, " rotate({}deg)", self.rotate);
+            }
+            if is_set(SCALE) {
+                let _ = write!(style, " scale({})", self.scale);
+            }
+            style.push(';');
+        }
+        if is_set(WIDTH) {
+            separate(style);
+            let _ = write!(style, "width: {}px;", self.width);
+        }
+        if is_set(HEIGHT) {
+            separate(style);
+            let _ = write!(style, "height: {}px;", self.height);
+        }
+        if is_set(TOP) {
+            separate(style);
+            let _ = write!(style, "top: {}px;", self.top);
+        }
+        if is_set(LEFT) {
+            separate(style);
+            let _ = write!(style, "left: {}px;", self.left);
+        }
+    }
+
+    /// Appends a `will-change` declaration for all set properties
+    fn write_will_change(&self, style: &mut String) {
+        let properties: Vec<&str> = [
+            (OPACITY, "opacity"),
+            (TRANSFORM, "transform"),
+            (WIDTH, "width"),
+            (HEIGHT, "height"),
+            (TOP, "top"),
+            (LEFT, "left"),
+        ]
+        .into_iter()
+        .filter(|(property, _)| self.set & property != 0)
+        .map(|(_, name)| name)
+        .collect();
+        if !properties.is_empty() {
+            if !style.is_empty() {
+                style.push(' ');
+            }
+            let _ = write!(style, "will-change: {};", properties.join(", "));
+        }
+    }
+
+    /// The declarations of all set properties, for the `style` attribute
+    pub fn to_style(&self) -> String {
+        let mut style = String::with_capacity(64);
+        self.write_style(&mut style);
+        style
+    }
+
+    fn zip(self, rhs: StyleTargets, f: impl Fn(f64, f64) -> f64) -> StyleTargets {
+        StyleTargets {
+            set: self.set | rhs.set,
+            opacity: f(self.opacity, rhs.opacity),
+            translate_x: f(self.translate_x, rhs.translate_x),
+            translate_y: f(self.translate_y, rhs.translate_y),
+            rotate: f(self.rotate, rhs.rotate),
+            scale: f(self.scale, rhs.scale),
+            width: f(self.width, rhs.width),
+            height: f(self.height, rhs.height),
+            top: f(self.top, rhs.top),
+            left: f(self.left, rhs.left),
+        }
+    }
+}
+
+impl Add for StyleTargets {
+    type Output = StyleTargets;
+
+    fn add(self, rhs: StyleTargets) -> StyleTargets {
+        self.zip(rhs, |a, b| a + b)
+    }
+}
+
+impl Sub for StyleTargets {
+    type Output = StyleTargets;
+
+    fn sub(self, rhs: StyleTargets) -> StyleTargets {
+        self.zip(rhs, |a, b| a - b)
+    }
+}
+
+impl Mul<f64> for StyleTargets {
+    type Output = StyleTargets;
+
+    fn mul(self, rhs: f64) -> StyleTargets {
+        self.zip(self, |a, _| a * rhs)
+    }
+}
+
+/// When [`AnimatedSignal::style_with()`] hints the browser with `will-change` that the properties
+/// are going to change, so it can prepare them for example on a separate compositor layer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WillChange {
+    /// From the start of an animation until it has settled, so no memory is held on to while the
+    /// element is static
+    #[default]
+    WhileAnimating,
+    /// All the time, for elements that animate so often that preparing them every time costs more
+    Always,
+    /// Never
+    Never,
+}
+
+impl<T> AnimatedSignal<T, StyleTargets> {
+    /// The animated properties for the `style` attribute, with a `will-change` hint while
+    /// animating. In debug builds a warning is logged the first time layout properties such as
+    /// `width` or `top` are animated, as animating `transform` instead is a lot cheaper.
+    pub fn style(&self) -> Signal<String, LocalStorage> {
+        self.style_with(WillChange::WhileAnimating)
+    }
+
+    /// The animated properties for the `style` attribute, with `will-change` hints as configured,
+    /// see [`style()`](AnimatedSignal::style)
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # use leptos_animation::style::{Px, StyleTargets, WillChange};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (dragging, set_dragging) = signal(false);
+    /// let card = AnimatedSignal::new(
+    ///     move || StyleTargets::new().scale(if dragging.get() { 1.1 } else { 1.0 }).into(),
+    ///     tween_default,
+    /// );
+    ///
+    /// // The card is picked up constantly, keep it on its own layer
+    /// let style = card.style_with(WillChange::Always);
+    /// assert_eq!(style.get_untracked(), "transform: scale(1); will-change: transform;");
+    /// ```
+    pub fn style_with(&self, will_change: WillChange) -> Signal<String, LocalStorage> {
+        let signal = self.animated_signal;
+        #[cfg(not(feature = "disable-animations"))]
+        let animation_status = self.animation_status;
+        #[cfg(debug_assertions)]
+        let warned = StoredValue::new(false);
+
+        Signal::derive_local(move || {
+            let targets = signal.get();
+            #[cfg(not(feature = "disable-animations"))]
+            let animating = animation_status
+                .try_with_value(AnimationStatus::animation_count)
+                .unwrap_or_default()
+                > 0;
+            // Without animations nothing is moving
+            #[cfg(feature = "disable-animations")]
+            let animating = false;
+
+            #[cfg(debug_assertions)]
+            if animating && targets.set & LAYOUT != 0 && !warned.get_value() {
+                warned.set_value(true);
+                leptos::logging::debug_warn!(
+                    "Animating the layout properties of \"{}\" recalculates the layout of the page on every frame, consider animating transform instead",
+                    targets.to_style()
+                );
+            }
+
+            let mut style = String::with_capacity(64);
+            targets.write_style(&mut style);
+            match will_change {
+                WillChange::WhileAnimating if animating => targets.write_will_change(&mut style),
+                WillChange::Always => targets.write_will_change(&mut style),
+                WillChange::WhileAnimating | WillChange::Never => {}
+            }
+            style
+        })
+    }
+}
@@ -0,0 +1,323 @@
+//! Testing components with animated signals without a browser.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Wake, Waker};
+use std::time::Duration;
+
+use leptos::prelude::*;
+use leptos::task::any_spawner::{PinnedFuture, PinnedLocalFuture};
+use leptos::task::{CustomExecutor, Executor};
+
+use crate::scheduler::ManualScheduler;
+use crate::AnimationContext;
+
+/// The time between frames that [`TestAnimationHarness::step()`] simulates, that of a 60fps display
+pub const FRAME_DURATION: Duration = Duration::from_micros(16_667);
+
+/// The longest [`TestAnimationHarness::run_until_settled()`] waits for animations to finish
+pub const SETTLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Drives an [`AnimationContext`] with a manual clock and manual animation frames, so tests of
+/// components with animated signals run instantly and deterministically.
+///
+/// The harness creates a reactive owner, provides an animation context in it and installs an
+/// executor on the current thread that runs effects whenever the harness steps. Effects only run
+/// when the `effects` feature of `leptos` is enabled, which for example the `csr` feature does.
+/// Without it animated signals never start animating and simply keep their initial value.
+///
+/// Like all signals, animated signals only update while something observes them. In an
+/// application the view does, in a test [`observe()`](TestAnimationHarness::observe) them.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::testing::TestAnimationHarness;
+/// let harness = TestAnimationHarness::new();
+/// let (open, set_open) = signal(false);
+/// let height: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+///     move || (if open.get() { 200.0 } else { 0.0 }, Duration::from_millis(300)).into(),
+///     tween_default,
+/// );
+/// harness.observe(*height);
+/// harness.assert_value_near(&height, 0.0, 0.001);
+///
+/// set_open.set(true);
+/// harness.step_by(Duration::from_millis(150));
+/// harness.run_until_settled();
+/// harness.assert_value_near(&height, 200.0, 0.001);
+/// ```
+pub struct TestAnimationHarness {
+    owner: Owner,
+    context: AnimationContext,
+    scheduler: ManualScheduler,
+}
+
+impl TestAnimationHarness {
+    /// Creates a reactive owner with a manually driven animation context. The owner is set as
+    /// the current owner, so signals created afterwards belong to it.
+    pub fn new() -> TestAnimationHarness {
+        // Only the first call on each thread installs the executor, the error of later calls is expected
+        let _ = Executor::init_local_custom_executor(TestExecutor);
+
+        let owner = Owner::new();
+        owner.set();
+        let scheduler = ManualScheduler::new();
+        let context = AnimationContext::provide_with_scheduler(scheduler.clone());
+        context.manual_time.set_value(Some(context.epoch));
+
+        TestAnimationHarness {
+            owner,
+            context,
+            scheduler,
+        }
+    }
+
+    /// The context that animated signals created within the harness use
+    pub fn context(&self) -> AnimationContext {
+        self.context
+    }
+
+    /// The owner of the harness, any signals created under it are disposed with the harness
+    pub fn owner(&self) -> &Owner {
+        &self.owner
+    }
+
+    /// The time that has passed on the manual clock since the harness was created
+    pub fn elapsed(&self) -> Duration {
+        self.context
+            .real_now()
+            .saturating_duration_since(self.context.epoch)
+    }
+
+    /// Moves the manual clock forward without running an animation frame
+    pub fn advance(&self, duration: Duration) {
+        let now = self.context.real_now() + duration;
+        self.context.manual_time.set_value(Some(now));
+    }
+
+    /// Keeps `signal` updated on every frame, like a view that shows it
+    pub fn observe(&self, signal: impl Track + 'static) {
+        Effect::new(move |_| signal.track());
+        self.run_effects();
+    }
+
+    /// Runs pending effects, so changes to signals start their animations
+    pub fn run_effects(&self) {
+        Executor::poll_local();
+    }
+
+    /// Simulates a single frame of a 60fps display, see [`step_by()`](TestAnimationHarness::step_by)
+    pub fn step(&self) {
+        self.step_by(FRAME_DURATION);
+    }
+
+    /// Runs pending effects, moves the clock forward by `duration` and runs the requested
+    /// animation frame, if any
+    pub fn step_by(&self, duration: Duration) {
+        self.run_effects();
+        self.advance(duration);
+        self.scheduler.run_frame();
+        self.run_effects();
+    }
+
+    /// Steps frame by frame until no animation requests another frame, and returns the simulated
+    /// time that took
+    ///
+    /// # Panics
+    ///
+    /// Panics if the animations have not settled after [`SETTLE_TIMEOUT`], such as when an
+    /// animation repeats infinitely.
+    #[track_caller]
+    pub fn run_until_settled(&self) -> Duration {
+        let start = self.elapsed();
+        self.run_effects();
+        while self.scheduler.is_frame_requested() {
+            let elapsed = self.elapsed() - start;
+            if elapsed > SETTLE_TIMEOUT {
+                panic!("Animations have not settled after {elapsed:?}");
+            }
+            self.step();
+        }
+        self.elapsed() - start
+    }
+
+    /// Whether an animation requested another frame
+    pub fn is_animating(&self) -> bool {
+        self.run_effects();
+        self.scheduler.is_frame_requested()
+    }
+
+    /// Asserts that the current value of `signal` is within `epsilon` of `expected`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is further away from `expected` than `epsilon`.
+    #[track_caller]
+    pub fn assert_value_near(
+        &self,
+        signal: &Signal<f64, LocalStorage>,
+        expected: f64,
+        epsilon: f64,
+    ) {
+        let value = signal.get_untracked();
+        assert!(
+            (value - expected).abs() <= epsilon,
+            "Expected a value within {epsilon} of {expected}, but it was {value} after {:?}",
+            self.elapsed()
+        );
+    }
+}
+
+impl Default for TestAnimationHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+thread_local! {
+    static TASKS: RefCell<HashMap<usize, PinnedLocalFuture<()>>> = RefCell::default();
+    static NEXT_TASK: Cell<usize> = const { Cell::new(0) };
+    static WOKEN: Arc<Mutex<Vec<usize>>> = Arc::default();
+}
+
+/// Runs all spawned futures on the current thread whenever it is polled
+struct TestExecutor;
+
+struct TaskWaker {
+    task: usize,
+    woken: Arc<Mutex<Vec<usize>>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        if let Ok(mut woken) = self.woken.lock() {
+            woken.push(self.task);
+        }
+    }
+}
+
+impl CustomExecutor for TestExecutor {
+    fn spawn(&self, fut: PinnedFuture<()>) {
+        self.spawn_local(fut);
+    }
+
+    fn spawn_local(&self, fut: PinnedLocalFuture<()>) {
+        let task = NEXT_TASK.replace(NEXT_TASK.get() + 1);
+        TASKS.with_borrow_mut(|tasks| tasks.insert(task, fut));
+        WOKEN.with(|woken| woken.lock().map(|mut woken| woken.push(task)).ok());
+    }
+
+    fn poll_local(&self) {
+        loop {
+            let woken = WOKEN.with(|woken| {
+                woken
+                    .lock()
+                    .map(|mut woken| std::mem::take(&mut *woken))
+                    .unwrap_or_default()
+            });
+            if woken.is_empty() {
+                break;
+            }
+            for task in woken {
+                // The task is taken out while polling, because it can spawn other tasks
+                let Some(mut future) = TASKS.with_borrow_mut(|tasks| tasks.remove(&task)) else {
+                    continue;
+                };
+                let waker = Waker::from(Arc::new(TaskWaker {
+                    task,
+                    woken: WOKEN.with(Arc::clone),
+                }));
+                if future
+                    .as_mut()
+                    .poll(&mut Context::from_waker(&waker))
+                    .is_pending()
+                {
+                    TASKS.with_borrow_mut(|tasks| tasks.insert(task, future));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{easing, tween_default, AnimatedSignal};
+    #[cfg(not(feature = "disable-animations"))]
+    use crate::{AnimationTarget, RepeatMode};
+
+    fn linear_signal(
+        harness: &TestAnimationHarness,
+    ) -> (WriteSignal<f64>, AnimatedSignal<f64, f64>) {
+        let (target, set_target) = signal(0.0);
+        let animated = AnimatedSignal::new(
+            move || (target.get(), Duration::from_millis(100), easing::LINEAR).into(),
+            tween_default,
+        );
+        harness.observe(*animated);
+        (set_target, animated)
+    }
+
+    #[test]
+    #[cfg(not(feature = "disable-animations"))]
+    fn steps_through_the_animation() {
+        let harness = TestAnimationHarness::new();
+        let (set_target, animated) = linear_signal(&harness);
+        set_target.set(100.0);
+        harness.step_by(Duration::ZERO);
+        harness.step_by(Duration::from_millis(50));
+        harness.assert_value_near(&animated, 50.0, 0.001);
+        assert!(harness.is_animating());
+    }
+
+    #[test]
+    #[cfg(not(feature = "disable-animations"))]
+    fn settles_on_the_target() {
+        let harness = TestAnimationHarness::new();
+        let (set_target, animated) = linear_signal(&harness);
+        assert!(!harness.is_animating());
+
+        set_target.set(100.0);
+        let took = harness.run_until_settled();
+        harness.assert_value_near(&animated, 100.0, 0.0);
+        assert!(took >= Duration::from_millis(100));
+        assert!(took < Duration::from_millis(100) + 2 * FRAME_DURATION);
+        assert!(!harness.is_animating());
+    }
+
+    #[test]
+    fn advances_the_manual_clock() {
+        let harness = TestAnimationHarness::new();
+        harness.advance(Duration::from_millis(50));
+        harness.step_by(Duration::from_millis(25));
+        assert_eq!(harness.elapsed(), Duration::from_millis(75));
+    }
+
+    #[test]
+    #[cfg(not(feature = "disable-animations"))]
+    #[should_panic(expected = "have not settled")]
+    fn infinite_animations_never_settle() {
+        let harness = TestAnimationHarness::new();
+        let (looping, set_looping) = signal(false);
+        let animated: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+            move || {
+                AnimationTarget::from(if looping.get() { 1.0 } else { 0.0 })
+                    .repeat(RepeatMode::Infinite)
+            },
+            tween_default,
+        );
+        harness.observe(*animated);
+        set_looping.set(true);
+        harness.run_until_settled();
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected a value within 0.5 of 10")]
+    fn values_far_from_the_expected_one_fail() {
+        let harness = TestAnimationHarness::new();
+        let (_, animated) = linear_signal(&harness);
+        harness.assert_value_near(&animated, 10.0, 0.5);
+    }
+}
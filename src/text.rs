@@ -0,0 +1,800 @@
+use std::time::Duration;
+
+use instant::Instant;
+use leptos::html::ElementType;
+use leptos::prelude::*;
+use leptos::wasm_bindgen::JsCast;
+
+use crate::time::saturating_mul_f64;
+use crate::{AnimatedSignal, AnimationContext, Easing};
+
+type WriteFn<I> = Box<dyn Fn(&I, &mut String)>;
+
+/// A string that is formatted from an animated value into a single reusable buffer.
+/// Created with [`AnimatedSignal::buffered()`].
+///
+/// Formatting an animated value (a transform, an SVG path, a text tween) into a new `String` every
+/// frame causes a steady stream of allocations. A `BufferedString` instead writes every frame into
+/// the same buffer and only gives out a borrowed `&str`, so after the first few frames the buffer
+/// has grown to its final capacity and no further allocations happen.
+#[derive(Copy, Clone)]
+pub struct BufferedString<I: 'static> {
+    signal: Signal<I, LocalStorage>,
+    buffer: StoredValue<String, LocalStorage>,
+    write: StoredValue<WriteFn<I>, LocalStorage>,
+}
+
+impl<I: Clone> BufferedString<I> {
+    /// Formats the current animated value into the buffer and calls `f` with the result.
+    /// Like `Signal::with()` this subscribes to the animated signal when used in a reactive context.
+    pub fn with_str<U>(&self, f: impl FnOnce(&str) -> U) -> U {
+        let value = self.signal.get();
+        self.buffer
+            .try_update_value(|buffer| {
+                buffer.clear();
+                self.write.with_value(|write| write(&value, buffer));
+                f(buffer)
+            })
+            .expect("BufferedString was used after it was disposed")
+    }
+}
+
+impl<I> Dispose for BufferedString<I> {
+    fn dispose(self) {
+        self.buffer.dispose();
+        self.write.dispose();
+    }
+}
+
+impl<T, I: Clone> AnimatedSignal<T, I> {
+    /// Creates a [`BufferedString`] which formats the animated value with `write` into a reusable
+    /// buffer instead of allocating a new `String` every frame. The buffer is cleared before `write`
+    /// is called.
+    /// ```
+    /// # use std::fmt::Write;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{AnimatedSignal, tween_default};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (x, set_x) = signal(0.0);
+    /// let animated_x = AnimatedSignal::new(move || x.get().into(), tween_default);
+    ///
+    /// let transform = animated_x.buffered(|x, buffer| {
+    ///     write!(buffer, "translateX({x:.2}px)").unwrap();
+    /// });
+    ///
+    /// transform.with_str(|transform| assert_eq!(transform, "translateX(0.00px)"));
+    /// ```
+    pub fn buffered(&self, write: impl Fn(&I, &mut String) + 'static) -> BufferedString<I> {
+        let write: WriteFn<I> = Box::new(write);
+        BufferedString {
+            signal: self.animated_signal,
+            buffer: StoredValue::new_local(String::new()),
+            write: StoredValue::new_local(write),
+        }
+    }
+}
+
+/// Splits the text of an element into the lines as they are currently laid out by the browser.
+///
+/// The text nodes that are direct children of the element are split into words, which are grouped
+/// into lines based on the position of their client rects (measured with `Range.getClientRects()`).
+/// Whitespace between words is normalized to a single space.
+pub fn measure_lines(element: &web_sys::Element) -> Vec<String> {
+    let Some(document) = element.owner_document() else {
+        return Vec::new();
+    };
+    let Ok(range) = document.create_range() else {
+        return Vec::new();
+    };
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut line_top: Option<f64> = None;
+
+    let children = element.child_nodes();
+    for node in (0..children.length()).filter_map(|index| children.item(index)) {
+        if node.node_type() != web_sys::Node::TEXT_NODE {
+            continue;
+        }
+        let text = node.text_content().unwrap_or_default();
+
+        // Range offsets within text nodes are in UTF-16 code units
+        let mut offset = 0;
+        let mut word_start = None;
+        for (index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+            if c.is_whitespace() {
+                if let Some((start, start_offset)) = word_start.take() {
+                    let word = &text[start..index];
+                    let top = (range.set_start(&node, start_offset).is_ok()
+                        && range.set_end(&node, offset).is_ok())
+                    .then(|| range.get_client_rects())
+                    .flatten()
+                    .and_then(|rects| rects.get(0))
+                    .map(|rect| rect.top());
+
+                    match (top, line_top, lines.last_mut()) {
+                        (Some(top), Some(previous), Some(line)) if top <= previous + 1.0 => {
+                            line.push(' ');
+                            line.push_str(word);
+                        }
+                        _ => {
+                            lines.push(word.to_string());
+                            line_top = top.or(line_top);
+                        }
+                    }
+                }
+            } else if word_start.is_none() {
+                word_start = Some((index, offset));
+            }
+            offset += c.len_utf16() as u32;
+        }
+    }
+    lines
+}
+
+/// Reveals the lines of a text one after another, as they are laid out by the browser.
+///
+/// The text of the element behind the `node_ref` is split into lines with [`measure_lines()`]
+/// whenever the element is mounted and again whenever the window is resized. Every line gets its
+/// own progress signal running from 0.0 to 1.0, each one delayed by `stagger` with respect to the
+/// previous line. The measured element should have the same width and font as the revealed lines,
+/// for example by rendering it invisibly behind them.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos::html::Div;
+/// # use leptos_animation::{easing, text::LineReveal};
+/// # // Listens to resizes of the window, so it only runs in a browser
+/// # let _ = || {
+/// let measure = NodeRef::<Div>::new();
+/// let reveal = LineReveal::new(measure, Duration::from_millis(400), Duration::from_millis(120), easing::CUBIC_OUT);
+///
+/// view! {
+///     <div class="headline">
+///         <div node_ref=measure style="visibility: hidden; position: absolute">"The quick brown fox jumps over the lazy dog"</div>
+///         {move || reveal.lines().get().into_iter().enumerate().map(|(index, line)| {
+///             let progress = reveal.progress(index);
+///             view! {
+///                 <div style:opacity=move || progress.get().to_string() style:transform=move || format!("translateY({}em)", 1.0 - progress.get())>
+///                     {line}
+///                 </div>
+///             }
+///         }).collect_view()}
+///     </div>
+/// }
+/// # };
+/// ```
+#[derive(Clone)]
+pub struct LineReveal {
+    context: AnimationContext,
+    lines: RwSignal<Vec<String>>,
+    start: StoredValue<Instant>,
+    duration: Duration,
+    stagger: Duration,
+    jitter: Duration,
+    easing: Easing,
+}
+
+impl LineReveal {
+    /// Starts revealing the lines of the element behind `node_ref` once it is mounted. The
+    /// durations and the easing can also be [motion tokens](crate::tokens).
+    pub fn new<E>(
+        node_ref: NodeRef<E>,
+        duration: impl Into<Duration>,
+        stagger: impl Into<Duration>,
+        easing: impl Into<Easing>,
+    ) -> LineReveal
+    where
+        E: ElementType + 'static,
+        E::Output: JsCast + Clone + 'static,
+    {
+        let context: AnimationContext = use_context().expect(
+            "No AnimationContext present, call AnimationContext::provide() in a parent scope",
+        );
+        let lines = RwSignal::new(Vec::new());
+        let start = StoredValue::new(context.now());
+
+        let measure = move || {
+            if let Some(element) = node_ref.get_untracked() {
+                lines.set(measure_lines(element.unchecked_ref()));
+            }
+        };
+
+        Effect::new(move |_| {
+            if node_ref.get().is_some() {
+                start.set_value(context.now());
+                measure();
+            }
+        });
+
+        let resize = window_event_listener(leptos::ev::resize, move |_| measure());
+        on_cleanup(move || resize.remove());
+
+        LineReveal {
+            context,
+            lines,
+            start,
+            duration: duration.into(),
+            stagger: stagger.into(),
+            jitter: Duration::ZERO,
+            easing: easing.into(),
+        }
+    }
+
+    /// Delays every line by a random part of `jitter` on top of its stagger, from the
+    /// [`Randomness`](crate::random::Randomness) of the context
+    pub fn with_jitter(self, jitter: Duration) -> LineReveal {
+        LineReveal { jitter, ..self }
+    }
+
+    /// The measured lines of the text
+    pub fn lines(&self) -> Signal<Vec<String>> {
+        self.lines.into()
+    }
+
+    /// The eased reveal progress of the line with the given index, from 0.0 to 1.0
+    pub fn progress(&self, line: usize) -> Signal<f64> {
+        staggered_progress(
+            self.context,
+            self.start,
+            self.duration,
+            jittered_delay(self.context, self.stagger, self.jitter, line),
+            self.easing.clone(),
+        )
+    }
+
+    /// Reveals all lines again from the start
+    pub fn restart(&self) {
+        self.start.set_value(self.context.now());
+        self.context.request_animation_frame();
+    }
+}
+
+/// The stream of the [`Randomness`](crate::random::Randomness) that staggered text jitters with
+const JITTER_STREAM: u64 = 0x6A17;
+
+/// The stagger of the item at `index`, delayed by a random part of `jitter` that stays the same for
+/// the index
+fn jittered_delay(
+    context: AnimationContext,
+    stagger: Duration,
+    jitter: Duration,
+    index: usize,
+) -> Duration {
+    let random = match jitter.is_zero() {
+        true => 0.0,
+        false => context.randomness().sample(JITTER_STREAM, index as u64),
+    };
+    stagger * index as u32 + saturating_mul_f64(jitter, random)
+}
+
+/// The eased progress of an animation that started `delay` after `start`, requesting frames until done
+fn staggered_progress(
+    context: AnimationContext,
+    start: StoredValue<Instant>,
+    duration: Duration,
+    delay: Duration,
+    easing: Easing,
+) -> Signal<f64> {
+    Signal::derive(move || {
+        context.animation_frame.track();
+        let elapsed = context
+            .now()
+            .saturating_duration_since(start.get_value())
+            .saturating_sub(delay);
+
+        let progress = if duration.is_zero() {
+            1.0
+        } else {
+            (elapsed.as_secs_f64() / duration.as_secs_f64()).min(1.0)
+        };
+        if progress < 1.0 {
+            context.request_animation_frame();
+        }
+        easing.ease(progress)
+    })
+}
+
+/// Styles that hide an element visually while keeping it available to screen readers
+const VISUALLY_HIDDEN: &str = "position: absolute; width: 1px; height: 1px; overflow: hidden; clip-path: inset(50%); white-space: nowrap";
+
+/// Whether the first character with a strong direction in `text` is right-to-left
+fn is_rtl(text: &str) -> bool {
+    for c in text.chars() {
+        match c as u32 {
+            // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic and their presentation forms
+            0x0590..=0x08FF
+            | 0xFB1D..=0xFDFF
+            | 0xFE70..=0xFEFF
+            | 0x10800..=0x10FFF
+            | 0x1E800..=0x1EFFF => return true,
+            _ if c.is_alphabetic() => return false,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Splits a text into glyphs and animates each glyph in with a stagger, for headline animations.
+///
+/// The text is split into grapheme clusters so combining marks, emoji sequences and other multi
+/// codepoint characters stay together in a single glyph. Glyphs are staggered in reading order, and
+/// the base direction of the text is detected from its first strong character so right-to-left text
+/// starts animating from the right. Note that splitting text into separate elements prevents cursive
+/// scripts such as Arabic from joining their letters, for those [`LineReveal`] is a better fit.
+///
+/// [`GlyphText::view()`] renders the glyphs as animated spans. Those spans are hidden from assistive
+/// technology and the full text is rendered once more, visually hidden, so screen readers read the
+/// text as a whole instead of character by character. Inside an SVG `<text>` element,
+/// [`GlyphText::svg_view()`] renders them as `<tspan>`s instead, which screen readers already read
+/// as one text.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::text::GlyphText;
+/// # use std::time::Duration;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let headline = GlyphText::new(
+///     "Cafe\u{301} 👩‍👩‍👧",
+///     Duration::from_millis(400),
+///     Duration::from_millis(40),
+///     easing::CUBIC_OUT,
+/// );
+///
+/// assert_eq!(headline.glyphs(), vec!["C", "a", "f", "e\u{301}", " ", "👩‍👩‍👧"]);
+/// assert_eq!(headline.direction(), "ltr");
+///
+/// // Slide every glyph up by half a line height while fading it in
+/// # let _html =
+/// view! { <h1>{headline.view(0.5)}</h1> }
+/// # .to_html();
+/// ```
+#[derive(Clone)]
+pub struct GlyphText {
+    context: AnimationContext,
+    text: StoredValue<String>,
+    glyphs: StoredValue<Vec<String>>,
+    start: StoredValue<Instant>,
+    duration: Duration,
+    stagger: Duration,
+    jitter: Duration,
+    easing: Easing,
+}
+
+impl GlyphText {
+    /// Starts animating in the glyphs of `text`. The durations and the easing can also be
+    /// [motion tokens](crate::tokens).
+    pub fn new(
+        text: impl Into<String>,
+        duration: impl Into<Duration>,
+        stagger: impl Into<Duration>,
+        easing: impl Into<Easing>,
+    ) -> GlyphText {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let context: AnimationContext = use_context().expect(
+            "No AnimationContext present, call AnimationContext::provide() in a parent scope",
+        );
+        let text = text.into();
+        let glyphs = text.graphemes(true).map(str::to_string).collect();
+
+        GlyphText {
+            context,
+            text: StoredValue::new(text),
+            glyphs: StoredValue::new(glyphs),
+            start: StoredValue::new(context.now()),
+            duration: duration.into(),
+            stagger: stagger.into(),
+            jitter: Duration::ZERO,
+            easing: easing.into(),
+        }
+    }
+
+    /// Delays every glyph by a random part of `jitter` on top of its stagger, for a less
+    /// mechanical entrance. The delays come from the [`Randomness`](crate::random::Randomness) of
+    /// the context, so a seeded context always jitters the same way.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::{easing, AnimationContext, random::Randomness};
+    /// # use leptos_animation::text::GlyphText;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let context = expect_context::<AnimationContext>();
+    /// context.set_randomness(Randomness::seeded(7));
+    ///
+    /// let headline = GlyphText::new("Welcome", Duration::from_millis(400), Duration::from_millis(30), easing::CUBIC_OUT)
+    ///     .with_jitter(Duration::from_millis(60));
+    /// ```
+    pub fn with_jitter(self, jitter: Duration) -> GlyphText {
+        GlyphText { jitter, ..self }
+    }
+
+    /// The full text, for use as accessible text
+    pub fn text(&self) -> String {
+        self.text.get_value()
+    }
+
+    /// The glyphs of the text in reading order
+    pub fn glyphs(&self) -> Vec<String> {
+        self.glyphs.get_value()
+    }
+
+    /// The base direction of the text, either `"ltr"` or `"rtl"`, for use in a `dir` attribute
+    pub fn direction(&self) -> &'static str {
+        if self.text.with_value(|text| is_rtl(text)) {
+            "rtl"
+        } else {
+            "ltr"
+        }
+    }
+
+    /// The eased progress of the glyph with the given index, from 0.0 to 1.0
+    pub fn progress(&self, glyph: usize) -> Signal<f64> {
+        staggered_progress(
+            self.context,
+            self.start,
+            self.duration,
+            jittered_delay(self.context, self.stagger, self.jitter, glyph),
+            self.easing.clone(),
+        )
+    }
+
+    /// The opacity of the glyph with the given index
+    pub fn opacity(&self, glyph: usize) -> Signal<f64> {
+        self.progress(glyph)
+    }
+
+    /// A CSS transform that moves the glyph with the given index up from `offset` line heights
+    /// below its resting position
+    pub fn transform(&self, glyph: usize, offset: f64) -> Signal<String> {
+        let shift = self.shift(glyph, offset);
+        Signal::derive(move || format!("translateY({}em)", shift.get()))
+    }
+
+    /// How many line heights the glyph with the given index is below its resting position, while
+    /// it moves up from `offset`
+    fn shift(&self, glyph: usize, offset: f64) -> Signal<f64> {
+        let progress = self.progress(glyph);
+        Signal::derive(move || offset * (1.0 - progress.get()))
+    }
+
+    /// Renders the glyphs as animated spans that move up from `offset` line heights below their
+    /// resting position while fading in
+    pub fn view(&self, offset: f64) -> impl IntoView {
+        let this = self.clone();
+        let glyphs = self
+            .glyphs()
+            .into_iter()
+            .enumerate()
+            .map(|(index, glyph)| {
+                let opacity = this.opacity(index);
+                let transform = this.transform(index, offset);
+                view! {
+                    <span
+                        style="display: inline-block; white-space: pre"
+                        style:opacity=move || opacity.get().to_string()
+                        style:transform=move || transform.get()
+                    >
+                        {glyph}
+                    </span>
+                }
+            })
+            .collect_view();
+
+        view! {
+            <span dir=self.direction() style="position: relative">
+                <span style=VISUALLY_HIDDEN>{self.text()}</span>
+                <span aria-hidden="true">{glyphs}</span>
+            </span>
+        }
+    }
+
+    /// Renders the glyphs as animated `<tspan>`s for an SVG `<text>` element, which move up from
+    /// `offset` line heights below their resting position while fading in. SVG text can't be
+    /// transformed glyph by glyph, so the glyphs are moved with `dy`. That moves the text after
+    /// a glyph along with it, so each glyph is moved by the difference to the glyph before it
+    /// and text following the glyphs in the same `<text>` element is moved by the last one.
+    /// ```
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # use leptos_animation::text::GlyphText;
+    /// # use std::time::Duration;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let headline = GlyphText::new("Hello", Duration::from_millis(400), Duration::from_millis(40), easing::CUBIC_OUT);
+    ///
+    /// # let _html =
+    /// view! {
+    ///     <svg viewBox="0 0 200 40">
+    ///         <text x="10" y="30" direction=headline.direction()>{headline.svg_view(0.5)}</text>
+    ///     </svg>
+    /// }
+    /// # .to_html();
+    /// ```
+    pub fn svg_view(&self, offset: f64) -> impl IntoView {
+        let this = self.clone();
+        self.glyphs()
+            .into_iter()
+            .enumerate()
+            .map(|(index, glyph)| {
+                let opacity = this.opacity(index);
+                let shift = this.shift(index, offset);
+                let previous_shift = index.checked_sub(1).map(|index| this.shift(index, offset));
+                let dy = move || {
+                    let previous = previous_shift.map_or(0.0, |shift| shift.get());
+                    format!("{}em", shift.get() - previous)
+                };
+                view! {
+                    <tspan
+                        dy=dy
+                        fill-opacity=move || opacity.get().to_string()
+                        style="white-space: pre"
+                    >
+                        {glyph}
+                    </tspan>
+                }
+            })
+            .collect_view()
+    }
+
+    /// Animates all glyphs in again from the start
+    pub fn restart(&self) {
+        self.start.set_value(self.context.now());
+        self.context.request_animation_frame();
+    }
+}
+
+/// What a typewriter shows after `steps` key strokes on the way from `from` to `to`: first the
+/// glyphs of `from` after the prefix that both share are deleted from the end, then the rest of
+/// `to` is typed. Also returns the number of key strokes the whole way takes.
+fn typewriter_at(from: &str, to: &str, steps: usize) -> (String, usize) {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let from: Vec<&str> = from.graphemes(true).collect();
+    let to: Vec<&str> = to.graphemes(true).collect();
+    let shared = from
+        .iter()
+        .zip(&to)
+        .take_while(|(from, to)| from == to)
+        .count();
+    let deleted = from.len() - shared;
+    let typed = to.len() - shared;
+    let shown = match steps < deleted {
+        true => from[..from.len() - steps].concat(),
+        false => to[..shared + (steps - deleted).min(typed)].concat(),
+    };
+    (shown, deleted + typed)
+}
+
+/// The text shown by a typewriter animation, see [`tween_typewriter`].
+///
+/// When a new text arrives while the previous one is still being typed, the newer animation
+/// decides what is shown, starting from the full previous text. [`Typewriter`] instead continues
+/// from the text that is shown.
+#[derive(Clone, Debug, Default)]
+pub struct TypedText {
+    text: String,
+    offset: Offset,
+}
+
+/// The role of a [`TypedText`] when the values of overlapping animations are combined
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Offset {
+    /// A shown text
+    #[default]
+    None,
+    /// An animation that shows the text instead of its target
+    Replace,
+    /// An animation that shows its target
+    Unchanged,
+}
+
+impl TypedText {
+    /// The shown text
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+impl std::ops::Deref for TypedText {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl std::fmt::Display for TypedText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl PartialEq for TypedText {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+}
+
+impl From<TypedText> for String {
+    fn from(typed: TypedText) -> String {
+        typed.text
+    }
+}
+
+// Text can't be added up, so the combined value of overlapping animations is the value of the
+// most recent animation that isn't at its target: `target - value` is that value as an offset,
+// and `accumulated - offset` takes it unless a more recent animation already replaced the text
+impl std::ops::Sub for TypedText {
+    type Output = TypedText;
+
+    fn sub(self, rhs: TypedText) -> TypedText {
+        match (self.offset, rhs.offset) {
+            (Offset::None, Offset::None) => TypedText {
+                offset: match self.text == rhs.text {
+                    true => Offset::Unchanged,
+                    false => Offset::Replace,
+                },
+                text: rhs.text,
+            },
+            (Offset::None, Offset::Replace) => TypedText {
+                text: rhs.text,
+                offset: Offset::Unchanged,
+            },
+            _ => self,
+        }
+    }
+}
+
+/// Types `to` over the course of the animation, after deleting the end of `from` that it doesn't
+/// share, one grapheme cluster at a time so accented letters and emoji are typed as one glyph.
+/// The key strokes are spread evenly over the progress, so use a linear easing for a steady pace.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::text::tween_typewriter;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let from = "Hello world".to_string();
+/// let to = "Hello there".to_string();
+/// // Five key strokes delete "world", five more type "there"
+/// assert_eq!(tween_typewriter(&from, &to, 0.3).as_str(), "Hello wo");
+/// assert_eq!(tween_typewriter(&from, &to, 0.7).as_str(), "Hello th");
+///
+/// let (greeting, set_greeting) = signal(from);
+/// let typed = AnimatedSignal::new(
+///     move || (greeting.get(), Duration::from_secs(1), easing::LINEAR).into(),
+///     tween_typewriter,
+/// );
+/// set_greeting.set(to);
+/// # let _html =
+/// view! { <p>{move || typed.get().to_string()}</p> }
+/// # .to_html();
+/// ```
+// Tween functions take their values as `&T`, and the values of text animations are `String`s
+#[allow(clippy::ptr_arg)]
+pub fn tween_typewriter(from: &String, to: &String, progress: f64) -> TypedText {
+    let (_, steps) = typewriter_at(from, to, 0);
+    // A progress that lands on a key stroke types it, despite rounding errors
+    let step = (progress.clamp(0.0, 1.0) * steps as f64 + 1e-9).floor() as usize;
+    TypedText {
+        text: typewriter_at(from, to, step).0,
+        offset: Offset::None,
+    }
+}
+
+/// A text that is typed out at a steady pace, and retyped whenever it changes: the end of the shown
+/// text that the new text doesn't share is deleted first, then the rest of the new text is typed.
+///
+/// Unlike an [`AnimatedSignal`] with [`tween_typewriter`], a text that changes while the previous
+/// one is still being typed continues from what is shown, and every key stroke takes the same time
+/// however long the text is. Between key strokes no frames are requested.
+///
+/// [`Typewriter::view()`] renders the typed text hidden from assistive technology and the full
+/// text visually hidden, so screen readers read the text once instead of every key stroke.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::text::Typewriter;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (status, set_status) = signal("Connecting".to_string());
+/// let typewriter = Typewriter::new(status, Duration::from_millis(60));
+/// assert_eq!(typewriter.text().get_untracked(), "");
+/// # let _html =
+/// view! { <p class="status">{typewriter.view()}</p> }
+/// # .to_html();
+/// set_status.set("Connected".to_string());
+/// ```
+#[derive(Clone, Copy)]
+pub struct Typewriter {
+    context: AnimationContext,
+    target: Signal<String>,
+    /// The text the typing started from, the text it types and when it started
+    typing: StoredValue<(String, String, Instant)>,
+    shown: Memo<(String, bool)>,
+}
+
+impl Typewriter {
+    /// Starts typing `text` with `per_glyph` between key strokes, which can also be a
+    /// [`DurationToken`](crate::tokens::DurationToken)
+    pub fn new(text: impl Into<Signal<String>>, per_glyph: impl Into<Duration>) -> Typewriter {
+        let per_glyph = per_glyph.into();
+        let context: AnimationContext = use_context().expect(
+            "No AnimationContext present, call AnimationContext::provide() in a parent scope",
+        );
+        let target = text.into();
+        let typing = StoredValue::new((String::new(), target.get_untracked(), context.now()));
+        let retyped = Trigger::new();
+
+        let shown = Memo::new(move |_| {
+            retyped.track();
+            context.animation_frame.track();
+            let Some((shown, steps, step, elapsed)) = typing.try_with_value(|(from, to, start)| {
+                let elapsed = context.now().saturating_duration_since(*start);
+                let step = match per_glyph.is_zero() {
+                    true => usize::MAX,
+                    false => (elapsed.as_nanos() / per_glyph.as_nanos()) as usize,
+                };
+                let (shown, steps) = typewriter_at(from, to, step);
+                (shown, steps, step, elapsed)
+            }) else {
+                return (String::new(), false);
+            };
+            let typing = step < steps;
+            if typing {
+                // Wake up for the next key stroke, not for every frame of the display
+                let next = per_glyph * (step as u32 + 1);
+                context.request_frame_after(next.saturating_sub(elapsed));
+            }
+            (shown, typing)
+        });
+
+        Effect::new(move |previous: Option<()>| {
+            let text = target.get();
+            if previous.is_some() {
+                let shown = shown.with_untracked(|(shown, _)| shown.clone());
+                typing.set_value((shown, text, context.now()));
+                retyped.notify();
+            }
+        });
+
+        Typewriter {
+            context,
+            target,
+            typing,
+            shown,
+        }
+    }
+
+    /// The text as it is typed so far
+    pub fn text(&self) -> Signal<String> {
+        let shown = self.shown;
+        Signal::derive(move || shown.with(|(shown, _)| shown.clone()))
+    }
+
+    /// Whether the typewriter is still typing or deleting
+    pub fn is_typing(&self) -> Signal<bool> {
+        let shown = self.shown;
+        Signal::derive(move || shown.with(|(_, typing)| *typing))
+    }
+
+    /// Renders the typed text, with the full text for screen readers
+    pub fn view(&self) -> impl IntoView {
+        let text = self.text();
+        let target = self.target;
+        view! {
+            <span style="position: relative">
+                <span style=VISUALLY_HIDDEN>{target}</span>
+                <span aria-hidden="true" style="white-space: pre-wrap">{text}</span>
+            </span>
+        }
+    }
+
+    /// Types the whole text again, starting from nothing
+    pub fn restart(&self) {
+        let text = self.target.get_untracked();
+        self.typing
+            .set_value((String::new(), text, self.context.now()));
+        self.context.request_animation_frame();
+    }
+}
@@ -0,0 +1,129 @@
+//! Callbacks for the moments an animated value crosses a threshold, such as the tick sound of a
+//! counter or the haptic feedback of a slider that snaps into place.
+
+use leptos::prelude::*;
+
+use crate::AnimatedSignal;
+
+/// The thresholds that [`on_cross()`](AnimatedSignal::on_cross) watches
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Threshold {
+    /// A single value
+    At(f64),
+
+    /// Every multiple of `step`, shifted by `offset`, such as every 10 units of a counter
+    Every {
+        /// The distance between thresholds, values that are not positive have no thresholds
+        step: f64,
+        /// A threshold that the others are counted from
+        offset: f64,
+    },
+}
+
+impl Threshold {
+    /// Every multiple of `step`
+    pub fn every(step: f64) -> Threshold {
+        Threshold::Every { step, offset: 0.0 }
+    }
+
+    /// Calls `crossed` with the thresholds from `from` to `to`, in the order they are passed. A
+    /// threshold counts as passed on the way up when it is reached and on the way down when it is
+    /// left, so a value that comes to rest on a threshold crosses it once.
+    fn crossings(&self, from: f64, to: f64, mut crossed: impl FnMut(f64)) {
+        match *self {
+            Threshold::At(threshold) => {
+                if (from < threshold && threshold <= to) || (to < threshold && threshold <= from) {
+                    crossed(threshold);
+                }
+            }
+            Threshold::Every { step, offset } => {
+                if !(step > 0.0 && step.is_finite()) {
+                    return;
+                }
+                let index = |value: f64| ((value - offset) / step).floor() as i64;
+                let at = |index: i64| offset + index as f64 * step;
+                if from < to {
+                    // The thresholds in (from, to]
+                    let mut first = index(from) + 1;
+                    if at(first - 1) > from {
+                        first -= 1;
+                    }
+                    (first..=index(to)).for_each(|index| crossed(at(index)));
+                } else if to < from {
+                    // The thresholds in (to, from], from the top
+                    let last = index(to) + 1;
+                    (last..=index(from))
+                        .rev()
+                        .for_each(|index| crossed(at(index)));
+                }
+            }
+        }
+    }
+}
+
+impl From<f64> for Threshold {
+    fn from(value: f64) -> Self {
+        Threshold::At(value)
+    }
+}
+
+/// The direction in which a value crosses a threshold
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CrossDirection {
+    /// From below the threshold upwards
+    Rising,
+    /// From above the threshold downwards
+    Falling,
+    /// In either direction, the callback still gets told which one. This is the default
+    #[default]
+    Either,
+}
+
+impl<T, I: Clone + Into<f64> + 'static> AnimatedSignal<T, I> {
+    /// Calls `callback` whenever the animated value crosses `threshold` in `direction`, with the
+    /// threshold that was crossed and the direction it was crossed in. The value is checked on
+    /// every animation frame. When the value passes several thresholds between two frames, the
+    /// callback is called once for each of them in the order they were passed, so a fast
+    /// animation doesn't skip ticks.
+    ///
+    /// The callback is called from an effect and stops with the current reactive owner.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::*;
+    /// # use leptos_animation::threshold::{CrossDirection, Threshold};
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (score, set_score) = signal(0.0);
+    /// let counter: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+    ///     move || (score.get(), Duration::from_secs(1), easing::CUBIC_OUT).into(),
+    ///     tween_default,
+    /// );
+    /// // Tick on every 10 points the counter rolls up
+    /// counter.on_cross(Threshold::every(10.0), CrossDirection::Rising, |points, _| {
+    ///     leptos::logging::log!("tick at {points}");
+    /// });
+    /// set_score.set(100.0);
+    /// ```
+    pub fn on_cross(
+        &self,
+        threshold: impl Into<Threshold>,
+        direction: CrossDirection,
+        callback: impl Fn(f64, CrossDirection) + 'static,
+    ) {
+        let threshold = threshold.into();
+        let signal = self.animated_signal;
+        Effect::new(move |previous: Option<f64>| {
+            let value: f64 = signal.get().into();
+            if let Some(previous) = previous {
+                let crossing = match value >= previous {
+                    true => CrossDirection::Rising,
+                    false => CrossDirection::Falling,
+                };
+                if direction == CrossDirection::Either || direction == crossing {
+                    threshold.crossings(previous, value, |crossed| callback(crossed, crossing));
+                }
+            }
+            value
+        });
+    }
+}
@@ -0,0 +1,151 @@
+//! A card that tilts towards the pointer in 3D and springs back when the pointer leaves.
+
+use std::time::Duration;
+
+use leptos::html::ElementType;
+use leptos::prelude::*;
+use leptos::wasm_bindgen::closure::Closure;
+use leptos::wasm_bindgen::JsCast;
+
+use crate::tokens::SpringToken;
+use crate::{easing, tween_default, AnimatedSignal, AnimationMode, AnimationTarget};
+
+/// The time in which the tilt catches up with the pointer
+const FOLLOW_DURATION: Duration = Duration::from_millis(120);
+
+/// The distance of the viewer from the card, the smaller the stronger the 3D effect
+const PERSPECTIVE_PX: f64 = 1000.0;
+
+type PointerListener = Closure<dyn Fn(web_sys::PointerEvent)>;
+
+/// The element with its `pointermove` and `pointerleave` listeners
+type Listeners = (web_sys::Element, PointerListener, PointerListener);
+
+/// The animated rotations of a card tilted by [`use_tilt()`]
+#[derive(Clone, Copy)]
+pub struct Tilt {
+    rotate_x: AnimatedSignal<f64, f64>,
+    rotate_y: AnimatedSignal<f64, f64>,
+}
+
+impl Tilt {
+    /// The rotation around the horizontal axis in degrees, for `rotateX()`
+    pub fn rotate_x(&self) -> Signal<f64, LocalStorage> {
+        *self.rotate_x
+    }
+
+    /// The rotation around the vertical axis in degrees, for `rotateY()`
+    pub fn rotate_y(&self) -> Signal<f64, LocalStorage> {
+        *self.rotate_y
+    }
+
+    /// Both rotations as a CSS transform with a perspective, for the `transform` property
+    pub fn transform(&self) -> Signal<String, LocalStorage> {
+        let (rotate_x, rotate_y) = (self.rotate_x, self.rotate_y);
+        Signal::derive_local(move || {
+            format!(
+                "perspective({PERSPECTIVE_PX}px) rotateX({}deg) rotateY({}deg)",
+                rotate_x.get(),
+                rotate_y.get()
+            )
+        })
+    }
+}
+
+/// Tilts the element behind `node_ref` towards the pointer while it hovers over the element, by at
+/// most `max_deg` degrees in each direction. The edge closest to the pointer comes towards the
+/// viewer. When the pointer leaves, the element springs back flat with the
+/// [`Bouncy`](SpringToken::Bouncy) spring of the current [`MotionTokens`](crate::tokens::MotionTokens).
+///
+/// The element starts flat and stays flat on devices without a hovering pointer.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos::html::Div;
+/// # use leptos_animation::AnimationContext;
+/// # use leptos_animation::tilt::use_tilt;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let card = NodeRef::<Div>::new();
+/// let tilt = use_tilt(card, 10.0);
+/// assert_eq!(tilt.rotate_x().get_untracked(), 0.0);
+/// # let _html =
+/// view! { <div node_ref=card class="card" style:transform=move || tilt.transform().get()>"Hover me"</div> }
+/// # .to_html();
+/// ```
+pub fn use_tilt<E>(node_ref: NodeRef<E>, max_deg: f64) -> Tilt
+where
+    E: ElementType + 'static,
+    E::Output: JsCast + Clone + 'static,
+{
+    // The pointer position over the element from -1.0 to 1.0 on both axes, `None` outside of it
+    let pointer = RwSignal::new(None::<(f64, f64)>);
+    let tilt = move |angle: fn((f64, f64)) -> f64| {
+        AnimatedSignal::new(
+            move || -> AnimationTarget<f64> {
+                match pointer.get() {
+                    Some(position) => (
+                        angle(position) * max_deg,
+                        FOLLOW_DURATION,
+                        easing::QUAD_OUT,
+                        AnimationMode::ReplaceWithVelocity,
+                    )
+                        .into(),
+                    None => (0.0, SpringToken::Bouncy, AnimationMode::ReplaceWithVelocity).into(),
+                }
+            },
+            tween_default,
+        )
+    };
+    let rotate_x = tilt(|(_, y)| y);
+    let rotate_y = tilt(|(x, _)| -x);
+
+    let listeners: StoredValue<Option<Listeners>, LocalStorage> = StoredValue::new_local(None);
+    let remove_listeners = move || {
+        listeners.try_update_value(|listeners| {
+            if let Some((element, move_listener, leave_listener)) = listeners.take() {
+                let _ = element.remove_event_listener_with_callback(
+                    "pointermove",
+                    move_listener.as_ref().unchecked_ref(),
+                );
+                let _ = element.remove_event_listener_with_callback(
+                    "pointerleave",
+                    leave_listener.as_ref().unchecked_ref(),
+                );
+            }
+        });
+    };
+
+    Effect::new(move |_| {
+        remove_listeners();
+        let Some(element) = node_ref.get() else {
+            return;
+        };
+        let element: web_sys::Element = element.unchecked_into();
+
+        let move_listener = Closure::<dyn Fn(web_sys::PointerEvent)>::new({
+            let element = element.clone();
+            move |event: web_sys::PointerEvent| {
+                let rect = element.get_bounding_client_rect();
+                if rect.width() <= 0.0 || rect.height() <= 0.0 {
+                    return;
+                }
+                let x = (event.client_x() as f64 - rect.left()) / rect.width() * 2.0 - 1.0;
+                let y = (event.client_y() as f64 - rect.top()) / rect.height() * 2.0 - 1.0;
+                pointer.set(Some((x.clamp(-1.0, 1.0), y.clamp(-1.0, 1.0))));
+            }
+        });
+        let leave_listener =
+            Closure::<dyn Fn(web_sys::PointerEvent)>::new(move |_| pointer.set(None));
+        let _ = element.add_event_listener_with_callback(
+            "pointermove",
+            move_listener.as_ref().unchecked_ref(),
+        );
+        let _ = element.add_event_listener_with_callback(
+            "pointerleave",
+            leave_listener.as_ref().unchecked_ref(),
+        );
+        listeners.set_value(Some((element, move_listener, leave_listener)));
+    });
+    on_cleanup(remove_listeners);
+
+    Tilt { rotate_x, rotate_y }
+}
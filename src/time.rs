@@ -0,0 +1,145 @@
+//! Animated spans of time, for time-valued UI such as countdown bars and timers.
+
+use std::ops::{Add, Mul, Neg, Sub};
+use std::time::Duration;
+
+use instant::Instant;
+
+use crate::tween::Tween;
+
+/// A span of time that can be negative, which animated signals use for durations and the
+/// differences between instants. Unlike [`Duration`] it supports the `Add`, `Sub` and `Mul<f64>`
+/// that [`tween_default`](crate::tween_default) and additive animations need, because the
+/// animations in between two durations run below zero when they are interrupted.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos_animation::time::TimeDelta;
+/// let half = TimeDelta::from(Duration::from_millis(500));
+/// assert_eq!((half - TimeDelta::from_secs_f64(2.0)).as_secs_f64(), -1.5);
+/// assert_eq!((half * 3.0).to_duration(), Duration::from_millis(1500));
+/// // Negative spans have no duration
+/// assert_eq!((-half).to_duration(), Duration::ZERO);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct TimeDelta(f64);
+
+impl TimeDelta {
+    /// No time at all
+    pub const ZERO: TimeDelta = TimeDelta(0.0);
+
+    /// A span of `seconds`, which may be negative
+    pub fn from_secs_f64(seconds: f64) -> TimeDelta {
+        TimeDelta(seconds)
+    }
+
+    /// The time from `earlier` to `later`, negative when `later` comes first
+    pub fn between(earlier: Instant, later: Instant) -> TimeDelta {
+        match later.checked_duration_since(earlier) {
+            Some(elapsed) => TimeDelta::from(elapsed),
+            None => -TimeDelta::from(earlier.duration_since(later)),
+        }
+    }
+
+    /// The span in seconds
+    pub fn as_secs_f64(self) -> f64 {
+        self.0
+    }
+
+    /// Whether the span is below zero
+    pub fn is_negative(self) -> bool {
+        self.0 < 0.0
+    }
+
+    /// The span as a [`Duration`], which is zero for negative spans
+    pub fn to_duration(self) -> Duration {
+        Duration::try_from_secs_f64(self.0).unwrap_or(Duration::ZERO)
+    }
+}
+
+/// `duration` times `factor` like [`Duration::mul_f64()`], but saturating at [`Duration::MAX`]
+/// instead of panicking, and zero for negative and NaN factors
+pub(crate) fn saturating_mul_f64(duration: Duration, factor: f64) -> Duration {
+    let seconds = duration.as_secs_f64() * factor;
+    if seconds.is_nan() || seconds <= 0.0 {
+        return Duration::ZERO;
+    }
+    Duration::try_from_secs_f64(seconds).unwrap_or(Duration::MAX)
+}
+
+impl From<Duration> for TimeDelta {
+    fn from(duration: Duration) -> Self {
+        TimeDelta(duration.as_secs_f64())
+    }
+}
+
+impl Add for TimeDelta {
+    type Output = TimeDelta;
+
+    fn add(self, rhs: TimeDelta) -> TimeDelta {
+        TimeDelta(self.0 + rhs.0)
+    }
+}
+
+impl Sub for TimeDelta {
+    type Output = TimeDelta;
+
+    fn sub(self, rhs: TimeDelta) -> TimeDelta {
+        TimeDelta(self.0 - rhs.0)
+    }
+}
+
+impl Neg for TimeDelta {
+    type Output = TimeDelta;
+
+    fn neg(self) -> TimeDelta {
+        TimeDelta(-self.0)
+    }
+}
+
+impl Mul<f64> for TimeDelta {
+    type Output = TimeDelta;
+
+    fn mul(self, rhs: f64) -> TimeDelta {
+        TimeDelta(self.0 * rhs)
+    }
+}
+
+impl Tween for TimeDelta {
+    type Interpolated = TimeDelta;
+
+    fn tween(&self, other: &Self, progress: f64) -> TimeDelta {
+        TimeDelta(self.0.tween(&other.0, progress))
+    }
+}
+
+/// Interpolates between two durations, for signals whose source is a [`Duration`]. The values in
+/// between are [`TimeDelta`]s, which can run below zero when an animation overshoots or is
+/// interrupted, [`to_duration()`](TimeDelta::to_duration) turns them back into durations.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::time::{tween_duration, TimeDelta};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let total = Duration::from_secs(30);
+/// let (remaining, set_remaining) = signal(total);
+/// let animated_remaining = AnimatedSignal::new(move || remaining.get().into(), tween_duration);
+/// assert_eq!(animated_remaining.get_untracked().to_duration(), total);
+///
+/// // Sources of time deltas animate with the default tween
+/// let (deadline_offset, set_deadline_offset) = signal(TimeDelta::from_secs_f64(-5.0));
+/// let animated_offset = AnimatedSignal::new(move || deadline_offset.get().into(), tween_default);
+/// # let _html =
+/// view! {
+///     <div
+///         class="countdown"
+///         style:width=move || {
+///             format!("{}%", animated_remaining.get().as_secs_f64() / total.as_secs_f64() * 100.0)
+///         }
+///     ></div>
+/// }
+/// # .to_html();
+/// ```
+pub fn tween_duration(from: &Duration, to: &Duration, progress: f64) -> TimeDelta {
+    TimeDelta::from(*from).tween(&TimeDelta::from(*to), progress)
+}
@@ -0,0 +1,598 @@
+//! Sequences of animations across several values that are played, paused and seeked as a whole.
+
+use std::fmt::Write;
+use std::ops::Deref;
+use std::time::Duration;
+
+use instant::Instant;
+use leptos::prelude::*;
+// The `Track` struct of this module shadows the trait of the prelude
+use leptos::prelude::Track as _;
+
+use crate::css::{css_linear, format_number};
+use crate::{Animation, AnimationContext, AnimationTarget, Easing, RepeatMode};
+
+struct TimelineState {
+    /// When the timeline started playing from `offset`, `None` while paused
+    playing_since: Option<Instant>,
+    /// The position at the time the timeline started playing or was paused
+    offset: Duration,
+    /// The end of the latest step
+    duration: Duration,
+    /// The start of the latest step, where steps added with `with()` start
+    cursor: Duration,
+    /// Where steps added with `then()` start, the end of all steps so far plus any delays
+    end: Duration,
+}
+
+/// A sequence of animations that can overlap and that can span several values, played back with a
+/// single handle.
+///
+/// The values a timeline animates are its [`Track`]s. Steps are added in order: a step added with
+/// [`then()`](Timeline::then) starts when all previous steps have ended, a step added with
+/// [`with()`](Timeline::with) starts together with the previous step and [`after()`](Timeline::after)
+/// leaves a gap before the next step. Every step animates its track from the target of the previous
+/// step of that track (or from its initial value) towards a new target. The duration, easing,
+/// delay, keyframes and repetitions of the target are used, the mode is ignored. Infinitely
+/// repeating targets play once.
+///
+/// A timeline starts paused at position zero.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::timeline::Timeline;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let timeline = Timeline::new();
+/// let x = timeline.track(0.0, tween_default);
+/// let opacity = timeline.track(0.0, tween_default);
+///
+/// // Fade in while sliding in, wait a moment and slide back
+/// let second = Duration::from_secs(1);
+/// timeline
+///     .then(x, (100.0, second, easing::LINEAR))
+///     .with(opacity, (1.0, second / 2, easing::LINEAR))
+///     .after(second)
+///     .then(x, (0.0, second, easing::LINEAR));
+/// assert_eq!(timeline.duration(), second * 3);
+///
+/// timeline.seek(second / 2);
+/// assert_eq!(x.get_untracked(), 50.0);
+/// assert_eq!(opacity.get_untracked(), 1.0);
+///
+/// timeline.seek(second * 2);
+/// assert_eq!(x.get_untracked(), 100.0);
+/// ```
+#[derive(Copy, Clone)]
+pub struct Timeline {
+    context: AnimationContext,
+    epoch: Instant,
+    state: StoredValue<TimelineState>,
+    changed: Trigger,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timeline {
+    /// Creates an empty timeline
+    pub fn new() -> Timeline {
+        let context: AnimationContext = use_context().expect(
+            "No AnimationContext present, call AnimationContext::provide() in a parent scope",
+        );
+        Timeline {
+            context,
+            epoch: context.now(),
+            state: StoredValue::new(TimelineState {
+                playing_since: None,
+                offset: Duration::ZERO,
+                duration: Duration::ZERO,
+                cursor: Duration::ZERO,
+                end: Duration::ZERO,
+            }),
+            changed: Trigger::new(),
+        }
+    }
+
+    /// Creates a new value that is animated by this timeline, with `initial` as its value
+    /// before its first step
+    pub fn track<T: Clone + 'static, I: Clone + 'static>(
+        &self,
+        initial: T,
+        tween: fn(&T, &T, f64) -> I,
+    ) -> Track<T, I> {
+        let timeline = *self;
+        let initial_i = tween(&initial, &initial, 1.0);
+        let steps: StoredValue<Vec<Animation<T, I>>, LocalStorage> =
+            StoredValue::new_local(Vec::new());
+
+        let signal = Signal::derive_local(move || {
+            timeline.changed.track();
+            timeline.context.animation_frame.track();
+            let position = timeline.position();
+            if timeline.is_playing() && position < timeline.duration() {
+                timeline.context.request_animation_frame();
+            }
+
+            let now = timeline.epoch + position;
+            steps.with_value(|steps| {
+                steps
+                    .iter()
+                    .rev()
+                    .find(|step| step.start <= now)
+                    .map(|step| step.value(&tween, now))
+                    .unwrap_or_else(|| initial_i.clone())
+            })
+        });
+
+        Track {
+            timeline,
+            initial: StoredValue::new_local(initial),
+            steps,
+            tween,
+            signal,
+        }
+    }
+
+    fn add_step<T: Clone, I>(
+        &self,
+        track: Track<T, I>,
+        start: Duration,
+        target: AnimationTarget<T>,
+    ) {
+        let from = track.steps.with_value(|steps| match steps.last() {
+            Some(step) => step.to.clone(),
+            None => track.initial.get_value(),
+        });
+        let step = Animation {
+            from,
+            to_i: (track.tween)(&target.target, &target.target, 1.0),
+            to: target.target,
+            start: self.epoch + start + target.delay,
+            duration: target.duration,
+            easing: target.easing,
+            keyframes: target.keyframes,
+            repeat: match target.repeat {
+                // A timeline has to end, so infinitely repeating steps play once
+                RepeatMode::Infinite | RepeatMode::AlternateInfinite => RepeatMode::Count(1),
+                repeat => repeat,
+            },
+            from_rest: false,
+            #[cfg(not(feature = "disable-animations"))]
+            on_complete: None,
+        };
+        let end = start + target.delay + step.total_duration().unwrap_or(step.duration);
+        track.steps.update_value(|steps| steps.push(step));
+
+        self.state.update_value(|state| {
+            state.cursor = start;
+            state.end = state.end.max(end);
+            state.duration = state.duration.max(end);
+        });
+        self.changed.notify();
+    }
+
+    /// Adds a step that starts when all previous steps have ended
+    pub fn then<T: Clone, I>(
+        self,
+        track: Track<T, I>,
+        target: impl Into<AnimationTarget<T>>,
+    ) -> Self {
+        let start = self.state.with_value(|state| state.end);
+        self.add_step(track, start, target.into());
+        self
+    }
+
+    /// Adds a step that starts at the same time as the previous step
+    pub fn with<T: Clone, I>(
+        self,
+        track: Track<T, I>,
+        target: impl Into<AnimationTarget<T>>,
+    ) -> Self {
+        let start = self.state.with_value(|state| state.cursor);
+        self.add_step(track, start, target.into());
+        self
+    }
+
+    /// Delays the next step added with [`then()`](Timeline::then) by `delay`
+    pub fn after(self, delay: Duration) -> Self {
+        self.state.update_value(|state| {
+            state.end += delay;
+            state.duration = state.duration.max(state.end);
+        });
+        self
+    }
+
+    /// The total duration of all steps
+    pub fn duration(&self) -> Duration {
+        self.state.with_value(|state| state.duration)
+    }
+
+    /// The current position of the playback
+    pub fn position(&self) -> Duration {
+        let now = self.context.now();
+        self.state.with_value(|state| match state.playing_since {
+            Some(since) => {
+                (state.offset + now.saturating_duration_since(since)).min(state.duration)
+            }
+            None => state.offset,
+        })
+    }
+
+    /// Whether the timeline is playing, it keeps playing at its end until it is paused
+    pub fn is_playing(&self) -> bool {
+        self.state.with_value(|state| state.playing_since.is_some())
+    }
+
+    /// Plays the timeline from its current position, or from the start if it is at its end
+    pub fn play(&self) {
+        let position = self.position();
+        let now = self.context.now();
+        self.state.update_value(|state| {
+            if position >= state.duration {
+                state.offset = Duration::ZERO;
+            } else {
+                state.offset = position;
+            }
+            state.playing_since = Some(now);
+        });
+        self.changed.notify();
+    }
+
+    /// Pauses the timeline at its current position
+    pub fn pause(&self) {
+        let position = self.position();
+        self.state.update_value(|state| {
+            state.offset = position;
+            state.playing_since = None;
+        });
+        self.changed.notify();
+    }
+
+    /// Starts an export of the steps of this timeline as keyframes, see [`TimelineExport`]
+    pub fn export(&self) -> TimelineExport {
+        TimelineExport {
+            duration: self.duration(),
+            keyframes: Vec::new(),
+        }
+    }
+
+    /// Jumps to `position`, the timeline keeps playing if it was playing
+    pub fn seek(&self, position: Duration) {
+        let now = self.context.now();
+        self.state.update_value(|state| {
+            state.offset = position.min(state.duration);
+            if state.playing_since.is_some() {
+                state.playing_since = Some(now);
+            }
+        });
+        self.changed.notify();
+    }
+}
+
+/// A value that is animated by a [`Timeline`], created with [`Timeline::track()`].
+/// The animated value is available by dereferencing to a signal.
+pub struct Track<T: 'static, I: 'static> {
+    timeline: Timeline,
+    initial: StoredValue<T, LocalStorage>,
+    steps: StoredValue<Vec<Animation<T, I>>, LocalStorage>,
+    tween: fn(&T, &T, f64) -> I,
+    signal: Signal<I, LocalStorage>,
+}
+
+impl<T, I> Clone for Track<T, I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, I> Copy for Track<T, I> {}
+
+impl<T, I> Track<T, I> {
+    /// The timeline this track belongs to
+    pub fn timeline(&self) -> Timeline {
+        self.timeline
+    }
+}
+
+impl<T, I> Deref for Track<T, I> {
+    type Target = Signal<I, LocalStorage>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.signal
+    }
+}
+
+/// The number of samples that tell linear easings apart from the others
+const LINEAR_SAMPLES: usize = 32;
+
+/// A keyframe of a single property of a [`TimelineExport`]
+struct ExportedKeyframe {
+    at: Duration,
+    property: String,
+    value: String,
+    /// The CSS timing function towards the next keyframe of the property
+    easing: String,
+}
+
+/// The tracks of a [`Timeline`] as keyframes for the [Web Animations
+/// API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Animations_API), so a sequence designed
+/// with this crate can be handed to design tools or played by `element.animate()` without the wasm.
+///
+/// Every exported track becomes a property, with its values written as CSS by a format function.
+/// The steps of the track are written out as they play: delays and gaps hold the value, repetitions
+/// are unrolled and the easing of every segment is sampled into a CSS `linear()` timing function.
+/// [`to_waapi_json()`](TimelineExport::to_waapi_json) writes the keyframes and the timing options
+/// as JSON. Property names are used as they are given, WAAPI expects them in camel case such as
+/// `backgroundColor`.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::timeline::Timeline;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let timeline = Timeline::new();
+/// let x = timeline.track(0.0, tween_default);
+/// let opacity = timeline.track(0.0, tween_default);
+/// let second = Duration::from_secs(1);
+/// timeline
+///     .then(opacity, (1.0, second, easing::LINEAR))
+///     .then(x, (100.0, second, easing::LINEAR));
+///
+/// let json = timeline
+///     .export()
+///     .track(opacity, "opacity", |opacity: &f64| opacity.to_string())
+///     .track(x, "transform", |x: &f64| format!("translateX({x}px)"))
+///     .to_waapi_json();
+/// assert_eq!(
+///     json,
+///     concat!(
+///         r#"{"keyframes": ["#,
+///         r#"{"offset": 0, "easing": "linear", "opacity": "0"}, "#,
+///         r#"{"offset": 0, "easing": "linear", "transform": "translateX(0px)"}, "#,
+///         r#"{"offset": 0.5, "easing": "linear", "opacity": "1"}, "#,
+///         r#"{"offset": 0.5, "easing": "linear", "transform": "translateX(0px)"}, "#,
+///         r#"{"offset": 1, "easing": "linear", "opacity": "1"}, "#,
+///         r#"{"offset": 1, "easing": "linear", "transform": "translateX(100px)"}"#,
+///         r#"], "options": {"duration": 2000, "fill": "both"}}"#,
+///     )
+/// );
+/// ```
+pub struct TimelineExport {
+    duration: Duration,
+    keyframes: Vec<ExportedKeyframe>,
+}
+
+/// A part of a step between two of its keyframes within a single repetition
+struct Segment<'a, T> {
+    start: Duration,
+    end: Duration,
+    from: &'a T,
+    to: &'a T,
+    easing: &'a Easing,
+    /// The progress of the segment at its start and end, which runs backwards in alternate
+    /// repetitions
+    progress: (f64, f64),
+}
+
+/// The segments of `step` in the order they play, with times measured from `epoch`
+fn segments<'a, T, I>(step: &'a Animation<T, I>, epoch: Instant) -> Vec<Segment<'a, T>> {
+    let start = step.start.saturating_duration_since(epoch);
+    let mut offsets = vec![(0.0, &step.from)];
+    offsets.extend(
+        step.keyframes
+            .iter()
+            .map(|keyframe| (keyframe.offset, &keyframe.value)),
+    );
+    let easings = step.keyframes.iter().map(|keyframe| &keyframe.easing);
+    let forward: Vec<(f64, f64, &T, &T, &Easing)> = offsets
+        .iter()
+        .zip(
+            offsets
+                .iter()
+                .skip(1)
+                .map(|(offset, value)| (*offset, *value))
+                .chain([(1.0, &step.to)]),
+        )
+        .zip(easings.chain([&step.easing]))
+        .map(|((&(a, from), (b, to)), easing)| (a, b, from, to, easing))
+        .collect();
+
+    let mut segments = Vec::new();
+    for repetition in 0..step.repeat.count().unwrap_or(1) {
+        let at = |offset: f64| start + step.duration.mul_f64(repetition as f64 + offset);
+        if step.repeat.alternates() && repetition % 2 == 1 {
+            for &(a, b, from, to, easing) in forward.iter().rev() {
+                segments.push(Segment {
+                    start: at(1.0 - b),
+                    end: at(1.0 - a),
+                    from,
+                    to,
+                    easing,
+                    progress: (1.0, 0.0),
+                });
+            }
+        } else {
+            for &(a, b, from, to, easing) in &forward {
+                segments.push(Segment {
+                    start: at(a),
+                    end: at(b),
+                    from,
+                    to,
+                    easing,
+                    progress: (0.0, 1.0),
+                });
+            }
+        }
+    }
+    segments
+}
+
+/// The CSS timing function of the part of `easing` from progress `from` to `to`
+fn segment_easing(easing: &Easing, (from, to): (f64, f64)) -> String {
+    let (eased_from, eased_to) = (easing.ease(from), easing.ease(to));
+    if (eased_to - eased_from).abs() < 1e-12 {
+        return "linear".to_string();
+    }
+    let ease = |progress: f64| {
+        (easing.ease(from + (to - from) * progress) - eased_from) / (eased_to - eased_from)
+    };
+    // Write linear parts, such as the parts of linear easings, with the CSS keyword
+    let is_linear = (0..=LINEAR_SAMPLES).all(|sample| {
+        let progress = sample as f64 / LINEAR_SAMPLES as f64;
+        (ease(progress) - progress).abs() < 1e-6
+    });
+    match is_linear {
+        true => "linear".to_string(),
+        false => css_linear(ease),
+    }
+}
+
+/// Adds a keyframe at `at` whose value eases towards the next one with `easing`, `None` while the
+/// value holds
+fn push_stop(
+    stops: &mut Vec<(Duration, String, Option<String>)>,
+    at: Duration,
+    value: String,
+    easing: Option<String>,
+) {
+    if let Some((last_at, last_value, last_easing)) = stops.last_mut() {
+        if *last_at == at && *last_value == value {
+            *last_easing = easing;
+            return;
+        }
+        // Hold the value until the next step starts instead of easing towards it
+        if *last_at < at && *last_value != value && last_easing.is_none() {
+            let held = last_value.clone();
+            stops.push((at, held, None));
+        }
+    }
+    stops.push((at, value, easing));
+}
+
+impl TimelineExport {
+    /// Adds the keyframes of `track` as `property`, with `format` writing its values as CSS
+    pub fn track<T: Clone, I>(
+        mut self,
+        track: Track<T, I>,
+        property: impl Into<String>,
+        format: impl Fn(&I) -> String,
+    ) -> Self {
+        let property = property.into();
+        let tween = track.tween;
+        let epoch = track.timeline.epoch;
+        let duration = self.duration;
+
+        let mut stops = Vec::new();
+        let initial = track
+            .initial
+            .with_value(|initial| format(&tween(initial, initial, 1.0)));
+        push_stop(&mut stops, Duration::ZERO, initial, None);
+        track.steps.with_value(|steps| {
+            for (index, step) in steps.iter().enumerate() {
+                // A track shows the latest added step that has started
+                let cut = steps[index + 1..]
+                    .iter()
+                    .map(|later| later.start.saturating_duration_since(epoch))
+                    .fold(duration, Duration::min);
+                for segment in segments(step, epoch) {
+                    // Steps that start at the very end still show at the end
+                    if segment.start > cut || (segment.start == cut && cut < duration) {
+                        break;
+                    }
+                    let (from, mut to) = segment.progress;
+                    let mut end = segment.end;
+                    if end > cut {
+                        let part = (cut - segment.start).as_secs_f64()
+                            / (end - segment.start).as_secs_f64();
+                        to = from + (to - from) * part;
+                        end = cut;
+                    }
+                    let value = |progress: f64| {
+                        format(&tween(
+                            segment.from,
+                            segment.to,
+                            segment.easing.ease(progress),
+                        ))
+                    };
+                    let easing = segment_easing(segment.easing, (from, to));
+                    push_stop(&mut stops, segment.start, value(from), Some(easing));
+                    push_stop(&mut stops, end, value(to), None);
+                }
+            }
+        });
+        if let Some((at, value, _)) = stops.last() {
+            if *at < duration {
+                let value = value.clone();
+                stops.push((duration, value, None));
+            }
+        }
+
+        self.keyframes.extend(
+            stops
+                .into_iter()
+                .map(|(at, value, easing)| ExportedKeyframe {
+                    at,
+                    property: property.clone(),
+                    value,
+                    easing: easing.unwrap_or_else(|| "linear".to_string()),
+                }),
+        );
+        self
+    }
+
+    /// The keyframes and timing options for `element.animate(keyframes, options)`, as a JSON
+    /// object with the two fields `keyframes` and `options`. The keyframes of all properties are
+    /// sorted by their offset.
+    pub fn to_waapi_json(&self) -> String {
+        let mut keyframes: Vec<&ExportedKeyframe> = self.keyframes.iter().collect();
+        keyframes.sort_by_key(|keyframe| keyframe.at);
+
+        let mut json = String::from("{\"keyframes\": [");
+        for (index, keyframe) in keyframes.iter().enumerate() {
+            let offset = match self.duration.is_zero() {
+                true => 1.0,
+                false => keyframe.at.as_secs_f64() / self.duration.as_secs_f64(),
+            };
+            if index > 0 {
+                json.push_str(", ");
+            }
+            let _ = write!(
+                json,
+                "{{\"offset\": {}, \"easing\": {}, {}: {}}}",
+                format_number(offset),
+                json_string(&keyframe.easing),
+                json_string(&keyframe.property),
+                json_string(&keyframe.value)
+            );
+        }
+        let _ = write!(
+            json,
+            "], \"options\": {{\"duration\": {}, \"fill\": \"both\"}}}}",
+            format_number(self.duration.as_secs_f64() * 1000.0)
+        );
+        json
+    }
+}
+
+/// `value` as a quoted and escaped JSON string
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            character if character.is_control() => {
+                let _ = write!(json, "\\u{:04x}", character as u32);
+            }
+            character => json.push(character),
+        }
+    }
+    json.push('"');
+    json
+}
@@ -0,0 +1,310 @@
+//! Named motion tokens, so a design system can re-theme the motion of a whole application at once.
+
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use crate::animation_target::DEFAULT_MODE;
+use crate::variants::Transition;
+use crate::{AnimationMode, AnimationTarget, Easing};
+
+/// The name of a duration of [`MotionTokens`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DurationToken {
+    /// Small changes such as the state of a button
+    Fast,
+    /// Most transitions
+    Normal,
+    /// Large elements moving across the screen
+    Slow,
+}
+
+/// The name of an easing of [`MotionTokens`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EasingToken {
+    /// Utility transitions that should not draw attention
+    Standard,
+    /// Transitions that should feel expressive, such as elements entering the screen
+    Emphasized,
+}
+
+/// The name of a spring preset of [`MotionTokens`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpringToken {
+    /// Settles smoothly without overshooting
+    Gentle,
+    /// Overshoots and bounces back noticeably
+    Bouncy,
+    /// Settles quickly with a slight overshoot
+    Snappy,
+}
+
+/// A spring preset, expressed as the duration of the animation and an easing that follows the spring
+#[derive(Clone, Debug)]
+pub struct Spring {
+    /// The time the spring needs to settle
+    pub duration: Duration,
+    /// The motion of the spring from 0.0 to 1.0
+    pub easing: Easing,
+}
+
+/// The durations, easings and spring presets of a design system. Components refer to them by name
+/// (such as [`DurationToken::Fast`]) instead of hardcoding values, and the tokens provided for a
+/// scope decide the actual motion. The default tokens are modelled after Material 3.
+///
+/// Tuples of a target and tokens convert into an [`AnimationTarget`], resolved with the tokens of
+/// the current scope (or the default tokens if none were provided):
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::tokens::{DurationToken, EasingToken, MotionTokens, SpringToken};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// // Make all motion of the application a bit calmer
+/// let mut tokens = MotionTokens::default();
+/// tokens.fast = Duration::from_millis(250);
+/// tokens.clone().provide();
+///
+/// let (open, set_open) = signal(false);
+/// let height: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+///     move || (if open.get() { 200.0 } else { 0.0 }, DurationToken::Fast, EasingToken::Emphasized).into(),
+///     tween_default,
+/// );
+/// set_open.set(true);
+///
+/// let target: AnimationTarget<f64> = (1.0, SpringToken::Bouncy).into();
+/// assert_eq!(target.duration, tokens.spring(SpringToken::Bouncy).duration);
+/// ```
+///
+/// The helpers of this crate that take a duration or an easing take tokens as well, and the
+/// defaults of [`Transition`](struct@crate::variants::Transition) and
+/// [`Ducking`](crate::ducking::Ducking) come from the tokens:
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::text::Typewriter;
+/// # use leptos_animation::tokens::{DurationToken, MotionTokens};
+/// # use leptos_animation::variants::Transition;
+/// # use std::time::Duration;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (status, set_status) = signal("Connecting".to_string());
+/// let typewriter = Typewriter::new(status, DurationToken::Fast);
+///
+/// assert_eq!(Transition::default().duration, MotionTokens::current().normal);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MotionTokens {
+    /// The duration of [`DurationToken::Fast`]
+    pub fast: Duration,
+    /// The duration of [`DurationToken::Normal`]
+    pub normal: Duration,
+    /// The duration of [`DurationToken::Slow`]
+    pub slow: Duration,
+
+    /// The easing of [`EasingToken::Standard`]
+    pub standard: Easing,
+    /// The easing of [`EasingToken::Emphasized`]
+    pub emphasized: Easing,
+
+    /// The spring of [`SpringToken::Gentle`]
+    pub gentle: Spring,
+    /// The spring of [`SpringToken::Bouncy`]
+    pub bouncy: Spring,
+    /// The spring of [`SpringToken::Snappy`]
+    pub snappy: Spring,
+}
+
+impl Default for MotionTokens {
+    fn default() -> Self {
+        MotionTokens {
+            fast: Duration::from_millis(200),
+            normal: Duration::from_millis(300),
+            slow: Duration::from_millis(500),
+            standard: STANDARD,
+            emphasized: EMPHASIZED,
+            gentle: Spring {
+                duration: Duration::from_millis(600),
+                easing: SPRING_GENTLE,
+            },
+            bouncy: Spring {
+                duration: Duration::from_millis(800),
+                easing: SPRING_BOUNCY,
+            },
+            snappy: Spring {
+                duration: Duration::from_millis(350),
+                easing: SPRING_SNAPPY,
+            },
+        }
+    }
+}
+
+impl MotionTokens {
+    /// Provides these tokens for this scope and all child scopes
+    pub fn provide(self) {
+        provide_context(self);
+    }
+
+    /// The tokens provided for the current scope, or the default tokens if none were provided
+    pub fn current() -> MotionTokens {
+        use_context().unwrap_or_default()
+    }
+
+    /// The duration named `token`
+    pub fn duration(&self, token: DurationToken) -> Duration {
+        match token {
+            DurationToken::Fast => self.fast,
+            DurationToken::Normal => self.normal,
+            DurationToken::Slow => self.slow,
+        }
+    }
+
+    /// The easing named `token`
+    pub fn easing(&self, token: EasingToken) -> Easing {
+        match token {
+            EasingToken::Standard => self.standard.clone(),
+            EasingToken::Emphasized => self.emphasized.clone(),
+        }
+    }
+
+    /// The spring named `token`
+    pub fn spring(&self, token: SpringToken) -> Spring {
+        match token {
+            SpringToken::Gentle => self.gentle.clone(),
+            SpringToken::Bouncy => self.bouncy.clone(),
+            SpringToken::Snappy => self.snappy.clone(),
+        }
+    }
+}
+
+/// The duration named by the token in the tokens of the current scope
+impl From<DurationToken> for Duration {
+    fn from(token: DurationToken) -> Self {
+        MotionTokens::current().duration(token)
+    }
+}
+
+/// The easing named by the token in the tokens of the current scope
+impl From<EasingToken> for Easing {
+    fn from(token: EasingToken) -> Self {
+        MotionTokens::current().easing(token)
+    }
+}
+
+impl<T> From<(T, DurationToken)> for AnimationTarget<T> {
+    fn from((target, duration): (T, DurationToken)) -> Self {
+        (target, MotionTokens::current().duration(duration)).into()
+    }
+}
+
+impl<T> From<(T, DurationToken, EasingToken)> for AnimationTarget<T> {
+    fn from((target, duration, easing): (T, DurationToken, EasingToken)) -> Self {
+        let tokens = MotionTokens::current();
+        (target, tokens.duration(duration), tokens.easing(easing)).into()
+    }
+}
+
+impl<T> From<(T, DurationToken, EasingToken, AnimationMode)> for AnimationTarget<T> {
+    fn from(
+        (target, duration, easing, mode): (T, DurationToken, EasingToken, AnimationMode),
+    ) -> Self {
+        let tokens = MotionTokens::current();
+        (
+            target,
+            tokens.duration(duration),
+            tokens.easing(easing),
+            mode,
+        )
+            .into()
+    }
+}
+
+impl<T> From<(T, SpringToken)> for AnimationTarget<T> {
+    fn from((target, spring): (T, SpringToken)) -> Self {
+        let spring = MotionTokens::current().spring(spring);
+        (target, spring.duration, spring.easing).into()
+    }
+}
+
+impl<T> From<(T, SpringToken, AnimationMode)> for AnimationTarget<T> {
+    fn from((target, spring, mode): (T, SpringToken, AnimationMode)) -> Self {
+        let spring = MotionTokens::current().spring(spring);
+        (target, spring.duration, spring.easing, mode).into()
+    }
+}
+
+impl Transition {
+    /// A transition with the duration and easing named by the tokens of the current scope
+    pub fn from_tokens(duration: DurationToken, easing: EasingToken) -> Transition {
+        let tokens = MotionTokens::current();
+        Transition {
+            duration: tokens.duration(duration),
+            easing: tokens.easing(easing),
+            mode: DEFAULT_MODE,
+        }
+    }
+
+    /// A transition that follows the spring named by the tokens of the current scope
+    pub fn spring(spring: SpringToken) -> Transition {
+        let spring = MotionTokens::current().spring(spring);
+        Transition {
+            duration: spring.duration,
+            easing: spring.easing,
+            mode: DEFAULT_MODE,
+        }
+    }
+}
+
+/// The Material 3 standard easing, `cubic-bezier(0.2, 0, 0, 1)`
+const STANDARD: Easing = Easing::from_fn(|t: f64| -> f64 { cubic_bezier(0.2, 0.0, 0.0, 1.0, t) });
+
+/// The Material 3 emphasized decelerate easing, `cubic-bezier(0.05, 0.7, 0.1, 1)`
+const EMPHASIZED: Easing =
+    Easing::from_fn(|t: f64| -> f64 { cubic_bezier(0.05, 0.7, 0.1, 1.0, t) });
+
+const SPRING_GENTLE: Easing = Easing::from_fn(|t: f64| -> f64 { damped_spring(1.0, t) });
+const SPRING_BOUNCY: Easing = Easing::from_fn(|t: f64| -> f64 { damped_spring(0.4, t) });
+const SPRING_SNAPPY: Easing = Easing::from_fn(|t: f64| -> f64 { damped_spring(0.75, t) });
+
+/// Evaluates a CSS cubic bezier easing curve with control points (x1, y1) and (x2, y2)
+fn cubic_bezier(x1: f64, y1: f64, x2: f64, y2: f64, t: f64) -> f64 {
+    if t <= 0.0 {
+        return 0.0;
+    }
+    if t >= 1.0 {
+        return 1.0;
+    }
+    let bezier = |a: f64, b: f64, s: f64| {
+        let inverse = 1.0 - s;
+        3.0 * inverse * inverse * s * a + 3.0 * inverse * s * s * b + s * s * s
+    };
+
+    // x grows monotonically with s for control points within 0..=1, so bisect for the s of x = t
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..32 {
+        let s = (low + high) / 2.0;
+        if bezier(x1, x2, s) < t {
+            low = s;
+        } else {
+            high = s;
+        }
+    }
+    bezier(y1, y2, (low + high) / 2.0)
+}
+
+/// The position of a spring released at 0.0 towards 1.0, scaled so it has settled at the end
+/// of the animation. `damping_ratio` is 1.0 for a spring without overshoot and lower for bouncier springs.
+fn damped_spring(damping_ratio: f64, t: f64) -> f64 {
+    if t >= 1.0 {
+        return 1.0;
+    }
+    // Decay the amplitude to less than 0.1% by the end of the animation
+    let decay = 8.0;
+    if damping_ratio >= 1.0 {
+        let frequency = decay + 2.0;
+        return 1.0 - (1.0 + frequency * t) * (-frequency * t).exp();
+    }
+    let frequency = decay / damping_ratio;
+    let damped_frequency = frequency * (1.0 - damping_ratio * damping_ratio).sqrt();
+    1.0 - (-decay * t).exp()
+        * ((damped_frequency * t).cos() + decay / damped_frequency * (damped_frequency * t).sin())
+}
@@ -0,0 +1,305 @@
+//! Complete 2D transforms that are animated as one value, the way CSS animates the `transform`
+//! property between different lists of transform functions.
+
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+use crate::css::format_number;
+use crate::style::{Deg, Px};
+use crate::tween::Tween;
+
+/// A 2D transform as the matrix of the CSS `matrix(a, b, c, d, e, f)` function, which maps a point
+/// `(x, y)` to `(a * x + c * y + e, b * x + d * y + f)`.
+///
+/// Transforms are built by chaining transform functions in the order they are written in CSS,
+/// and written out with [`to_css()`](Transform2D::to_css). Animate them with [`tween_transform`],
+/// which interpolates the translation, rotation, scale and skew the transforms are made of instead
+/// of the numbers of the matrix, so a rotation keeps its size while it turns.
+/// ```
+/// # use leptos_animation::style::{Deg, Px};
+/// # use leptos_animation::transform::Transform2D;
+/// let card = Transform2D::new().translate(Px(100.0), Px(20.0)).rotate(Deg(90.0)).scale(2.0, 2.0);
+/// assert_eq!(card.to_css(), "matrix(0, 2, -2, 0, 100, 20)");
+/// assert_eq!(card.apply(1.0, 0.0), (100.0, 22.0));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform2D {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Transform2D::IDENTITY
+    }
+}
+
+/// The parts of a transform, see <https://www.w3.org/TR/css-transforms-1/#decomposing-a-2d-matrix>
+#[derive(Clone, Copy)]
+struct Decomposed {
+    translate: (f64, f64),
+    scale: (f64, f64),
+    /// The rotation in degrees
+    angle: f64,
+    /// What is left of the matrix after removing the scale and rotation, the skew
+    m11: f64,
+    m12: f64,
+    m21: f64,
+    m22: f64,
+}
+
+impl Transform2D {
+    /// The transform that leaves everything in place
+    pub const IDENTITY: Transform2D = Transform2D::from_matrix(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+    /// The identity transform, to chain transform functions onto
+    pub const fn new() -> Transform2D {
+        Transform2D::IDENTITY
+    }
+
+    /// The transform of the CSS `matrix(a, b, c, d, e, f)` function
+    pub const fn from_matrix(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Transform2D {
+        Transform2D { a, b, c, d, e, f }
+    }
+
+    /// Applies `translate(x, y)` after the transform functions so far
+    pub fn translate(self, Px(x): Px, Px(y): Px) -> Transform2D {
+        self * Transform2D::from_matrix(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    /// Applies `rotate(angle)` after the transform functions so far
+    pub fn rotate(self, Deg(angle): Deg) -> Transform2D {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        self * Transform2D::from_matrix(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Applies `scale(x, y)` after the transform functions so far
+    pub fn scale(self, x: f64, y: f64) -> Transform2D {
+        self * Transform2D::from_matrix(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    /// Applies `skew(x, y)` after the transform functions so far
+    pub fn skew(self, Deg(x): Deg, Deg(y): Deg) -> Transform2D {
+        self * Transform2D::from_matrix(
+            1.0,
+            y.to_radians().tan(),
+            x.to_radians().tan(),
+            1.0,
+            0.0,
+            0.0,
+        )
+    }
+
+    /// Transforms the point `(x, y)`
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+
+    /// The transform as a CSS `matrix()` function, for the `transform` property
+    pub fn to_css(&self) -> String {
+        self.to_string()
+    }
+
+    fn decompose(&self) -> Decomposed {
+        let (mut row0x, mut row0y) = (self.a, self.b);
+        let (mut row1x, mut row1y) = (self.c, self.d);
+        let mut scale = (row0x.hypot(row0y), row1x.hypot(row1y));
+
+        // A negative determinant means that one axis was flipped
+        if row0x * row1y - row0y * row1x < 0.0 {
+            if row0x < row1y {
+                scale.0 = -scale.0;
+            } else {
+                scale.1 = -scale.1;
+            }
+        }
+        if scale.0 != 0.0 {
+            row0x /= scale.0;
+            row0y /= scale.0;
+        }
+        if scale.1 != 0.0 {
+            row1x /= scale.1;
+            row1y /= scale.1;
+        }
+
+        let angle = row0y.atan2(row0x);
+        if angle != 0.0 {
+            let (sn, cs) = (-row0y, row0x);
+            let (m11, m12, m21, m22) = (row0x, row0y, row1x, row1y);
+            row0x = cs * m11 + sn * m21;
+            row0y = cs * m12 + sn * m22;
+            row1x = -sn * m11 + cs * m21;
+            row1y = -sn * m12 + cs * m22;
+        }
+
+        Decomposed {
+            translate: (self.e, self.f),
+            scale,
+            angle: angle.to_degrees(),
+            m11: row0x,
+            m12: row0y,
+            m21: row1x,
+            m22: row1y,
+        }
+    }
+
+    fn recompose(parts: &Decomposed) -> Transform2D {
+        let (sin, cos) = parts.angle.to_radians().sin_cos();
+        // The rotation applied to the rows of the skew
+        let (row0x, row0y) = (
+            cos * parts.m11 + sin * parts.m21,
+            cos * parts.m12 + sin * parts.m22,
+        );
+        let (row1x, row1y) = (
+            -sin * parts.m11 + cos * parts.m21,
+            -sin * parts.m12 + cos * parts.m22,
+        );
+        Transform2D::from_matrix(
+            row0x * parts.scale.0,
+            row0y * parts.scale.0,
+            row1x * parts.scale.1,
+            row1y * parts.scale.1,
+            parts.translate.0,
+            parts.translate.1,
+        )
+    }
+}
+
+/// Applies `rhs` before `self`, like `transform: self rhs` in CSS
+impl Mul for Transform2D {
+    type Output = Transform2D;
+
+    fn mul(self, rhs: Transform2D) -> Transform2D {
+        Transform2D::from_matrix(
+            self.a * rhs.a + self.c * rhs.b,
+            self.b * rhs.a + self.d * rhs.b,
+            self.a * rhs.c + self.c * rhs.d,
+            self.b * rhs.c + self.d * rhs.d,
+            self.a * rhs.e + self.c * rhs.f + self.e,
+            self.b * rhs.e + self.d * rhs.f + self.f,
+        )
+    }
+}
+
+impl fmt::Display for Transform2D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, translate_y] =
+            [self.a, self.b, self.c, self.d, self.e, self.f].map(format_number);
+        write!(f, "matrix({a}, {b}, {c}, {d}, {e}, {translate_y})")
+    }
+}
+
+/// Interpolates between two transforms the way CSS interpolates transforms that it can't match
+/// function by function: both are decomposed into a translation, scale, rotation and skew, which
+/// are interpolated separately and composed again. The rotation turns the shorter way around.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::style::{Deg, Px};
+/// # use leptos_animation::transform::{tween_transform, Transform2D};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let resting = Transform2D::new();
+/// let tilted = Transform2D::new().translate(Px(40.0), Px(0.0)).rotate(Deg(120.0));
+/// // Halfway the card has turned by 60° without shrinking, as the mix of the matrices would
+/// let halfway = tween_transform(&resting, &tilted, 0.5);
+/// assert_eq!(halfway.to_css(), "matrix(0.5, 0.866, -0.866, 0.5, 20, 0)");
+///
+/// let (tilt, set_tilt) = signal(false);
+/// let card = AnimatedSignal::new(
+///     move || if tilt.get() { tilted } else { resting }.into(),
+///     tween_transform,
+/// );
+/// # let _html =
+/// view! { <div style:transform=move || card.get().to_css()></div> }
+/// # .to_html();
+/// ```
+pub fn tween_transform(from: &Transform2D, to: &Transform2D, progress: f64) -> Transform2D {
+    let mut from = from.decompose();
+    let mut to = to.decompose();
+
+    // Turn one flipped axis on each side into a rotation
+    if (from.scale.0 < 0.0 && to.scale.1 < 0.0) || (from.scale.1 < 0.0 && to.scale.0 < 0.0) {
+        from.scale = (-from.scale.0, -from.scale.1);
+        from.angle += if from.angle < 0.0 { 180.0 } else { -180.0 };
+    }
+    // Don't rotate the long way around
+    if from.angle == 0.0 {
+        from.angle = 360.0;
+    }
+    if to.angle == 0.0 {
+        to.angle = 360.0;
+    }
+    if (from.angle - to.angle).abs() > 180.0 {
+        if from.angle > to.angle {
+            from.angle -= 360.0;
+        } else {
+            to.angle -= 360.0;
+        }
+    }
+
+    let mix = |from: f64, to: f64| from.tween(&to, progress);
+    Transform2D::recompose(&Decomposed {
+        translate: (
+            mix(from.translate.0, to.translate.0),
+            mix(from.translate.1, to.translate.1),
+        ),
+        scale: (mix(from.scale.0, to.scale.0), mix(from.scale.1, to.scale.1)),
+        angle: mix(from.angle, to.angle),
+        m11: mix(from.m11, to.m11),
+        m12: mix(from.m12, to.m12),
+        m21: mix(from.m21, to.m21),
+        m22: mix(from.m22, to.m22),
+    })
+}
+
+impl Tween for Transform2D {
+    type Interpolated = Transform2D;
+
+    fn tween(&self, other: &Self, progress: f64) -> Transform2D {
+        tween_transform(self, other, progress)
+    }
+}
+
+impl Transform2D {
+    fn zip(self, rhs: Transform2D, f: impl Fn(f64, f64) -> f64) -> Transform2D {
+        Transform2D::from_matrix(
+            f(self.a, rhs.a),
+            f(self.b, rhs.b),
+            f(self.c, rhs.c),
+            f(self.d, rhs.d),
+            f(self.e, rhs.e),
+            f(self.f, rhs.f),
+        )
+    }
+}
+
+impl Add for Transform2D {
+    type Output = Transform2D;
+
+    fn add(self, rhs: Transform2D) -> Transform2D {
+        self.zip(rhs, |a, b| a + b)
+    }
+}
+
+impl Sub for Transform2D {
+    type Output = Transform2D;
+
+    fn sub(self, rhs: Transform2D) -> Transform2D {
+        self.zip(rhs, |a, b| a - b)
+    }
+}
+
+impl Mul<f64> for Transform2D {
+    type Output = Transform2D;
+
+    fn mul(self, rhs: f64) -> Transform2D {
+        self.zip(self, |a, _| a * rhs)
+    }
+}
@@ -0,0 +1,199 @@
+//! Values that know how to interpolate themselves, so animated signals don't need a tween function.
+
+use std::ops::Sub;
+use std::time::Duration;
+
+/// Derives [`Tween`] for a struct whose fields interpolate into their own type, such as `f64` and
+/// `f32` fields. Animated signals also need the `Sub` implementation of additive animations, which
+/// `#[tween(sub)]` derives field by field. Available with the `derive` feature.
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::AnimatedSignal;
+/// # use leptos_animation::tween::Tween;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// #[derive(Tween, Clone, Copy, Debug, PartialEq)]
+/// #[tween(sub)]
+/// struct Position {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// let halfway = Position { x: 0.0, y: 0.0 }.tween(&Position { x: 10.0, y: 20.0 }, 0.5);
+/// assert_eq!(halfway, Position { x: 5.0, y: 10.0 });
+///
+/// let (position, set_position) = signal(Position { x: 0.0, y: 0.0 });
+/// let animated_position = AnimatedSignal::new_tweenable(move || position.get().into());
+/// ```
+#[cfg(feature = "derive")]
+pub use leptos_animation_derive::Tween;
+
+use crate::animation_target::AnimationSource;
+use crate::AnimatedSignal;
+
+/// A value that can be interpolated towards another value of the same type.
+///
+/// Floats interpolate into their own type. Integers interpolate as `f64`, so the values in between
+/// are not rounded, and durations as `f64` seconds, or as [`TimeDelta`](crate::time::TimeDelta)s
+/// with [`tween_duration`](crate::time::tween_duration). Tuples interpolate every element on its own,
+/// and so do `Vec`s, whose lengths may differ, see [`LengthMismatch`](crate::series::LengthMismatch).
+///
+/// Additive animations add up the offsets of interrupted animations, so animated signals also need
+/// `Sub` for the values in between. Values made of several numbers, such as transforms, add and
+/// subtract them number by number, after giving both values the same structure where they can
+/// differ. That is exact for a single animation and close enough while a new one takes over.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos_animation::tween::Tween;
+/// assert_eq!(0.0.tween(&10.0, 0.25), 2.5);
+/// assert_eq!(3u8.tween(&4, 0.5), 3.5);
+/// assert_eq!((0.0, 100).tween(&(1.0, 200), 0.5), (0.5, 150.0));
+/// assert_eq!(Duration::from_secs(1).tween(&Duration::from_secs(3), 0.5), 2.0);
+///
+/// // Without the `derive` feature, the tween of a struct can be written in terms of the tweens of
+/// // its fields
+/// struct Label {
+///     position: (f64, f64),
+///     opacity: f32,
+/// }
+///
+/// fn tween_label(from: &Label, to: &Label, progress: f64) -> (f64, f64, f32) {
+///     let (x, y) = from.position.tween(&to.position, progress);
+///     (x, y, from.opacity.tween(&to.opacity, progress))
+/// }
+/// ```
+pub trait Tween {
+    /// The type of the values in between
+    type Interpolated;
+
+    /// The value at `progress` of the way from `self` to `other`, where 0.0 is `self` and 1.0 is
+    /// `other`. The progress can lie outside of 0.0 - 1.0 for easings that overshoot.
+    fn tween(&self, other: &Self, progress: f64) -> Self::Interpolated;
+}
+
+impl Tween for f64 {
+    type Interpolated = f64;
+
+    fn tween(&self, other: &Self, progress: f64) -> f64 {
+        (other - self) * progress + self
+    }
+}
+
+impl Tween for f32 {
+    type Interpolated = f32;
+
+    fn tween(&self, other: &Self, progress: f64) -> f32 {
+        (other - self) * progress as f32 + self
+    }
+}
+
+macro_rules! tween_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl Tween for $integer {
+                type Interpolated = f64;
+
+                fn tween(&self, other: &Self, progress: f64) -> f64 {
+                    (*self as f64).tween(&(*other as f64), progress)
+                }
+            }
+        )*
+    };
+}
+
+tween_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! tween_tuple {
+    ($($element:ident $index:tt),*) => {
+        impl<$($element: Tween),*> Tween for ($($element,)*) {
+            type Interpolated = ($($element::Interpolated,)*);
+
+            fn tween(&self, other: &Self, progress: f64) -> Self::Interpolated {
+                ($(self.$index.tween(&other.$index, progress),)*)
+            }
+        }
+    };
+}
+
+tween_tuple!(A 0, B 1);
+tween_tuple!(A 0, B 1, C 2);
+tween_tuple!(A 0, B 1, C 2, D 3);
+
+impl Tween for Duration {
+    type Interpolated = f64;
+
+    fn tween(&self, other: &Self, progress: f64) -> f64 {
+        self.as_secs_f64().tween(&other.as_secs_f64(), progress)
+    }
+}
+
+impl<T> AnimatedSignal<T, T::Interpolated>
+where
+    T: Tween + Clone + 'static,
+    T::Interpolated: Clone + Sub<T::Interpolated, Output = T::Interpolated>,
+{
+    /// Like [`AnimatedSignal::new()`], but the values are interpolated with their [`Tween`]
+    /// implementation instead of a tween function. Running animations are added up, so the
+    /// interpolated values have to support subtraction, which tuples don't.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use leptos::prelude::*;
+    /// # use leptos_animation::AnimatedSignal;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let (count, set_count) = signal(3u32);
+    /// let animated_count = AnimatedSignal::new_tweenable(move || count.get().into());
+    /// assert_eq!(animated_count.get_untracked(), 3.0);
+    ///
+    /// // The seconds left of a countdown
+    /// let (remaining, set_remaining) = signal(Duration::from_secs(30));
+    /// let animated_remaining = AnimatedSignal::new_tweenable(move || remaining.get().into());
+    /// assert_eq!(animated_remaining.get_untracked(), 30.0);
+    /// ```
+    #[track_caller]
+    pub fn new_tweenable<Marker>(
+        source: impl AnimationSource<T, Marker>,
+    ) -> AnimatedSignal<T, T::Interpolated> {
+        AnimatedSignal::new(source, T::tween)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn tweens_start_and_end_on_their_values(from in -1e6..1e6f64, to in -1e6..1e6f64) {
+            prop_assert_eq!(from.tween(&to, 0.0), from);
+            prop_assert!((from.tween(&to, 1.0) - to).abs() <= 1e-9);
+        }
+
+        #[test]
+        fn tweens_are_linear(from in -1e6..1e6f64, to in -1e6..1e6f64, progress in -1.0..2.0f64) {
+            let expected = from + (to - from) * progress;
+            prop_assert!((from.tween(&to, progress) - expected).abs() <= 1e-6);
+        }
+
+        #[test]
+        fn tweens_stay_in_between_their_values(
+            from in -1e6..1e6f64,
+            to in -1e6..1e6f64,
+            progress in 0.0..=1.0f64,
+        ) {
+            let value = from.tween(&to, progress);
+            prop_assert!(value >= from.min(to) - 1e-6 && value <= from.max(to) + 1e-6);
+        }
+
+        #[test]
+        fn tuples_tween_element_by_element(
+            from in (-1e3..1e3f64, 0..1000u32),
+            to in (-1e3..1e3f64, 0..1000u32),
+            progress in 0.0..=1.0f64,
+        ) {
+            let (a, b) = from.tween(&to, progress);
+            prop_assert_eq!(a, from.0.tween(&to.0, progress));
+            prop_assert_eq!(b, from.1.tween(&to.1, progress));
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Animations driven by a state machine, like Framer Motion's variants.
+
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use crate::tokens::{DurationToken, EasingToken};
+use crate::{AnimationMode, AnimationTarget, Easing, RepeatMode};
+
+/// How to animate from one state to another
+#[derive(Clone, Debug)]
+pub struct Transition {
+    /// The duration of the animation, defaults to [`DurationToken::Normal`] of the
+    /// [`MotionTokens`](crate::tokens::MotionTokens) of the current scope
+    pub duration: Duration,
+
+    /// The easing of the animation, defaults to [`EasingToken::Standard`] of the
+    /// [`MotionTokens`](crate::tokens::MotionTokens) of the current scope
+    pub easing: Easing,
+
+    /// How to deal with animations that are still running, defaults to [`AnimationMode::Start`]
+    pub mode: AnimationMode,
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Transition::from_tokens(DurationToken::Normal, EasingToken::Standard)
+    }
+}
+
+/// The states a transition applies to, either a specific state or any state
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatePattern<K> {
+    /// Matches every state
+    Any,
+
+    /// Matches only this state
+    State(K),
+}
+
+impl<K> From<K> for StatePattern<K> {
+    fn from(state: K) -> Self {
+        StatePattern::State(state)
+    }
+}
+
+impl<K: PartialEq> StatePattern<K> {
+    fn matches(&self, state: &K) -> bool {
+        match self {
+            StatePattern::Any => true,
+            StatePattern::State(other) => other == state,
+        }
+    }
+
+    fn is_any(&self) -> bool {
+        matches!(self, StatePattern::Any)
+    }
+}
+
+/// Maps named states of a component to the values to animate towards in that state.
+///
+/// A component usually has a small set of states (such as idle, hovered, pressed and disabled) and
+/// each of them has a value for every animated property. Instead of computing targets from a
+/// handful of booleans, describe the value of each state once and drive the animation with a single
+/// state signal. Transitions between specific states can use their own duration and easing, for
+/// example a quick press after hovering.
+///
+/// Either side of a transition can be [`StatePattern::Any`] to cover several transitions at once,
+/// so a component library can ship a complete transition table for its states. When several
+/// transitions match, the most specific one is used: an exact match of both states first, then a
+/// transition from the previous state to any state, then one from any state to the next state.
+/// The default transition is used when nothing matches.
+///
+/// To animate several properties from the same state either use a struct of all properties as the
+/// value, or create one `Variants` per property and pass each of them the same state signal.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::variants::{StatePattern, Transition, Variants};
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// #[derive(Clone, Copy, PartialEq)]
+/// enum Button {
+///     Idle,
+///     Hover,
+///     Pressed,
+/// }
+///
+/// let (state, set_state) = signal(Button::Idle);
+///
+/// let scale = Variants::new()
+///     .variant(Button::Idle, 1.0)
+///     .variant(Button::Hover, 1.05)
+///     .variant(Button::Pressed, 0.95)
+///     .transition(Button::Hover, Button::Pressed, Transition {
+///         duration: Duration::from_millis(80),
+///         easing: easing::QUAD_OUT,
+///         mode: AnimationMode::ReplaceOrStart,
+///     })
+///     // Settle slowly after letting go of the button, whichever state comes next
+///     .transition(Button::Pressed, StatePattern::Any, Transition {
+///         duration: Duration::from_millis(400),
+///         easing: easing::BACK_OUT,
+///         mode: AnimationMode::ReplaceOrStart,
+///     });
+/// assert_eq!(scale.transition_between(&Button::Pressed, &Button::Idle).duration, Duration::from_millis(400));
+/// assert_eq!(scale.transition_between(&Button::Idle, &Button::Hover).duration, Duration::from_millis(300));
+///
+/// let animated_scale = AnimatedSignal::new(scale.source(state), tween_default);
+///
+/// set_state.set(Button::Hover);
+/// ```
+///
+/// States without a variant keep the value of the state before them:
+/// ```
+/// # use leptos::prelude::*;
+/// # use leptos_animation::*;
+/// # use leptos_animation::variants::Variants;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// let (state, set_state) = signal("idle");
+/// let opacity = Variants::new().variant("idle", 1.0).variant("hidden", 0.0);
+/// let source = opacity.source(state);
+///
+/// set_state.set("hidden");
+/// assert_eq!(source.get().target, 0.0);
+/// set_state.set("loading");
+/// assert_eq!(source.get().target, 0.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Variants<K, T> {
+    values: Vec<(K, T)>,
+    default_transition: Transition,
+    transitions: Vec<(StatePattern<K>, StatePattern<K>, Transition)>,
+}
+
+impl<K, T> Default for Variants<K, T> {
+    fn default() -> Self {
+        Variants {
+            values: Vec::new(),
+            default_transition: Transition::default(),
+            transitions: Vec::new(),
+        }
+    }
+}
+
+impl<K, T> Variants<K, T>
+where
+    K: Clone + PartialEq + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    /// Variants without any states
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value to animate towards when in `state`
+    pub fn variant(mut self, state: K, value: T) -> Self {
+        match self.values.iter_mut().find(|(other, _)| *other == state) {
+            Some((_, existing)) => *existing = value,
+            None => self.values.push((state, value)),
+        }
+        self
+    }
+
+    /// Sets the transition used between states that have no specific transition
+    pub fn default_transition(mut self, transition: Transition) -> Self {
+        self.default_transition = transition;
+        self
+    }
+
+    /// Sets the transition used when going from state `from` to state `to`, either of which can be
+    /// [`StatePattern::Any`]
+    pub fn transition(
+        mut self,
+        from: impl Into<StatePattern<K>>,
+        to: impl Into<StatePattern<K>>,
+        transition: Transition,
+    ) -> Self {
+        let (from, to) = (from.into(), to.into());
+        if from.is_any() && to.is_any() {
+            self.default_transition = transition;
+            return self;
+        }
+        self.transitions
+            .retain(|(other_from, other_to, _)| *other_from != from || *other_to != to);
+        self.transitions.push((from, to, transition));
+        self
+    }
+
+    /// Sets all transitions of a table at once, see [`Variants::transition()`]
+    pub fn transitions<F, To>(
+        mut self,
+        table: impl IntoIterator<Item = (F, To, Transition)>,
+    ) -> Self
+    where
+        F: Into<StatePattern<K>>,
+        To: Into<StatePattern<K>>,
+    {
+        for (from, to, transition) in table {
+            self = self.transition(from, to, transition);
+        }
+        self
+    }
+
+    /// The value of `state`, `None` if no variant was set for it
+    pub fn value(&self, state: &K) -> Option<&T> {
+        self.values
+            .iter()
+            .find_map(|(other, value)| (other == state).then_some(value))
+    }
+
+    /// The transition used when going from state `from` to state `to`
+    pub fn transition_between(&self, from: &K, to: &K) -> Transition {
+        self.transitions
+            .iter()
+            .filter(|(other_from, other_to, _)| other_from.matches(from) && other_to.matches(to))
+            // Prefer exact matches, then a specific previous state over a specific next state
+            .min_by_key(|(other_from, other_to, _)| (other_from.is_any(), other_to.is_any()))
+            .map(|(_, _, transition)| transition.clone())
+            .unwrap_or_else(|| self.default_transition.clone())
+    }
+
+    /// The animation targets for following `state`, for use as the source of an
+    /// [`AnimatedSignal`](crate::AnimatedSignal)
+    ///
+    /// A state without a variant keeps the value of the state before it, and a signal that starts
+    /// in such a state starts at the value of the first variant.
+    ///
+    /// # Panics
+    /// Panics if no variant was set at all.
+    #[track_caller]
+    pub fn source(&self, state: impl Into<Signal<K>>) -> Signal<AnimationTarget<T>> {
+        assert!(!self.values.is_empty(), "No variant was set");
+        let variants = self.clone();
+        let state = state.into();
+
+        // The memo keeps track of the previous state to find the transition that applies
+        let target = Memo::new_with_compare(
+            move |previous: Option<&(K, AnimationTarget<T>)>| {
+                let to = state.get();
+                let transition = match previous {
+                    Some((from, _)) => variants.transition_between(from, &to),
+                    None => variants.default_transition.clone(),
+                };
+                let value = variants
+                    .value(&to)
+                    .or(previous.map(|(_, previous)| &previous.target))
+                    .unwrap_or(&variants.values[0].1);
+                let target = AnimationTarget {
+                    target: value.clone(),
+                    duration: transition.duration,
+                    easing: transition.easing,
+                    delay: Duration::ZERO,
+                    mode: transition.mode,
+                    keyframes: Vec::new(),
+                    repeat: RepeatMode::Count(1),
+                    on_complete: None,
+                    relative: None,
+                };
+                (to, target)
+            },
+            |previous, next| previous.map(|(state, _)| state) != next.map(|(state, _)| state),
+        );
+
+        Signal::derive(move || target.with(|(_, target)| target.clone()))
+    }
+}
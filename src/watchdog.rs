@@ -0,0 +1,109 @@
+//! Catching animated signals that never stop animating.
+
+#[cfg(not(feature = "disable-animations"))]
+use std::panic::Location;
+use std::time::Duration;
+
+#[cfg(not(feature = "disable-animations"))]
+use instant::Instant;
+#[cfg(not(feature = "disable-animations"))]
+use leptos::logging::debug_warn;
+use leptos::prelude::*;
+
+use crate::AnimatedSignal;
+
+/// An `AnimationWatchdog` flags animated signals that have been animating without a break for
+/// longer than a threshold, by logging a warning with the name of the signal in debug builds. The
+/// usual cause is a source that changes on every frame, such as the pointer position, with a mode
+/// that starts a new animation for every change, so the signal never settles and keeps the page
+/// busy.
+///
+/// Like an [`AnimationBudget`](crate::budget::AnimationBudget) the watchdog applies to all animated
+/// signals created in the scope where it is provided and all of its child scopes. Signals are named
+/// with [`AnimatedSignal::with_name()`], otherwise the warning shows where they were created.
+/// ```
+/// # use std::time::Duration;
+/// # use leptos::prelude::*;
+/// # use leptos_animation::{AnimatedSignal, tween_default};
+/// # use leptos_animation::watchdog::AnimationWatchdog;
+/// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+/// // At the root of the application
+/// AnimationWatchdog::provide(Duration::from_secs(10));
+///
+/// let (x, set_x) = signal(0.0);
+/// let animated_x = AnimatedSignal::new(move || x.get().into(), tween_default).with_name("cursor x");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct AnimationWatchdog {
+    threshold: Duration,
+}
+
+impl AnimationWatchdog {
+    /// Provides a watchdog that flags signals animating for longer than `threshold` for this scope
+    /// and all child scopes
+    pub fn provide(threshold: Duration) -> AnimationWatchdog {
+        let watchdog = AnimationWatchdog { threshold };
+        provide_context(watchdog);
+        watchdog
+    }
+
+    /// How long a signal may animate without a break before it is flagged
+    pub fn threshold(&self) -> Duration {
+        self.threshold
+    }
+}
+
+/// What the watchdog knows about a single animated signal
+#[cfg(not(feature = "disable-animations"))]
+pub(crate) struct Watch {
+    name: Option<String>,
+    location: &'static Location<'static>,
+    animating_since: Option<Instant>,
+    flagged: bool,
+}
+
+#[cfg(not(feature = "disable-animations"))]
+impl Watch {
+    pub(crate) fn new(location: &'static Location<'static>) -> Watch {
+        Watch {
+            name: None,
+            location,
+            animating_since: None,
+            flagged: false,
+        }
+    }
+
+    /// Updates the watch on an animation frame and flags the signal once it exceeds the threshold
+    pub(crate) fn check(&mut self, watchdog: AnimationWatchdog, animating: bool, now: Instant) {
+        if !animating {
+            self.animating_since = None;
+            self.flagged = false;
+            return;
+        }
+        let since = *self.animating_since.get_or_insert(now);
+        let elapsed = now.saturating_duration_since(since);
+        if elapsed > watchdog.threshold && !self.flagged {
+            self.flagged = true;
+            let name = match &self.name {
+                Some(name) => format!("\"{name}\""),
+                None => format!("created at {}", self.location),
+            };
+            debug_warn!(
+                "Animated signal {name} has been animating for {elapsed:?} without a break. If its source changes on every frame, use AnimationMode::ReplaceOrStart or follow() so it does not start a new animation every time"
+            );
+        }
+    }
+}
+
+impl<T, I> AnimatedSignal<T, I> {
+    /// Names this signal in the warnings of the [`AnimationWatchdog`]
+    pub fn with_name(self, name: impl Into<String>) -> Self {
+        // Signals without animations have nothing to apply the setting to
+        #[cfg(not(feature = "disable-animations"))]
+        self.watch
+            .update_value(|watch| watch.name = Some(name.into()));
+        #[cfg(feature = "disable-animations")]
+        let _ = name;
+        self
+    }
+}
@@ -0,0 +1,93 @@
+use leptos::prelude::*;
+use leptos::wasm_bindgen::closure::Closure;
+use leptos::wasm_bindgen::JsCast;
+
+use crate::scheduler::WebScheduler;
+use crate::AnimationContext;
+
+impl AnimationContext {
+    /// Sets up an AnimationContext for this scope and all child scopes whose animations run in
+    /// another window, such as a same-origin iframe or a popup opened with `window.open()`.
+    ///
+    /// Browsers only deliver animation frames to windows that are being shown, so animations
+    /// that are rendered into an iframe (for example the preview pane of an editor) have to use
+    /// the animation frames of that iframe instead of those of the window of the application.
+    /// The [`visible()`](AnimationContext::visible) signal follows the visibility of the
+    /// document of `window` as well, and the animations wait while it is hidden.
+    ///
+    /// ```no_run
+    /// # use leptos::prelude::*;
+    /// # use leptos::wasm_bindgen::JsCast;
+    /// # use leptos_animation::AnimationContext;
+    /// # let harness = leptos_animation::testing::TestAnimationHarness::new();
+    /// let iframe: web_sys::HtmlIFrameElement = document()
+    ///     .get_element_by_id("preview")
+    ///     .unwrap()
+    ///     .unchecked_into();
+    /// let preview_window = iframe.content_window().unwrap();
+    ///
+    /// // Animations created in this scope are driven by the animation frames of the iframe
+    /// AnimationContext::provide_for_window(preview_window);
+    /// ```
+    pub fn provide_for_window(window: web_sys::Window) -> AnimationContext {
+        Self::provide_in(
+            Some(window.clone()),
+            Box::new(WebScheduler::for_window(window)),
+        )
+    }
+
+    /// Whether the document the animations of this context are shown in is visible.
+    /// Animation frames are paused by the browser while it is hidden, for example when the user
+    /// switched to another tab or minimized the popup window. The time of the animations stands
+    /// still until the document is shown again, so they continue where they were instead of
+    /// jumping ahead, like with [`pause_all()`](AnimationContext::pause_all).
+    pub fn visible(&self) -> Signal<bool> {
+        self.visible.into()
+    }
+
+    /// Stops the clock of this context while its document is hidden
+    fn set_visible(&self, visible: bool) {
+        self.visible.set(visible);
+        let real_now = self.steady_now();
+        let started = self
+            .clock
+            .try_update_value(|clock| clock.set_hidden(real_now, !visible));
+        if started == Some(true) && self.animating.get_value() {
+            self.request_frame();
+        }
+    }
+
+    /// Keeps the `visible` signal and the clock in sync with the visibility of the document of
+    /// the window
+    pub(crate) fn track_visibility(&self) {
+        #[cfg(target_arch = "wasm32")]
+        let window = self.window.get_value().or_else(web_sys::window);
+        #[cfg(not(target_arch = "wasm32"))]
+        let window = self.window.get_value();
+
+        let Some(document) = window.and_then(|window| window.document()) else {
+            return;
+        };
+
+        let context = *self;
+        context.set_visible(!document.hidden());
+        let listener = Closure::<dyn Fn()>::new({
+            let document = document.clone();
+            move || context.set_visible(!document.hidden())
+        });
+        let _ = document.add_event_listener_with_callback(
+            "visibilitychange",
+            listener.as_ref().unchecked_ref(),
+        );
+
+        let listener = StoredValue::new_local((document, listener));
+        on_cleanup(move || {
+            listener.try_with_value(|(document, listener)| {
+                let _ = document.remove_event_listener_with_callback(
+                    "visibilitychange",
+                    listener.as_ref().unchecked_ref(),
+                );
+            });
+        });
+    }
+}
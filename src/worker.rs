@@ -0,0 +1,252 @@
+//! Plumbing to run animations inside a dedicated worker that renders into an `OffscreenCanvas`.
+//!
+//! Heavy visualizations can move both their animations and their drawing off the main thread so
+//! they never cause jank in the rest of the UI. The worker sets up its own reactive owner and
+//! [`AnimationContext`](crate::AnimationContext), which automatically schedules frames with the
+//! `requestAnimationFrame()` of the worker (see [`WorkerScheduler`](crate::scheduler::WorkerScheduler)).
+//!
+//! The main thread sends new targets with a [`TargetSender`], and the worker receives them as a
+//! signal of [`AnimationTarget`]s with [`receive_target()`]. Functions can't be sent to a worker,
+//! so the easing of the received targets is chosen on the worker side. Keyframes are not sent.
+//!
+//! On the main thread:
+//! ```no_run
+//! # use std::time::Duration;
+//! # use leptos_animation::*;
+//! # use leptos_animation::worker::TargetSender;
+//! let worker = web_sys::Worker::new("./chart_worker.js").unwrap();
+//! let sender = TargetSender::new(worker);
+//!
+//! // Zoom in the chart over half a second
+//! sender.send("zoom", &AnimationTarget {
+//!     target: 2.0,
+//!     duration: Duration::from_millis(500),
+//!     easing: easing::LINEAR, // Not sent, the worker decides on the easing
+//!     delay: Duration::ZERO,
+//!     mode: AnimationMode::ReplaceOrStart,
+//!     keyframes: Vec::new(), // Not sent either
+//!     repeat: RepeatMode::Count(1),
+//!     on_complete: None,
+//!     relative: None,
+//! });
+//! ```
+//!
+//! In the worker:
+//! ```no_run
+//! # use leptos::prelude::*;
+//! # use leptos_animation::*;
+//! # use leptos_animation::worker::receive_target;
+//! let owner = Owner::new();
+//! owner.set();
+//! AnimationContext::provide();
+//!
+//! let zoom = receive_target("zoom", 1.0, easing::CUBIC_OUT);
+//! let animated_zoom = AnimatedSignal::new(move || zoom.get(), tween_default);
+//! // Redraw the OffscreenCanvas whenever `animated_zoom` changes
+//! ```
+
+use std::time::Duration;
+
+use leptos::prelude::*;
+use leptos::wasm_bindgen::closure::Closure;
+use leptos::wasm_bindgen::{JsCast, JsValue};
+
+use crate::rect::Rect;
+use crate::{AnimationMode, AnimationTarget, Easing, RepeatMode};
+
+/// A value that can be sent to a worker as part of an [`AnimationTarget`]
+pub trait WorkerValue: Sized {
+    /// Converts the value into something that can be posted to a worker
+    fn to_js(&self) -> JsValue;
+
+    /// Converts a posted value back, `None` if it has the wrong shape
+    fn from_js(value: &JsValue) -> Option<Self>;
+}
+
+impl WorkerValue for f64 {
+    fn to_js(&self) -> JsValue {
+        JsValue::from_f64(*self)
+    }
+
+    fn from_js(value: &JsValue) -> Option<Self> {
+        value.as_f64()
+    }
+}
+
+impl WorkerValue for f32 {
+    fn to_js(&self) -> JsValue {
+        JsValue::from_f64(*self as f64)
+    }
+
+    fn from_js(value: &JsValue) -> Option<Self> {
+        value.as_f64().map(|value| value as f32)
+    }
+}
+
+impl WorkerValue for (f64, f64) {
+    fn to_js(&self) -> JsValue {
+        js_sys::Array::of2(&self.0.to_js(), &self.1.to_js()).into()
+    }
+
+    fn from_js(value: &JsValue) -> Option<Self> {
+        let array: &js_sys::Array = value.dyn_ref()?;
+        Some((array.get(0).as_f64()?, array.get(1).as_f64()?))
+    }
+}
+
+impl WorkerValue for Rect {
+    fn to_js(&self) -> JsValue {
+        js_sys::Array::of4(
+            &self.x.to_js(),
+            &self.y.to_js(),
+            &self.width.to_js(),
+            &self.height.to_js(),
+        )
+        .into()
+    }
+
+    fn from_js(value: &JsValue) -> Option<Self> {
+        let array: &js_sys::Array = value.dyn_ref()?;
+        Some(Rect::new(
+            array.get(0).as_f64()?,
+            array.get(1).as_f64()?,
+            array.get(2).as_f64()?,
+            array.get(3).as_f64()?,
+        ))
+    }
+}
+
+fn mode_to_str(mode: AnimationMode) -> &'static str {
+    match mode {
+        AnimationMode::Start => "start",
+        AnimationMode::ReplaceOrStart => "replace_or_start",
+        AnimationMode::ReplaceOrSnap => "replace_or_snap",
+        AnimationMode::Snap => "snap",
+        AnimationMode::ReplaceWithVelocity => "replace_with_velocity",
+    }
+}
+
+fn mode_from_str(mode: &str) -> Option<AnimationMode> {
+    match mode {
+        "start" => Some(AnimationMode::Start),
+        "replace_or_start" => Some(AnimationMode::ReplaceOrStart),
+        "replace_or_snap" => Some(AnimationMode::ReplaceOrSnap),
+        "snap" => Some(AnimationMode::Snap),
+        "replace_with_velocity" => Some(AnimationMode::ReplaceWithVelocity),
+        _ => None,
+    }
+}
+
+fn repeat_to_js(repeat: RepeatMode) -> f64 {
+    match repeat.count() {
+        Some(count) => count as f64,
+        None => f64::INFINITY,
+    }
+}
+
+fn repeat_from_js(repeat: f64, alternate: bool) -> RepeatMode {
+    match (repeat.is_finite(), alternate) {
+        (true, false) => RepeatMode::Count(repeat.max(0.0) as u32),
+        (false, false) => RepeatMode::Infinite,
+        (true, true) => RepeatMode::Alternate(repeat.max(0.0) as u32),
+        (false, true) => RepeatMode::AlternateInfinite,
+    }
+}
+
+/// Sends animation targets from the main thread to a worker
+pub struct TargetSender {
+    worker: web_sys::Worker,
+}
+
+impl TargetSender {
+    /// Sends targets to `worker`
+    pub fn new(worker: web_sys::Worker) -> TargetSender {
+        TargetSender { worker }
+    }
+
+    /// Sends a new target to the [`receive_target()`] signal of `channel` in the worker.
+    /// The easing of the target is not sent, the worker uses its own.
+    pub fn send<T: WorkerValue>(&self, channel: &str, target: &AnimationTarget<T>) {
+        let message = js_sys::Object::new();
+        let set = |key: &str, value: JsValue| {
+            let _ = js_sys::Reflect::set(&message, &JsValue::from_str(key), &value);
+        };
+        set("channel", JsValue::from_str(channel));
+        set("target", target.target.to_js());
+        set(
+            "duration",
+            JsValue::from_f64(target.duration.as_secs_f64() * 1000.0),
+        );
+        set(
+            "delay",
+            JsValue::from_f64(target.delay.as_secs_f64() * 1000.0),
+        );
+        set("mode", JsValue::from_str(mode_to_str(target.mode)));
+        set("repeat", JsValue::from_f64(repeat_to_js(target.repeat)));
+        set("alternate", JsValue::from_bool(target.repeat.alternates()));
+
+        self.worker
+            .post_message(&message)
+            .expect("Failed to post animation target to worker");
+    }
+}
+
+/// Parses a message posted by [`TargetSender::send()`] if it belongs to `channel`, `None` for
+/// messages with durations or delays that don't fit a [`Duration`]
+fn parse_message<T: WorkerValue>(
+    message: &JsValue,
+    channel: &str,
+    easing: Easing,
+) -> Option<AnimationTarget<T>> {
+    let get = |key: &str| js_sys::Reflect::get(message, &JsValue::from_str(key)).ok();
+    if get("channel")?.as_string()? != channel {
+        return None;
+    }
+    let duration = get("duration")?.as_f64()?.max(0.0) / 1000.0;
+    let delay = get("delay")?.as_f64()?.max(0.0) / 1000.0;
+    Some(AnimationTarget {
+        target: T::from_js(&get("target")?)?,
+        duration: Duration::try_from_secs_f64(duration).ok()?,
+        easing,
+        delay: Duration::try_from_secs_f64(delay).ok()?,
+        mode: mode_from_str(&get("mode")?.as_string()?)?,
+        keyframes: Vec::new(),
+        repeat: repeat_from_js(get("repeat")?.as_f64()?, get("alternate")?.as_bool()?),
+        on_complete: None,
+        relative: None,
+    })
+}
+
+/// Receives the targets sent to `channel` by a [`TargetSender`] on the main thread, for use in
+/// the source of an [`AnimatedSignal`](crate::AnimatedSignal) inside the worker.
+/// The signal holds `initial` until the first target arrives.
+pub fn receive_target<T>(
+    channel: impl Into<String>,
+    initial: T,
+    easing: Easing,
+) -> Signal<AnimationTarget<T>, LocalStorage>
+where
+    T: WorkerValue + Clone + 'static,
+{
+    let channel = channel.into();
+    let target = RwSignal::new_local((initial, easing.clone()).into());
+
+    let listener =
+        Closure::<dyn Fn(web_sys::MessageEvent)>::new(move |event: web_sys::MessageEvent| {
+            if let Some(new_target) = parse_message(&event.data(), &channel, easing.clone()) {
+                target.set(new_target);
+            }
+        });
+    let global: web_sys::EventTarget = js_sys::global().unchecked_into();
+    let _ = global.add_event_listener_with_callback("message", listener.as_ref().unchecked_ref());
+
+    let listener = StoredValue::new_local((global, listener));
+    on_cleanup(move || {
+        listener.try_with_value(|(global, listener)| {
+            let _ = global
+                .remove_event_listener_with_callback("message", listener.as_ref().unchecked_ref());
+        });
+    });
+
+    target.into()
+}
@@ -0,0 +1,114 @@
+use std::ops::{Add, Mul, Sub};
+
+/// A value that wraps around after a `period`, like a hue, a heading or a longitude.
+///
+/// `Wrapping` implements the operations needed by [`tween_default`](crate::tween_default) in such a
+/// way that values are always interpolated along the shortest path around the period, and that
+/// additive animations are accumulated modulo the period. For example animating a hue from 350° to
+/// 10° takes the 20° path through 0° instead of going all the way around.
+/// ```
+/// # use leptos_animation::{tween_default, wrapping::Wrapping};
+/// let from = Wrapping::new(350.0, 360.0);
+/// let to = Wrapping::new(10.0, 360.0);
+///
+/// assert_eq!(tween_default(&from, &to, 0.25).value(), 355.0);
+/// assert_eq!(tween_default(&from, &to, 0.75).value(), 5.0);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Wrapping<T> {
+    value: T,
+    period: T,
+}
+
+/// The floating point types that can be used in a [`Wrapping`]
+pub trait WrappingValue:
+    Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    #[doc(hidden)]
+    fn rem_euclid(self, rhs: Self) -> Self;
+
+    #[doc(hidden)]
+    fn from_f64(value: f64) -> Self;
+}
+
+impl WrappingValue for f32 {
+    fn rem_euclid(self, rhs: Self) -> Self {
+        f32::rem_euclid(self, rhs)
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl WrappingValue for f64 {
+    fn rem_euclid(self, rhs: Self) -> Self {
+        f64::rem_euclid(self, rhs)
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+impl<T: WrappingValue> Wrapping<T> {
+    /// Creates a new value that wraps around after `period`
+    pub fn new(value: T, period: T) -> Self {
+        Wrapping { value, period }
+    }
+
+    /// The value, normalized to the range `0.0..period`
+    pub fn value(&self) -> T {
+        self.value.rem_euclid(self.period)
+    }
+
+    /// The period after which the value wraps around
+    pub fn period(&self) -> T {
+        self.period
+    }
+}
+
+impl<T: WrappingValue> PartialEq for Wrapping<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.period == other.period && self.value() == other.value()
+    }
+}
+
+/// The shortest signed difference between two values, in the range `-period / 2..period / 2`
+impl<T: WrappingValue> Sub for Wrapping<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let difference = (self.value - rhs.value).rem_euclid(self.period);
+        Wrapping {
+            value: if difference >= self.period * T::from_f64(0.5) {
+                difference - self.period
+            } else {
+                difference
+            },
+            period: self.period,
+        }
+    }
+}
+
+impl<T: WrappingValue> Add for Wrapping<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Wrapping {
+            value: (self.value + rhs.value).rem_euclid(self.period),
+            period: self.period,
+        }
+    }
+}
+
+impl<T: WrappingValue> Mul<f64> for Wrapping<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Wrapping {
+            value: self.value * T::from_f64(rhs),
+            period: self.period,
+        }
+    }
+}
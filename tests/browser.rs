@@ -0,0 +1,181 @@
+//! Animated signals driven by the animation frames of a real browser, run with
+//! `wasm-pack test --headless --firefox` (or `--chrome`). That builds every test target for
+//! wasm32, `wasm-pack test --headless --firefox --test browser` builds only this one.
+#![cfg(target_arch = "wasm32")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use leptos::prelude::*;
+use leptos::task::Executor;
+use leptos::wasm_bindgen::JsValue;
+use leptos_animation::*;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// An owner with an animation context driven by the animation frames of the browser
+fn setup() -> (Owner, AnimationContext) {
+    // Only the first test installs the executor that runs the effects
+    let _ = Executor::init_wasm_bindgen();
+    let owner = Owner::new();
+    owner.set();
+    (owner, AnimationContext::provide())
+}
+
+/// Resolves on the next animation frame of the window
+async fn next_frame() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let _ = window().request_animation_frame(&resolve);
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
+/// Resolves after `duration` has passed
+async fn sleep(duration: Duration) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let _ = window().set_timeout_with_callback_and_timeout_and_arguments_0(
+            &resolve,
+            duration.as_millis() as i32,
+        );
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
+/// Makes the document report `hidden` and tells the listeners about it, as switching tabs does
+fn set_document_hidden(hidden: bool) {
+    let descriptor = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&descriptor, &"value".into(), &JsValue::from_bool(hidden));
+    let _ = js_sys::Reflect::set(&descriptor, &"configurable".into(), &JsValue::TRUE);
+    js_sys::Object::define_property(&document(), &"hidden".into(), &descriptor);
+    let event = web_sys::Event::new("visibilitychange").unwrap();
+    let _ = document().dispatch_event(&event);
+}
+
+/// Reads `signal` in an effect, like the view of a component does
+fn observe(signal: Signal<f64, LocalStorage>) {
+    Effect::new(move || {
+        signal.get();
+    });
+}
+
+#[wasm_bindgen_test]
+async fn animations_run_on_animation_frames_until_they_settle() {
+    let (owner, _) = setup();
+    let (open, set_open) = signal(false);
+    let height: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+        move || {
+            (
+                if open.get() { 200.0 } else { 0.0 },
+                Duration::from_millis(200),
+            )
+                .into()
+        },
+        tween_default,
+    );
+    observe(*height);
+    next_frame().await;
+    assert_eq!(height.get_untracked(), 0.0);
+
+    set_open.set(true);
+    next_frame().await;
+    next_frame().await;
+    next_frame().await;
+    let value = height.get_untracked();
+    assert!(value > 0.0 && value < 200.0, "{value}");
+    assert!(height.is_animating().get_untracked());
+
+    height.finished().await;
+    assert_eq!(height.get_untracked(), 200.0);
+    assert!(!height.is_animating().get_untracked());
+    owner.cleanup();
+}
+
+#[wasm_bindgen_test]
+async fn disposed_owners_stop_requesting_frames() {
+    static EVALUATIONS: AtomicUsize = AtomicUsize::new(0);
+    fn counting_tween(from: &f64, to: &f64, progress: f64) -> f64 {
+        EVALUATIONS.fetch_add(1, Ordering::Relaxed);
+        tween_default(from, to, progress)
+    }
+
+    let (owner, _) = setup();
+    let (open, set_open) = signal(false);
+    let opacity: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+        move || (if open.get() { 1.0 } else { 0.0 }, Duration::from_secs(10)).into(),
+        counting_tween,
+    );
+    observe(*opacity);
+    set_open.set(true);
+    for _ in 0..5 {
+        next_frame().await;
+    }
+    assert!(EVALUATIONS.load(Ordering::Relaxed) > 0);
+
+    owner.cleanup();
+    next_frame().await;
+    let evaluations = EVALUATIONS.load(Ordering::Relaxed);
+    for _ in 0..10 {
+        next_frame().await;
+    }
+    assert_eq!(EVALUATIONS.load(Ordering::Relaxed), evaluations);
+}
+
+#[wasm_bindgen_test]
+async fn hidden_documents_hold_the_animations() {
+    let (owner, context) = setup();
+    let (open, set_open) = signal(false);
+    let width: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+        move || {
+            (
+                if open.get() { 100.0 } else { 0.0 },
+                Duration::from_millis(300),
+                easing::LINEAR,
+            )
+                .into()
+        },
+        tween_default,
+    );
+    observe(*width);
+    set_open.set(true);
+    next_frame().await;
+    next_frame().await;
+
+    set_document_hidden(true);
+    assert!(!context.visible().get_untracked());
+    let hidden_at = width.get_untracked();
+    sleep(Duration::from_millis(500)).await;
+    assert_eq!(width.get_untracked(), hidden_at);
+
+    // The animation continues where it was instead of jumping to its end
+    set_document_hidden(false);
+    assert!(context.visible().get_untracked());
+    next_frame().await;
+    next_frame().await;
+    let value = width.get_untracked();
+    assert!(value >= hidden_at && value < 100.0, "{value}");
+
+    width.finished().await;
+    assert_eq!(width.get_untracked(), 100.0);
+    owner.cleanup();
+}
+
+#[wasm_bindgen_test]
+async fn static_and_snapped_targets_need_no_frames() {
+    let (owner, _) = setup();
+    let (target, set_target) = signal(10.0);
+    let offset: AnimatedSignal<f64, f64> = AnimatedSignal::new(
+        move || (target.get(), AnimationMode::Snap).into(),
+        tween_default,
+    );
+    observe(*offset);
+    assert_eq!(offset.get_untracked(), 10.0);
+    assert!(!offset.is_animating().get_untracked());
+
+    set_target.set(50.0);
+    next_frame().await;
+    assert_eq!(offset.get_untracked(), 50.0);
+    assert!(!offset.is_animating().get_untracked());
+    owner.cleanup();
+}
@@ -0,0 +1,9 @@
+//! Compile tests of `#[derive(Tween)]`, run natively with the `derive` feature
+#![cfg(all(feature = "derive", not(target_arch = "wasm32")))]
+
+#[test]
+fn derive_tween() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/derive/pass/*.rs");
+    cases.compile_fail("tests/derive/fail/*.rs");
+}
@@ -0,0 +1,9 @@
+use leptos_animation::tween::Tween;
+
+#[derive(Tween)]
+enum Shape {
+    Circle(f64),
+    Square(f64),
+}
+
+fn main() {}
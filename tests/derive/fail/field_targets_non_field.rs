@@ -0,0 +1,9 @@
+use leptos_animation::fields::FieldTargets;
+
+#[derive(FieldTargets, Clone)]
+struct Label {
+    opacity: f64,
+    text: String,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use leptos_animation::fields::FieldTargets;
+
+#[derive(FieldTargets)]
+struct Nothing;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use leptos_animation::tween::Tween;
+
+#[derive(Tween)]
+struct Label {
+    opacity: f64,
+    text: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use leptos_animation::tween::Tween;
+
+#[derive(Tween, Clone, Copy)]
+struct Position {
+    x: f64,
+    y: f64,
+}
+
+fn main() {
+    let _ = Position { x: 1.0, y: 1.0 } - Position { x: 0.0, y: 0.0 };
+}
@@ -0,0 +1,10 @@
+use leptos_animation::tween::Tween;
+
+#[derive(Tween)]
+#[tween(add)]
+struct Position {
+    x: f64,
+    y: f64,
+}
+
+fn main() {}
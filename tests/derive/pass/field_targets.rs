@@ -0,0 +1,40 @@
+#![deny(warnings)]
+
+use leptos::prelude::*;
+use leptos_animation::fields::{animate_fields, FieldTargets};
+use leptos_animation::testing::TestAnimationHarness;
+use leptos_animation::{AnimationMode, AnimationTarget};
+
+#[derive(FieldTargets, Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub x: f64,
+    y: f64,
+}
+
+#[derive(FieldTargets, Clone, Copy, Debug, PartialEq)]
+struct Size(f64, f64);
+
+#[derive(FieldTargets, Clone, Copy, Debug, PartialEq)]
+struct Range<T: Copy> {
+    start: T,
+    end: T,
+}
+
+fn main() {
+    let _harness = TestAnimationHarness::new();
+
+    let position = animate_fields(|| PositionTargets {
+        x: AnimationTarget::from((1.0, AnimationMode::Snap)),
+        y: AnimationTarget::from(2.0),
+    });
+    assert_eq!(position.get_untracked(), Position { x: 1.0, y: 2.0 });
+
+    let size = animate_fields(|| SizeTargets(AnimationTarget::from(3.0), AnimationTarget::from(4.0)));
+    assert_eq!(size.get_untracked(), Size(3.0, 4.0));
+
+    let range = animate_fields(|| RangeTargets {
+        start: AnimationTarget::from(0.5),
+        end: AnimationTarget::from(1.5),
+    });
+    assert_eq!(range.get_untracked(), Range { start: 0.5, end: 1.5 });
+}
@@ -0,0 +1,17 @@
+#![deny(warnings)]
+
+use leptos_animation::tween::Tween;
+
+#[derive(Tween, Clone, Copy, Debug, PartialEq)]
+#[tween(sub)]
+struct Range<T> {
+    start: T,
+    end: T,
+}
+
+fn main() {
+    let from = Range { start: 0.0f32, end: 1.0 };
+    let to = Range { start: 1.0, end: 3.0 };
+    assert_eq!(from.tween(&to, 0.5), Range { start: 0.5, end: 2.0 });
+    assert_eq!(to - from, Range { start: 1.0, end: 2.0 });
+}
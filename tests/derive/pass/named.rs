@@ -0,0 +1,39 @@
+#![deny(warnings)]
+
+use std::ops::Sub;
+
+use leptos_animation::tween::Tween;
+
+#[derive(Tween, Clone, Copy, Debug, PartialEq)]
+#[tween(sub)]
+struct Position {
+    x: f64,
+    y: f32,
+}
+
+// Without `#[tween(sub)]` the struct can implement `Sub` itself
+#[derive(Tween, Clone, Copy, Debug, PartialEq)]
+struct Hue {
+    degrees: f64,
+}
+
+impl Sub for Hue {
+    type Output = Hue;
+
+    fn sub(self, rhs: Hue) -> Hue {
+        Hue {
+            degrees: (self.degrees - rhs.degrees).rem_euclid(360.0),
+        }
+    }
+}
+
+fn main() {
+    let from = Position { x: 0.0, y: 0.0 };
+    let to = Position { x: 10.0, y: 20.0 };
+    assert_eq!(from.tween(&to, 0.5), Position { x: 5.0, y: 10.0 });
+    assert_eq!(to - from, to);
+
+    let hue = Hue { degrees: 10.0 }.tween(&Hue { degrees: 30.0 }, 0.5);
+    assert_eq!(hue, Hue { degrees: 20.0 });
+    assert_eq!(Hue { degrees: 10.0 } - Hue { degrees: 30.0 }, Hue { degrees: 340.0 });
+}
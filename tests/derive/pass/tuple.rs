@@ -0,0 +1,12 @@
+#![deny(warnings)]
+
+use leptos_animation::tween::Tween;
+
+#[derive(Tween, Clone, Copy, Debug, PartialEq)]
+#[tween(sub)]
+struct Size(f64, f64);
+
+fn main() {
+    assert_eq!(Size(0.0, 10.0).tween(&Size(10.0, 30.0), 0.5), Size(5.0, 20.0));
+    assert_eq!(Size(10.0, 30.0) - Size(5.0, 10.0), Size(5.0, 20.0));
+}
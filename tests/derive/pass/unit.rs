@@ -0,0 +1,12 @@
+#![deny(warnings)]
+
+use leptos_animation::tween::Tween;
+
+#[derive(Tween, Clone, Copy, Debug, PartialEq)]
+#[tween(sub)]
+struct Nothing;
+
+fn main() {
+    assert_eq!(Nothing.tween(&Nothing, 0.5), Nothing);
+    assert_eq!(Nothing - Nothing, Nothing);
+}